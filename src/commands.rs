@@ -0,0 +1,59 @@
+pub mod add;
+pub mod analyze_plugins;
+pub mod api_snapshot;
+pub mod convert_build;
+pub mod inspect;
+pub mod lint;
+pub mod migrate;
+pub mod scan_bytecode;
+pub mod server;
+pub mod set_mc_version;
+pub mod template;
+pub mod upgrade;
+
+use std::env;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+
+use crate::errors::GeneratorError;
+use crate::parameter_reader::{get_parameters, take_flag};
+use crate::project_generator::file_change::FileChange;
+
+/// The `--project` directory, the current one by default.
+fn project_dir(args: &mut Vec<String>) -> Result<PathBuf, GeneratorError> {
+    match take_flag(args, "--project") {
+        Some(directory) => Ok(PathBuf::from(directory)),
+        None => env::current_dir()
+            .map_err(|error| GeneratorError::InvalidProject(String::from("."), error.to_string())),
+    }
+}
+
+/// Shows the diff of every change, then writes them once confirmed, `confirmed` skipping the question.
+/// Tells whether they were written.
+fn apply_changes<B: BufRead>(
+    project_dir: &Path,
+    changes: &[FileChange],
+    confirmed: bool,
+    input_buffer: &mut B,
+) -> Result<bool, GeneratorError> {
+    for change in changes {
+        println!("{}", change.diff());
+    }
+    let answer = match confirmed {
+        true => String::from("y"),
+        false => get_parameters(
+            &mut Vec::new(),
+            input_buffer,
+            "Apply these changes? [y/N]",
+            true,
+        )
+        .unwrap_or_default(),
+    };
+    if !answer.eq_ignore_ascii_case("y") && !answer.eq_ignore_ascii_case("yes") {
+        return Ok(false);
+    }
+    for change in changes {
+        change.write(project_dir)?;
+    }
+    Ok(true)
+}
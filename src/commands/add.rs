@@ -0,0 +1,139 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::commands::project_dir;
+use crate::errors::GeneratorError;
+use crate::parameter_reader::{take_flag, take_switch};
+use crate::project_generator::config_spec::parse_config_spec;
+use crate::project_generator::existing_project::ExistingProject;
+use crate::project_generator::library_catalog::find_library;
+use crate::project_generator::plugin_descriptor::{split_list, CommandSpec};
+use crate::project_generator::scaffold::{
+    add_command, add_config, add_dependency, add_listener, add_message, EventHandlerSpec,
+};
+
+pub fn execute(args: &mut Vec<String>) -> Result<String, GeneratorError> {
+    let project_dir = project_dir(args)?;
+    execute_in(&project_dir, args)
+}
+
+fn execute_in(project_dir: &Path, args: &mut Vec<String>) -> Result<String, GeneratorError> {
+    if args.is_empty() {
+        return Err(GeneratorError::UnknownCommand(String::from("add")));
+    }
+    let subcommand = args.remove(0);
+
+    match subcommand.as_str() {
+        "command" => {
+            let spec = CommandSpec {
+                description: take_flag(args, "--description"),
+                usage: take_flag(args, "--usage"),
+                aliases: split_list(&take_flag(args, "--aliases").unwrap_or_default()),
+                permission: take_flag(args, "--permission"),
+                permission_message: take_flag(args, "--permission-message"),
+            };
+            let name = argument(args, "name")?;
+            let mut project = ExistingProject::open(project_dir)?;
+            let class_name = add_command(&mut project, &name, spec)?;
+            Ok(format!("Command {} added in {}!", name, class_name))
+        }
+        "listener" => {
+            let class_name = take_flag(args, "--name");
+            let priority = take_flag(args, "--priority").unwrap_or_else(|| String::from("NORMAL"));
+            let ignore_cancelled = take_switch(args, "--ignore-cancelled");
+            let handlers = args
+                .drain(..)
+                .map(|event| EventHandlerSpec::parse(&event, &priority, ignore_cancelled))
+                .collect::<Result<Vec<_>, _>>()?;
+            let project = ExistingProject::open(project_dir)?;
+            let class_name = add_listener(&project, class_name.as_deref(), &handlers)?;
+            Ok(format!("Listener {} added!", class_name))
+        }
+        "config" => {
+            let class_name = take_flag(args, "--name");
+            let command = take_flag(args, "--command");
+            let spec = match take_flag(args, "--config") {
+                Some(spec) => spec,
+                None => argument(args, "config")?,
+            };
+            let entries = parse_config_spec(&spec)?;
+            let mut project = ExistingProject::open(project_dir)?;
+            let class_name = add_config(
+                &mut project,
+                &entries,
+                class_name.as_deref(),
+                command.as_deref(),
+            )?;
+            Ok(format!("Configuration {} added!", class_name))
+        }
+        "message" => {
+            let mut translations = BTreeMap::new();
+            while let Some(translation) = take_flag(args, "--translation") {
+                let (locale, text) = translation.split_once('=').ok_or_else(|| {
+                    GeneratorError::MissingParameter(format!("text of translation {}", translation))
+                })?;
+                translations.insert(locale.trim().to_string(), text.to_string());
+            }
+            let key = argument(args, "key")?;
+            let text = argument(args, "text")?;
+            let project = ExistingProject::open(project_dir)?;
+            let locales = add_message(&project, &key, &text, &translations)?;
+            Ok(format!("Message {} added to {}!", key, locales.join(", ")))
+        }
+        "dependency" => {
+            let hook = take_switch(args, "--hook");
+            let library = find_library(&argument(args, "library")?)?;
+            let mut project = ExistingProject::open(project_dir)?;
+            match add_dependency(&mut project, library, hook)? {
+                Some(hook_class) => Ok(format!(
+                    "Dependency {} added with {}!",
+                    library.name, hook_class
+                )),
+                None => Ok(format!("Dependency {} added!", library.name)),
+            }
+        }
+        _ => Err(GeneratorError::UnknownCommand(format!(
+            "add {}",
+            subcommand
+        ))),
+    }
+}
+
+fn argument(args: &mut Vec<String>, name: &str) -> Result<String, GeneratorError> {
+    if args.is_empty() {
+        Err(GeneratorError::MissingParameter(name.to_string()))
+    } else {
+        Ok(args.remove(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn add_should_reject_unknown_subcommand() {
+        // GIVEN an unknown kind of element
+        let mut args = vec![String::from("recipe")];
+
+        // WHEN we try to add it
+        let result = execute_in(Path::new("."), &mut args);
+
+        // THEN the command should be unknown
+        assert!(
+            matches!(result, Err(GeneratorError::UnknownCommand(command)) if command == "add recipe")
+        );
+    }
+
+    #[test]
+    fn add_command_should_require_a_name() {
+        // GIVEN no command name
+        let mut args = vec![String::from("command"), String::from("--usage=/home")];
+
+        // WHEN we try to add it
+        let result = execute_in(Path::new("."), &mut args);
+
+        // THEN the name should be reported as missing
+        assert!(matches!(result, Err(GeneratorError::MissingParameter(name)) if name == "name"));
+    }
+}
@@ -0,0 +1,46 @@
+use std::path::Path;
+
+use crate::errors::GeneratorError;
+use crate::parameter_reader::take_flag;
+use crate::project_generator::plugin_graph::{analyze_plugins, PluginGraph};
+use crate::project_generator::validation::invalid_parameter;
+
+pub fn execute(args: &mut Vec<String>) -> Result<String, GeneratorError> {
+    let format = take_flag(args, "--format").unwrap_or_else(|| String::from("text"));
+    if args.is_empty() {
+        return Err(GeneratorError::MissingParameter(String::from(
+            "plugins directory",
+        )));
+    }
+    let graph = analyze_plugins(Path::new(&args.remove(0)))?;
+    match format.as_str() {
+        "text" => Ok(report(&graph)),
+        "dot" => Ok(graph.to_dot()),
+        "mermaid" => Ok(graph.to_mermaid()),
+        _ => Err(invalid_parameter(
+            "format",
+            &format,
+            "it must be text, dot or mermaid",
+            String::from("text"),
+        )),
+    }
+}
+
+fn report(graph: &PluginGraph) -> String {
+    let mut lines = vec![String::from("Load order:")];
+    lines.extend(
+        graph
+            .load_order
+            .iter()
+            .enumerate()
+            .map(|(index, name)| format!("  {}. {}", index + 1, name)),
+    );
+    lines.extend(graph.problems.iter().cloned());
+    lines.extend(graph.notes.iter().cloned());
+    lines.push(format!(
+        "{} plugin(s), {} problem(s)",
+        graph.plugins.len(),
+        graph.problems.len()
+    ));
+    lines.join("\n")
+}
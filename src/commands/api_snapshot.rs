@@ -0,0 +1,48 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::api_snapshot_dir;
+use crate::errors::GeneratorError;
+use crate::parameter_reader::take_flag;
+use crate::project_generator::api_snapshot::ApiSnapshot;
+use crate::project_generator::version_catalog::find_release;
+
+pub fn execute(args: &mut Vec<String>) -> Result<String, GeneratorError> {
+    execute_in(api_snapshot_dir(), args)
+}
+
+fn execute_in(
+    snapshots: Option<PathBuf>,
+    args: &mut Vec<String>,
+) -> Result<String, GeneratorError> {
+    let version = take_flag(args, "--target")
+        .ok_or_else(|| GeneratorError::MissingParameter(String::from("--target")))?;
+    find_release(&version)?;
+    let output = take_flag(args, "--output")
+        .map(PathBuf::from)
+        .or_else(|| snapshots.map(|dir| dir.join(format!("{}.api", version))))
+        .ok_or_else(|| GeneratorError::MissingParameter(String::from("--output")))?;
+    if args.is_empty() {
+        return Err(GeneratorError::MissingParameter(String::from("API jar")));
+    }
+
+    let snapshot = ApiSnapshot::from_jar(Path::new(&args.remove(0)), &version)?;
+    if let Some(parent) = output
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        fs::create_dir_all(parent).map_err(GeneratorError::DirectoryCreationError)?;
+    }
+    fs::write(&output, snapshot.to_text()).map_err(GeneratorError::FileCreationError)?;
+    Ok(format!(
+        "API snapshot of {} written to {}: {} classes, {} members",
+        version,
+        output.display(),
+        snapshot.classes.len(),
+        snapshot
+            .classes
+            .values()
+            .map(|class| class.members.len())
+            .sum::<usize>()
+    ))
+}
@@ -0,0 +1,44 @@
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use crate::commands::{apply_changes, project_dir};
+use crate::errors::GeneratorError;
+use crate::parameter_reader::take_switch;
+use crate::project_generator::build_model::{convert_to_gradle, convert_to_maven};
+
+pub fn execute(args: &mut Vec<String>) -> Result<String, GeneratorError> {
+    let project_dir = project_dir(args)?;
+    execute_in(&project_dir, args, &mut BufReader::new(io::stdin()))
+}
+
+fn execute_in<B: BufRead>(
+    project_dir: &Path,
+    args: &mut Vec<String>,
+    input_buffer: &mut B,
+) -> Result<String, GeneratorError> {
+    let confirmed = take_switch(args, "--yes");
+    if args.is_empty() {
+        return Err(GeneratorError::MissingParameter(String::from(
+            "build system",
+        )));
+    }
+    let build_system = args.remove(0);
+    let (conversion, name) = match build_system.as_str() {
+        "gradle" => (convert_to_gradle(project_dir)?, "Gradle"),
+        "maven" => (convert_to_maven(project_dir)?, "Maven"),
+        _ => {
+            return Err(GeneratorError::UnknownCommand(format!(
+                "convert-build {}",
+                build_system
+            )))
+        }
+    };
+    let written = apply_changes(project_dir, &conversion.changes, confirmed, input_buffer)?;
+
+    let mut lines = conversion.notes;
+    lines.push(match written {
+        true => format!("Build converted to {}!", name),
+        false => String::from("Nothing written."),
+    });
+    Ok(lines.join("\n"))
+}
@@ -0,0 +1,51 @@
+use std::path::Path;
+
+use crate::errors::GeneratorError;
+use crate::project_generator::jar_inspector::{inspect_jar, java_version, JarInspection};
+
+pub fn execute(args: &mut Vec<String>) -> Result<String, GeneratorError> {
+    if args.is_empty() {
+        return Err(GeneratorError::MissingParameter(String::from("jar")));
+    }
+    let jar = args.remove(0);
+    Ok(report(&inspect_jar(Path::new(&jar))?))
+}
+
+fn report(inspection: &JarInspection) -> String {
+    let mut lines = Vec::new();
+    for descriptor in &inspection.descriptors {
+        lines.push(format!("{} ({})", descriptor.path, descriptor.platform));
+        lines.extend(
+            descriptor
+                .metadata
+                .iter()
+                .map(|(key, value)| format!("  {}: {}", key, value)),
+        );
+        lines.extend(descriptor.classes.iter().map(|(class, found)| {
+            format!(
+                "  class {}: {}",
+                class,
+                if *found { "found" } else { "MISSING" }
+            )
+        }));
+    }
+    lines.extend(inspection.class_versions.iter().map(|(major, count)| {
+        format!(
+            "{} class(es) for Java {} (major version {})",
+            count,
+            java_version(*major),
+            major
+        )
+    }));
+    if let Some(java) = inspection.minimum_java_version() {
+        lines.push(format!("Minimum Java version: {}", java));
+    }
+    lines.extend(
+        inspection
+            .unrelocated
+            .iter()
+            .map(|(package, reason)| format!("{}: {}", package, reason)),
+    );
+    lines.extend(inspection.notes.iter().cloned());
+    lines.join("\n")
+}
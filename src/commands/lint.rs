@@ -0,0 +1,96 @@
+use std::path::Path;
+
+use crate::commands::project_dir;
+use crate::errors::GeneratorError;
+use crate::parameter_reader::take_switch;
+use crate::project_generator::plugin_lint::{lint_project, DescriptorLint, Severity};
+
+pub fn execute(args: &mut Vec<String>) -> Result<String, GeneratorError> {
+    let project_dir = project_dir(args)?;
+    execute_in(&project_dir, args)
+}
+
+/// The report is printed before failing, so CI gets both the issues and the exit code.
+fn execute_in(project_dir: &Path, args: &mut Vec<String>) -> Result<String, GeneratorError> {
+    let json = take_switch(args, "--json");
+    let lints = lint_project(project_dir)?;
+    let errors: usize = lints.iter().map(|lint| lint.count(Severity::Error)).sum();
+    let report = match json {
+        true => json_report(&lints, errors)?,
+        false => text_report(&lints, errors),
+    };
+    if errors > 0 {
+        println!("{}", report);
+        return Err(GeneratorError::LintFailed(errors));
+    }
+    Ok(report)
+}
+
+fn text_report(lints: &[DescriptorLint], errors: usize) -> String {
+    let mut lines = Vec::new();
+    for lint in lints {
+        lines.extend(lint.issues.iter().map(|issue| {
+            format!(
+                "{}: {} {}: {}",
+                lint.path,
+                issue.severity.name(),
+                issue.key,
+                issue.message
+            )
+        }));
+    }
+    let warnings: usize = lints.iter().map(|lint| lint.count(Severity::Warning)).sum();
+    lines.push(format!("{} error(s), {} warning(s)", errors, warnings));
+    lines.join("\n")
+}
+
+fn json_report(lints: &[DescriptorLint], errors: usize) -> Result<String, GeneratorError> {
+    let warnings: usize = lints.iter().map(|lint| lint.count(Severity::Warning)).sum();
+    let report = serde_json::json!({
+        "errors": errors,
+        "warnings": warnings,
+        "files": lints,
+    });
+    serde_json::to_string_pretty(&report).map_err(|error| {
+        GeneratorError::InvalidConfiguration(String::from("lint report"), error.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn execute_in_should_report_issues_as_json() {
+        // GIVEN a project whose plugin.yml has no main class source
+        let root = env::temp_dir().join("mcdev-lint-command");
+        let resources = root.join("src/main/resources");
+        fs::create_dir_all(&resources).unwrap();
+        fs::write(
+            resources.join("plugin.yml"),
+            "name: Homes\nversion: '1.0'\nmain: com.test.Homes\napi-version: '1.21'\n",
+        )
+        .unwrap();
+
+        // WHEN we lint it, with and without --json
+        let text = execute_in(&root, &mut Vec::new());
+        let lints = lint_project(&root).unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&json_report(&lints, 1).unwrap()).unwrap();
+
+        // THEN the command should fail with the error listed in both reports
+        assert_eq!("Lint failed with 1 error(s)", text.unwrap_err().to_string());
+        assert_eq!(
+            "src/main/resources/plugin.yml: error main: the class 'com.test.Homes' does not exist in the sources\n1 error(s), 0 warning(s)",
+            text_report(&lints, 1)
+        );
+        assert_eq!(1, json["errors"]);
+        assert_eq!("src/main/resources/plugin.yml", json["files"][0]["path"]);
+        assert_eq!("error", json["files"][0]["issues"][0]["severity"]);
+        assert_eq!("main", json["files"][0]["issues"][0]["key"]);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,52 @@
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use crate::commands::{apply_changes, project_dir};
+use crate::errors::GeneratorError;
+use crate::parameter_reader::take_switch;
+use crate::project_generator::existing_project::ExistingProject;
+use crate::project_generator::paper_migration::migrate_to_paper;
+
+pub fn execute(args: &mut Vec<String>) -> Result<String, GeneratorError> {
+    let project_dir = project_dir(args)?;
+    execute_in(&project_dir, args, &mut BufReader::new(io::stdin()))
+}
+
+fn execute_in<B: BufRead>(
+    project_dir: &Path,
+    args: &mut Vec<String>,
+    input_buffer: &mut B,
+) -> Result<String, GeneratorError> {
+    let confirmed = take_switch(args, "--yes");
+    let paper_plugin = take_switch(args, "--paper-plugin");
+    if args.is_empty() {
+        return Err(GeneratorError::UnknownCommand(String::from("migrate")));
+    }
+    let migration = args.remove(0);
+    if migration != "spigot-to-paper" {
+        return Err(GeneratorError::UnknownCommand(format!(
+            "migrate {}",
+            migration
+        )));
+    }
+
+    let project = ExistingProject::open(project_dir)?;
+    let migration = migrate_to_paper(&project, paper_plugin)?;
+    let written = apply_changes(project_dir, &migration.changes, confirmed, input_buffer)?;
+
+    let mut lines: Vec<String> = migration
+        .usages
+        .iter()
+        .map(|usage| {
+            format!(
+                "{}:{}: {} is deprecated by Paper, use {}",
+                usage.path, usage.line, usage.api.api, usage.api.replacement
+            )
+        })
+        .collect();
+    lines.push(String::from(match written {
+        true => "Project migrated to Paper!",
+        false => "Nothing written.",
+    }));
+    Ok(lines.join("\n"))
+}
@@ -0,0 +1,97 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use crate::config::api_snapshot_dir;
+use crate::errors::GeneratorError;
+use crate::parameter_reader::take_flag;
+use crate::project_generator::api_catalog::API_SIGNATURES;
+use crate::project_generator::api_snapshot::ApiSnapshot;
+use crate::project_generator::bytecode_scan::{scan_bytecode, BytecodeScan};
+use crate::project_generator::version_catalog::RELEASES;
+
+pub fn execute(args: &mut Vec<String>) -> Result<String, GeneratorError> {
+    execute_in(api_snapshot_dir(), args)
+}
+
+fn execute_in(
+    snapshots: Option<PathBuf>,
+    args: &mut Vec<String>,
+) -> Result<String, GeneratorError> {
+    let version = take_flag(args, "--target")
+        .unwrap_or_else(|| RELEASES[RELEASES.len() - 1].version.to_string());
+    let snapshot_path = take_flag(args, "--snapshot")
+        .map(PathBuf::from)
+        .or_else(|| {
+            snapshots
+                .map(|dir| dir.join(format!("{}.api", version)))
+                .filter(|path| path.is_file())
+        });
+    if args.is_empty() {
+        return Err(GeneratorError::MissingParameter(String::from(
+            "jar or classes directory",
+        )));
+    }
+    let snapshot = snapshot_path
+        .map(|path| ApiSnapshot::load(&path))
+        .transpose()?;
+    let scan = scan_bytecode(Path::new(&args.remove(0)), &version, snapshot.as_ref())?;
+    Ok(report(&scan, &version, snapshot.as_ref()))
+}
+
+fn report(scan: &BytecodeScan, version: &str, snapshot: Option<&ApiSnapshot>) -> String {
+    let users = |users: &BTreeSet<String>| users.iter().cloned().collect::<Vec<_>>().join(", ");
+    let mut lines = vec![format!(
+        "Scanned {} class(es) against Minecraft {}",
+        scan.classes, version
+    )];
+    lines.extend(
+        scan.internals
+            .iter()
+            .map(|(class, classes)| format!("{} used by {}", class, users(classes))),
+    );
+    lines.extend(scan.craftbukkit_revisions.iter().map(|revision| {
+        format!(
+            "CraftBukkit {} is only found on the server versions it was built for",
+            revision
+        )
+    }));
+    lines.extend(scan.missing_apis.iter().map(|(name, missing)| {
+        let change = match missing.signature {
+            Some(signature) => match (signature.removed, signature.replacement) {
+                (Some(removed), Some(replacement)) => {
+                    format!("removed in {}, use {}", removed, replacement)
+                }
+                (Some(removed), None) => format!("removed in {}", removed),
+                (None, _) => format!("added in {}", signature.since),
+            },
+            None => String::from("not in its API snapshot"),
+        };
+        format!(
+            "{} is missing in {} ({}), used by {}",
+            name,
+            version,
+            change,
+            users(&missing.users)
+        )
+    }));
+    if scan.internals.is_empty() && scan.missing_apis.is_empty() {
+        lines.push(String::from(
+            "No server internals nor known missing APIs found.",
+        ));
+    }
+    match snapshot {
+        Some(snapshot) => lines.push(format!(
+            "API references were checked against the {} classes of the {} API snapshot.",
+            snapshot.classes.len(),
+            snapshot.version
+        )),
+        None => lines.push(format!(
+            "No API snapshot for {0}, so missing APIs were only checked against {1} curated API changes. \
+             Run `api-snapshot <api jar> --target {0}` for a full check.",
+            version,
+            API_SIGNATURES.len()
+        )),
+    }
+    lines.extend(scan.notes.iter().cloned());
+    lines.join("\n")
+}
@@ -0,0 +1,199 @@
+use std::fs;
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use crate::commands::{apply_changes, project_dir};
+use crate::config::server_jar_dir;
+use crate::errors::GeneratorError;
+use crate::parameter_reader::{get_parameters, take_flag, take_switch};
+use crate::project_generator::dev_server::{
+    cache_server_jar, eula, find_cached_jar, install_server_jar, server_files, validate_memory,
+    CachedJar, ServerSettings, EULA_URL, RUN_DIRECTORY,
+};
+use crate::project_generator::existing_project::ExistingProject;
+
+pub fn execute(args: &mut Vec<String>) -> Result<String, GeneratorError> {
+    let project_dir = project_dir(args)?;
+    execute_in(
+        &project_dir,
+        server_jar_dir(),
+        args,
+        &mut BufReader::new(io::stdin()),
+    )
+}
+
+fn execute_in<B: BufRead>(
+    project_dir: &Path,
+    cache: Option<PathBuf>,
+    args: &mut Vec<String>,
+    input_buffer: &mut B,
+) -> Result<String, GeneratorError> {
+    let confirmed = take_switch(args, "--yes");
+    let accept_eula = take_switch(args, "--accept-eula");
+    let server_jar = take_flag(args, "--server-jar");
+    let mut settings = ServerSettings::default();
+    if let Some(memory) = take_flag(args, "--memory") {
+        validate_memory(&memory)?;
+        settings.memory = memory;
+    }
+    if let Some(jvm_flags) = take_flag(args, "--jvm-flags") {
+        settings.jvm_flags = jvm_flags;
+    }
+    if args.is_empty() {
+        return Err(GeneratorError::UnknownCommand(String::from("server")));
+    }
+    let command = args.remove(0);
+    if command != "init" {
+        return Err(GeneratorError::UnknownCommand(format!(
+            "server {}",
+            command
+        )));
+    }
+
+    if let Ok(project) = ExistingProject::open(project_dir) {
+        settings.motd = format!("{} development server", project.plugin_name());
+    }
+    let jar = match server_jar {
+        Some(jar) => Some(resolve_server_jar(cache.as_deref(), &jar)?),
+        None => None,
+    };
+    if let Some(jar) = &jar {
+        settings.jar = jar
+            .path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+    }
+
+    let changes = server_files(project_dir, &settings);
+    if !changes.is_empty() && !apply_changes(project_dir, &changes, confirmed, input_buffer)? {
+        return Ok(String::from("Nothing written."));
+    }
+    let run = project_dir.join(RUN_DIRECTORY);
+    fs::create_dir_all(run.join("plugins")).map_err(GeneratorError::DirectoryCreationError)?;
+    make_executable(&run.join("start.sh"))?;
+
+    let mut lines = Vec::new();
+    if let Some(jar) = &jar {
+        if install_server_jar(project_dir, jar)? {
+            lines.push(format!(
+                "{} copied to {}/, SHA-256 {}",
+                settings.jar, RUN_DIRECTORY, jar.sha256
+            ));
+        }
+    } else if !run.join(&settings.jar).exists() {
+        lines.push(format!(
+            "Put a server jar at {}/{}, or give one with --server-jar",
+            RUN_DIRECTORY, settings.jar
+        ));
+    }
+
+    let eula_path = run.join("eula.txt");
+    let agreed = fs::read_to_string(&eula_path).is_ok_and(|content| content.contains("eula=true"));
+    if !agreed {
+        let answer = match accept_eula {
+            true => String::from("y"),
+            false => get_parameters(
+                &mut Vec::new(),
+                input_buffer,
+                &format!("Do you agree to the Minecraft EULA ({})? [y/N]", EULA_URL),
+                true,
+            )
+            .unwrap_or_default(),
+        };
+        match answer.eq_ignore_ascii_case("y") || answer.eq_ignore_ascii_case("yes") {
+            true => fs::write(&eula_path, eula()).map_err(GeneratorError::FileCreationError)?,
+            false => lines.push(format!(
+                "The server will not start until you agree to the EULA in {}/eula.txt",
+                RUN_DIRECTORY
+            )),
+        }
+    }
+    lines.push(format!(
+        "Server ready, start it with {}/start.sh!",
+        RUN_DIRECTORY
+    ));
+    Ok(lines.join("\n"))
+}
+
+/// A jar given by path is cached, one given by name is taken from the cache.
+fn resolve_server_jar(cache: Option<&Path>, jar: &str) -> Result<CachedJar, GeneratorError> {
+    let invalid = |reason: &str| GeneratorError::InvalidJar(jar.to_string(), reason.to_string());
+    let cache = cache.ok_or_else(|| invalid("no cache directory, set MCDEV_CACHE_DIR"))?;
+    if Path::new(jar).is_file() {
+        return cache_server_jar(cache, Path::new(jar));
+    }
+    find_cached_jar(cache, jar)?.ok_or_else(|| invalid("it is neither a file nor a cached jar"))
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<(), GeneratorError> {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755))
+        .map_err(GeneratorError::FileCreationError)
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<(), GeneratorError> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn execute_in_should_create_run_directory() {
+        // GIVEN a project and a server jar
+        let root = env::temp_dir().join("mcdev-server-init");
+        let cache = root.join("cache");
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("paper.jar"), "jar content").unwrap();
+        let mut args: Vec<String> = ["init", "--server-jar", "--memory", "1G"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+        args.insert(2, root.join("paper.jar").display().to_string());
+
+        // WHEN we init the server, applying the files and agreeing to the EULA
+        let message = execute_in(
+            &root,
+            Some(cache.clone()),
+            &mut args,
+            &mut "y\ny\n".as_bytes(),
+        )
+        .unwrap();
+
+        // THEN the run directory should be ready to start
+        let run = root.join(RUN_DIRECTORY);
+        assert!(message.ends_with("Server ready, start it with run/start.sh!"));
+        assert!(fs::read_to_string(run.join("server.properties"))
+            .unwrap()
+            .contains("\nonline-mode=false\n"));
+        assert_eq!(
+            "#!/bin/sh\ncd \"$(dirname \"$0\")\"\nexec java -Xms1G -Xmx1G -XX:+UseG1GC -jar paper.jar nogui\n",
+            fs::read_to_string(run.join("start.sh")).unwrap()
+        );
+        assert!(run.join("plugins").is_dir());
+        assert_eq!(
+            "jar content",
+            fs::read_to_string(run.join("paper.jar")).unwrap()
+        );
+        assert!(cache.join("paper.jar.sha256").is_file());
+        assert!(fs::read_to_string(run.join("eula.txt"))
+            .unwrap()
+            .ends_with("eula=true\n"));
+
+        // AND initializing again should reuse the cached jar by name without asking anything
+        let mut args: Vec<String> = ["init", "--server-jar", "paper.jar", "--memory", "1G"]
+            .iter()
+            .map(|arg| arg.to_string())
+            .collect();
+        let message = execute_in(&root, Some(cache), &mut args, &mut "".as_bytes()).unwrap();
+        assert_eq!("Server ready, start it with run/start.sh!", message);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,79 @@
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use crate::commands::{apply_changes, project_dir};
+use crate::errors::GeneratorError;
+use crate::parameter_reader::take_switch;
+use crate::project_generator::version_update::set_minecraft_version;
+
+pub fn execute(args: &mut Vec<String>) -> Result<String, GeneratorError> {
+    let project_dir = project_dir(args)?;
+    execute_in(&project_dir, args, &mut BufReader::new(io::stdin()))
+}
+
+fn execute_in<B: BufRead>(
+    project_dir: &Path,
+    args: &mut Vec<String>,
+    input_buffer: &mut B,
+) -> Result<String, GeneratorError> {
+    let confirmed = take_switch(args, "--yes");
+    if args.is_empty() {
+        return Err(GeneratorError::MissingParameter(String::from("version")));
+    }
+    let version = args.remove(0);
+
+    let changes = set_minecraft_version(project_dir, &version)?;
+    if changes.is_empty() {
+        return Ok(format!(
+            "The project already targets Minecraft {}.",
+            version
+        ));
+    }
+    if !apply_changes(project_dir, &changes, confirmed, input_buffer)? {
+        return Ok(String::from("Nothing written."));
+    }
+    Ok(format!("Minecraft version set to {}!", version))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+    use std::{env, fs};
+
+    #[test]
+    fn set_mc_version_should_only_write_once_confirmed() {
+        // GIVEN a project targeting 1.20.4
+        let root = env::temp_dir().join("mcdev-set-mc-version-command");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        let pom = "<spigot.version>1.20.4-R0.1-SNAPSHOT</spigot.version>\n";
+        fs::write(root.join("pom.xml"), pom).unwrap();
+
+        // WHEN the change is refused, then confirmed
+        let refused = execute_in(
+            &root,
+            &mut vec![String::from("1.21.4")],
+            &mut Cursor::new("n\n"),
+        )
+        .unwrap();
+        let unchanged = fs::read_to_string(root.join("pom.xml")).unwrap();
+        let confirmed = execute_in(
+            &root,
+            &mut vec![String::from("1.21.4")],
+            &mut Cursor::new("y\n"),
+        )
+        .unwrap();
+
+        // THEN the pom should only be written the second time
+        assert_eq!("Nothing written.", refused);
+        assert_eq!(pom, unchanged);
+        assert_eq!("Minecraft version set to 1.21.4!", confirmed);
+        assert_eq!(
+            "<spigot.version>1.21.4-R0.1-SNAPSHOT</spigot.version>\n",
+            fs::read_to_string(root.join("pom.xml")).unwrap()
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,127 @@
+use std::path::Path;
+
+use crate::config::template_pack_dir;
+use crate::errors::GeneratorError;
+use crate::project_generator::template_pack::{
+    find_template_pack, install_template_pack, list_template_packs, remove_template_pack,
+};
+
+pub fn execute(args: &mut Vec<String>) -> Result<String, GeneratorError> {
+    let packs_dir = template_pack_dir().ok_or_else(|| {
+        GeneratorError::TemplatePackError(String::from("Unable to locate the cache directory"))
+    })?;
+    execute_in(&packs_dir, args)
+}
+
+fn execute_in(packs_dir: &Path, args: &mut Vec<String>) -> Result<String, GeneratorError> {
+    if args.is_empty() {
+        return Err(GeneratorError::UnknownCommand(String::from("template")));
+    }
+    let subcommand = args.remove(0);
+    let mut argument = |name: &str| {
+        if args.is_empty() {
+            Err(GeneratorError::MissingParameter(name.to_string()))
+        } else {
+            Ok(args.remove(0))
+        }
+    };
+
+    match subcommand.as_str() {
+        "add" => {
+            let pack = install_template_pack(packs_dir, &argument("source")?)?;
+            Ok(format!(
+                "Template pack {} {} installed!",
+                pack.name, pack.version
+            ))
+        }
+        "list" => {
+            let packs = list_template_packs(packs_dir)?;
+            if packs.is_empty() {
+                return Ok(String::from("No template pack installed."));
+            }
+            Ok(packs
+                .iter()
+                .map(|pack| match pack.description.is_empty() {
+                    true => format!("{} {}", pack.name, pack.version),
+                    false => format!("{} {} - {}", pack.name, pack.version, pack.description),
+                })
+                .collect::<Vec<_>>()
+                .join("\n"))
+        }
+        "changelog" => find_template_pack(packs_dir, &argument("name")?)?.changelog(),
+        "remove" => {
+            let pack = remove_template_pack(packs_dir, &argument("name")?)?;
+            Ok(format!(
+                "Template pack {} {} removed!",
+                pack.name, pack.version
+            ))
+        }
+        _ => Err(GeneratorError::UnknownCommand(format!(
+            "template {}",
+            subcommand
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, fs};
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn template_commands_should_add_list_and_remove_pack() {
+        // GIVEN a pack source and an empty cache
+        let source = env::temp_dir().join("mcdev-template-command-source");
+        let packs_dir = env::temp_dir().join("mcdev-template-command-cache");
+        let _ = fs::remove_dir_all(&packs_dir);
+        fs::create_dir_all(&source).unwrap();
+        fs::write(
+            source.join("manifest.toml"),
+            "name = \"company\"\nversion = \"2.0.0\"\ndescription = \"Company base plugin\"\n",
+        )
+        .unwrap();
+        fs::write(source.join("CHANGELOG.md"), "## 2.0.0\n- Initial pack\n").unwrap();
+
+        // WHEN we add, list and remove it
+        let added = execute_in(&packs_dir, &mut args(&["add", source.to_str().unwrap()]));
+        let listed = execute_in(&packs_dir, &mut args(&["list"]));
+        let changelog = execute_in(&packs_dir, &mut args(&["changelog", "company"]));
+        let removed = execute_in(&packs_dir, &mut args(&["remove", "company"]));
+        let listed_after_removal = execute_in(&packs_dir, &mut args(&["list"]));
+
+        // THEN every command should report what it did
+        assert_eq!("Template pack company 2.0.0 installed!", added.unwrap());
+        assert_eq!("company 2.0.0 - Company base plugin", listed.unwrap());
+        assert_eq!("## 2.0.0\n- Initial pack\n", changelog.unwrap());
+        assert_eq!("Template pack company 2.0.0 removed!", removed.unwrap());
+        assert_eq!("No template pack installed.", listed_after_removal.unwrap());
+    }
+
+    #[test]
+    fn template_command_should_reject_unknown_subcommand() {
+        // GIVEN an unknown subcommand
+        let mut args = args(&["update"]);
+
+        // WHEN we execute it
+        let result = execute_in(Path::new("unused"), &mut args);
+
+        // THEN it should be rejected
+        assert!(matches!(result, Err(GeneratorError::UnknownCommand(_))));
+    }
+
+    #[test]
+    fn template_add_should_require_a_source() {
+        // GIVEN the add subcommand without source
+        let mut args = args(&["add"]);
+
+        // WHEN we execute it
+        let result = execute_in(Path::new("unused"), &mut args);
+
+        // THEN the source should be reported as missing
+        assert!(matches!(result, Err(GeneratorError::MissingParameter(name)) if name == "source"));
+    }
+}
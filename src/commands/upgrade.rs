@@ -0,0 +1,46 @@
+use crate::commands::project_dir;
+use crate::errors::GeneratorError;
+use crate::parameter_reader::take_switch;
+use crate::project_generator::project_metadata::ProjectMetadata;
+use crate::project_generator::upgrade::{upgrade_project, FileUpgrade};
+use crate::project_generator::GeneratorRegistry;
+
+pub fn execute(
+    registry: &GeneratorRegistry,
+    args: &mut Vec<String>,
+) -> Result<String, GeneratorError> {
+    let project_dir = project_dir(args)?;
+    let reject = take_switch(args, "--reject");
+
+    let metadata = ProjectMetadata::read(&project_dir)?;
+    let generator = registry.find(&metadata.generator)?;
+    let report = upgrade_project(&project_dir, generator, reject)?;
+
+    let mut lines: Vec<String> = report
+        .iter()
+        .filter_map(|(path, upgrade)| describe(upgrade).map(|text| format!("{}: {}", path, text)))
+        .collect();
+    let conflicted = report.iter().any(|(_, upgrade)| {
+        matches!(
+            upgrade,
+            FileUpgrade::Conflicted(_) | FileUpgrade::Rejected(_)
+        )
+    });
+    lines.push(String::from(match conflicted {
+        true => "Project upgraded, please resolve the conflicts!",
+        false => "Project upgraded!",
+    }));
+    Ok(lines.join("\n"))
+}
+
+fn describe(upgrade: &FileUpgrade) -> Option<String> {
+    match upgrade {
+        FileUpgrade::Unchanged => None,
+        FileUpgrade::Updated => Some(String::from("updated")),
+        FileUpgrade::Created => Some(String::from("created")),
+        FileUpgrade::Skipped => Some(String::from("deleted by you, skipped")),
+        FileUpgrade::Merged => Some(String::from("merged")),
+        FileUpgrade::Conflicted(count) => Some(format!("{} conflict(s) marked", count)),
+        FileUpgrade::Rejected(count) => Some(format!("{} conflict(s) written to .rej", count)),
+    }
+}
@@ -0,0 +1,147 @@
+use std::path::{Path, PathBuf};
+use std::{env, fs};
+
+use crate::errors::GeneratorError;
+use crate::project_generator::Parameters;
+
+pub fn config_dir() -> Option<PathBuf> {
+    if let Some(dir) = env_dir("MCDEV_CONFIG_DIR") {
+        return Some(dir);
+    }
+    if let Some(dir) = env_dir("XDG_CONFIG_HOME") {
+        return Some(dir.join("mcdev"));
+    }
+    if let Some(dir) = env_dir("APPDATA") {
+        return Some(dir.join("mcdev"));
+    }
+    env_dir("HOME").map(|home| home.join(".config").join("mcdev"))
+}
+
+pub fn user_template_dir() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("templates"))
+}
+
+fn env_dir(name: &str) -> Option<PathBuf> {
+    env::var_os(name)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
+/// The file holding parameter values used by every generator when they are not given.
+pub fn defaults_file() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("defaults.toml"))
+}
+
+pub fn load_defaults(path: &Path) -> Result<Parameters, GeneratorError> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(_) if !path.exists() => return Ok(Parameters::new()),
+        Err(error) => {
+            return Err(GeneratorError::InvalidConfiguration(
+                path.display().to_string(),
+                error.to_string(),
+            ))
+        }
+    };
+    let table: toml::Table = toml::from_str(&content).map_err(|error| {
+        GeneratorError::InvalidConfiguration(path.display().to_string(), error.to_string())
+    })?;
+
+    Ok(table
+        .into_iter()
+        .map(|(name, value)| {
+            let value = match value {
+                toml::Value::String(value) => value,
+                toml::Value::Array(values) => values
+                    .iter()
+                    .map(|value| match value {
+                        toml::Value::String(value) => value.clone(),
+                        value => value.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                value => value.to_string(),
+            };
+            (name, value)
+        })
+        .collect())
+}
+
+pub fn cache_dir() -> Option<PathBuf> {
+    if let Some(dir) = env_dir("MCDEV_CACHE_DIR") {
+        return Some(dir);
+    }
+    if let Some(dir) = env_dir("XDG_CACHE_HOME") {
+        return Some(dir.join("mcdev"));
+    }
+    if let Some(dir) = env_dir("LOCALAPPDATA") {
+        return Some(dir.join("mcdev"));
+    }
+    env_dir("HOME").map(|home| home.join(".cache").join("mcdev"))
+}
+
+pub fn template_pack_dir() -> Option<PathBuf> {
+    cache_dir().map(|dir| dir.join("templates"))
+}
+
+/// The server jars given to `server init`, kept with their checksum.
+pub fn server_jar_dir() -> Option<PathBuf> {
+    cache_dir().map(|dir| dir.join("servers"))
+}
+
+/// The API snapshots written by `api-snapshot`, one `<version>.api` file per Minecraft version.
+pub fn api_snapshot_dir() -> Option<PathBuf> {
+    cache_dir().map(|dir| dir.join("api-snapshots"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn load_defaults_should_flatten_values() {
+        // GIVEN a defaults file with strings and a list
+        let path = env::temp_dir().join("mcdev-config-defaults.toml");
+        fs::write(
+            &path,
+            "authors = [\"Notch\", \"jeb_\"]\nlicense = \"MIT\"\nproject_version = 2\n",
+        )
+        .unwrap();
+
+        // WHEN we load it
+        let defaults = load_defaults(&path).unwrap();
+
+        // THEN every value should be a parameter string
+        assert_eq!(Some(&String::from("Notch, jeb_")), defaults.get("authors"));
+        assert_eq!(Some(&String::from("MIT")), defaults.get("license"));
+        assert_eq!(Some(&String::from("2")), defaults.get("project_version"));
+    }
+
+    #[test]
+    fn load_defaults_should_ignore_missing_file() {
+        // GIVEN a path without file
+        let path = env::temp_dir().join("mcdev-config-missing-defaults.toml");
+
+        // WHEN we load it
+        let defaults = load_defaults(&path).unwrap();
+
+        // THEN there should be no default
+        assert!(defaults.is_empty());
+    }
+
+    #[test]
+    fn load_defaults_should_reject_invalid_toml() {
+        // GIVEN an invalid defaults file
+        let path = env::temp_dir().join("mcdev-config-invalid-defaults.toml");
+        fs::write(&path, "authors = [").unwrap();
+
+        // WHEN we load it
+        let result = load_defaults(&path);
+
+        // THEN an error should be returned
+        assert!(matches!(
+            result,
+            Err(GeneratorError::InvalidConfiguration(_, _))
+        ));
+    }
+}
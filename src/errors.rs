@@ -6,12 +6,71 @@ pub enum GeneratorError {
     #[error("Unable to determine the project generator")]
     UnableToDetermineProjectGenerator,
 
+    #[error("Unknown command: {0}")]
+    UnknownCommand(String),
+
     #[error("Unable to read a mandatory parameter")]
     UnableToReadMandatoryParameter,
 
+    #[error("Missing mandatory parameter: {0}")]
+    MissingParameter(String),
+
+    #[error("Invalid group id '{group_id}': {reason}, did you mean '{suggestion}'?")]
+    InvalidGroupId {
+        group_id: String,
+        reason: String,
+        suggestion: String,
+    },
+
+    #[error("Invalid {name} '{value}': {reason}, did you mean '{suggestion}'?")]
+    InvalidParameter {
+        name: String,
+        value: String,
+        reason: String,
+        suggestion: String,
+    },
+
+    #[error("Unable to render template: {0}")]
+    TemplateError(String),
+
+    #[error("Invalid template directory {0}: {1}")]
+    InvalidTemplateDirectory(String, String),
+
+    #[error("Refusing to write outside the project folder: {0}")]
+    PathOutsideProject(String),
+
+    #[error("Generator {0} could not be loaded: {1}")]
+    BrokenGenerator(String, String),
+
+    #[error("Invalid configuration {0}: {1}")]
+    InvalidConfiguration(String, String),
+
+    #[error("Invalid project {0}: {1}")]
+    InvalidProject(String, String),
+
+    #[error("Invalid jar {0}: {1}")]
+    InvalidJar(String, String),
+
+    #[error("Template pack error: {0}")]
+    TemplatePackError(String),
+
+    #[error("Lint failed with {0} error(s)")]
+    LintFailed(usize),
+
     #[error("Unable to create file: {0}")]
     FileCreationError(io::Error),
 
     #[error("Unable to create directory: {0}")]
     DirectoryCreationError(io::Error),
 }
+
+impl GeneratorError {
+    /// The corrected value proposed by validation errors, if any.
+    pub fn suggestion(&self) -> Option<&str> {
+        match self {
+            GeneratorError::InvalidGroupId { suggestion, .. }
+            | GeneratorError::InvalidParameter { suggestion, .. } => Some(suggestion),
+            _ => None,
+        }
+    }
+}
@@ -1,18 +1,81 @@
-mod errors;
+mod commands;
+mod config;
+pub mod errors;
 mod parameter_reader;
-mod project_generator;
+pub mod project_generator;
 
 use std::io::BufReader;
+use std::path::Path;
 use std::{env, io};
 
+use crate::config::{defaults_file, load_defaults, template_pack_dir, user_template_dir};
 use crate::errors::GeneratorError;
-use crate::parameter_reader::get_parameters;
-use crate::project_generator::spigot::SpigotGenerator;
-use crate::project_generator::{get_project_type, ProjectGeneratorType};
+use crate::parameter_reader::{get_parameters, get_valid_parameter, take_flag};
 
-pub fn execute<'a>() -> Result<&'a str, GeneratorError> {
+pub use crate::project_generator::{
+    GeneratorRegistry, ParameterDescriptor, Parameters, ProjectGenerator, ProjectGeneratorType,
+};
+
+pub fn execute() -> Result<String, GeneratorError> {
     let mut args: Vec<String> = env::args().skip(1).collect();
+
+    match args.first().map(String::as_str) {
+        Some("add") => {
+            args.remove(0);
+            commands::add::execute(&mut args)
+        }
+        Some("analyze-plugins") => {
+            args.remove(0);
+            commands::analyze_plugins::execute(&mut args)
+        }
+        Some("api-snapshot") => {
+            args.remove(0);
+            commands::api_snapshot::execute(&mut args)
+        }
+        Some("convert-build") => {
+            args.remove(0);
+            commands::convert_build::execute(&mut args)
+        }
+        Some("inspect") => {
+            args.remove(0);
+            commands::inspect::execute(&mut args)
+        }
+        Some("lint") => {
+            args.remove(0);
+            commands::lint::execute(&mut args)
+        }
+        Some("migrate") => {
+            args.remove(0);
+            commands::migrate::execute(&mut args)
+        }
+        Some("scan-bytecode") => {
+            args.remove(0);
+            commands::scan_bytecode::execute(&mut args)
+        }
+        Some("server") => {
+            args.remove(0);
+            commands::server::execute(&mut args)
+        }
+        Some("set-mc-version") => {
+            args.remove(0);
+            commands::set_mc_version::execute(&mut args)
+        }
+        Some("template") => {
+            args.remove(0);
+            commands::template::execute(&mut args)
+        }
+        Some("upgrade") => {
+            args.remove(0);
+            let registry = load_registry(&mut args)?;
+            commands::upgrade::execute(&registry, &mut args)
+        }
+        _ => generate_project(args),
+    }
+}
+
+fn generate_project(mut args: Vec<String>) -> Result<String, GeneratorError> {
     let mut input_buffer = BufReader::new(io::stdin());
+    let registry = load_registry(&mut args)?;
 
     let project_type = get_parameters(
         &mut args,
@@ -22,49 +85,73 @@ pub fn execute<'a>() -> Result<&'a str, GeneratorError> {
     )
     .ok_or(GeneratorError::UnableToDetermineProjectGenerator)?;
 
-    let project_generator = get_project_type(&project_type);
+    let project_generator = registry.find(&project_type)?;
 
-    if let Some(project_generator) = project_generator {
-        match project_generator {
-            ProjectGeneratorType::Spigot => {
-                let name = get_parameters(
-                    &mut args,
-                    &mut input_buffer,
-                    "Please provide a name for your project",
-                    true,
-                )
-                .ok_or(GeneratorError::UnableToReadMandatoryParameter)?;
+    let defaults = match defaults_file() {
+        Some(path) => load_defaults(&path)?,
+        None => Parameters::new(),
+    };
+    let descriptors = project_generator.parameters();
+    let mut flags: Vec<Option<String>> = descriptors
+        .iter()
+        .map(|descriptor| take_flag(&mut args, &parameter_flag(&descriptor.name)))
+        .collect();
 
-                let version = get_parameters(
-                    &mut args,
-                    &mut input_buffer,
-                    "Please provide a version for your project",
-                    true,
-                )
-                .ok_or(GeneratorError::UnableToReadMandatoryParameter)?;
-
-                let group_id = get_parameters(
-                    &mut args,
-                    &mut input_buffer,
-                    "Please provide a group id for your project",
-                    true,
-                )
-                .ok_or(GeneratorError::UnableToReadMandatoryParameter)?;
+    let mut parameters = Parameters::new();
+    for (descriptor, flag) in descriptors.into_iter().zip(flags.iter_mut()) {
+        let value = match flag.take() {
+            Some(value) => {
+                project_generator.validate_parameter(&descriptor.name, &value)?;
+                Some(value)
+            }
+            None => get_valid_parameter(
+                &mut args,
+                &mut input_buffer,
+                &descriptor.prompt,
+                descriptor.mandatory,
+                |value| project_generator.validate_parameter(&descriptor.name, value),
+            )?,
+        };
+        match value {
+            Some(value) => {
+                parameters.insert(descriptor.name, value);
+            }
+            None if descriptor.mandatory => {
+                return Err(GeneratorError::UnableToReadMandatoryParameter)
+            }
+            None => {
+                let default = defaults.get(&descriptor.name).cloned();
+                if let Some(default) = default.or(descriptor.default) {
+                    project_generator.validate_parameter(&descriptor.name, &default)?;
+                    parameters.insert(descriptor.name, default);
+                }
+            }
+        }
+    }
 
-                let path = get_parameters(
-                    &mut args,
-                    &mut input_buffer,
-                    "Please provide a path for your project, leave empty for current directory",
-                    false,
-                );
+    project_generator.validate(&parameters)?;
+    project_generator.generate(&parameters)?;
+    Ok(String::from("Project generated!"))
+}
 
-                let spigot_generator = SpigotGenerator::new(name, version, group_id, path);
+/// The built-in generators plus the installed and `--template-dir` template directories.
+/// A broken installed directory is only reported, unlike a broken `--template-dir`.
+fn load_registry(args: &mut Vec<String>) -> Result<GeneratorRegistry, GeneratorError> {
+    let mut registry = GeneratorRegistry::default();
 
-                spigot_generator.generate_project()?;
-                Ok("Project generated!")
-            }
+    let installed = [template_pack_dir(), user_template_dir()];
+    for directory in installed.into_iter().flatten() {
+        for warning in registry.load_template_directories(&directory) {
+            eprintln!("{}", warning);
         }
-    } else {
-        Err(GeneratorError::UnableToDetermineProjectGenerator)
     }
+    if let Some(directory) = take_flag(args, "--template-dir") {
+        registry.load_template_directory(Path::new(&directory))?;
+    }
+    Ok(registry)
+}
+
+/// Every parameter can also be given as a flag, `group_id` being `--group-id`.
+fn parameter_flag(name: &str) -> String {
+    format!("--{}", name.replace('_', "-"))
 }
@@ -1,8 +1,13 @@
+use std::process;
+
 use minecraft_dev_tool::execute;
 
 fn main() {
     match execute() {
         Ok(message) => println!("{}", message),
-        Err(error) => eprintln!("{}", error),
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
     }
 }
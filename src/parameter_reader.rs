@@ -1,6 +1,10 @@
 use std::io;
 
-fn get_user_input<B: io::BufRead>(buffer_reader: &mut B, input: &'static str) -> String {
+use crate::errors::GeneratorError;
+
+const MAX_ATTEMPTS: usize = 3;
+
+fn get_user_input<B: io::BufRead>(buffer_reader: &mut B, input: &str) -> String {
     println!("{}", input);
     let mut user_input = String::new();
     buffer_reader
@@ -12,7 +16,7 @@ fn get_user_input<B: io::BufRead>(buffer_reader: &mut B, input: &'static str) ->
 pub fn get_parameters<B: io::BufRead>(
     args: &mut Vec<String>,
     buffer_reader: &mut B,
-    input: &'static str,
+    input: &str,
     mandatory: bool,
 ) -> Option<String> {
     if !args.is_empty() {
@@ -24,6 +28,70 @@ pub fn get_parameters<B: io::BufRead>(
     }
 }
 
+/// Same as `get_parameters`, but asks again when a value typed by the user is rejected by `validate`.
+pub fn get_valid_parameter<B: io::BufRead, F: Fn(&str) -> Result<(), GeneratorError>>(
+    args: &mut Vec<String>,
+    buffer_reader: &mut B,
+    input: &str,
+    mandatory: bool,
+    validate: F,
+) -> Result<Option<String>, GeneratorError> {
+    if !args.is_empty() || !mandatory {
+        let parameter = get_parameters(args, buffer_reader, input, mandatory);
+        if let Some(parameter) = &parameter {
+            validate(parameter)?;
+        }
+        return Ok(parameter);
+    }
+
+    let mut suggestion: Option<String> = None;
+    for attempt in 1..=MAX_ATTEMPTS {
+        let mut parameter = get_user_input(buffer_reader, input);
+        if parameter.is_empty() {
+            if let Some(suggestion) = suggestion.take() {
+                parameter = suggestion;
+            }
+        }
+        match validate(&parameter) {
+            Ok(()) => return Ok(Some(parameter)),
+            Err(error) if attempt == MAX_ATTEMPTS => return Err(error),
+            Err(error) => {
+                println!("{}", error);
+                suggestion = error.suggestion().map(str::to_string);
+                if let Some(suggestion) = &suggestion {
+                    println!("Leave empty to use '{}'", suggestion);
+                }
+            }
+        }
+    }
+    Err(GeneratorError::UnableToReadMandatoryParameter)
+}
+
+/// Removes `--flag value` or `--flag=value` from the args and returns its value.
+pub fn take_flag(args: &mut Vec<String>, flag: &str) -> Option<String> {
+    let prefix = format!("{}=", flag);
+    let index = args
+        .iter()
+        .position(|arg| arg == flag || arg.starts_with(&prefix))?;
+    let arg = args.remove(index);
+    match arg.strip_prefix(&prefix) {
+        Some(value) => Some(value.to_string()),
+        None if index < args.len() => Some(args.remove(index)),
+        None => None,
+    }
+}
+
+/// Removes the `--flag` switch from the args and tells whether it was there.
+pub fn take_switch(args: &mut Vec<String>, flag: &str) -> bool {
+    match args.iter().position(|arg| arg == flag) {
+        Some(index) => {
+            args.remove(index);
+            true
+        }
+        None => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -123,4 +191,140 @@ mod tests {
         assert!(parameter.is_some());
         assert_eq!(Some(String::from("toto")), parameter);
     }
+
+    #[test]
+    fn take_flag_should_remove_flag_and_value() {
+        // GIVEN args containing a flag followed by its value
+        let mut args = vec![
+            String::from("spigot"),
+            String::from("--template-dir"),
+            String::from("./templates"),
+            String::from("MyPlugin"),
+        ];
+
+        // WHEN we take the flag
+        let value = take_flag(&mut args, "--template-dir");
+
+        // THEN the value should be returned and both removed from the args
+        assert_eq!(Some(String::from("./templates")), value);
+        assert_eq!(vec![String::from("spigot"), String::from("MyPlugin")], args);
+    }
+
+    #[test]
+    fn take_flag_should_support_equal_sign() {
+        // GIVEN args containing a flag with an inline value
+        let mut args = vec![String::from("--template-dir=./templates")];
+
+        // WHEN we take the flag
+        let value = take_flag(&mut args, "--template-dir");
+
+        // THEN the value should be returned
+        assert_eq!(Some(String::from("./templates")), value);
+        assert!(args.is_empty());
+    }
+
+    #[test]
+    fn take_flag_should_return_none_when_absent() {
+        // GIVEN args without the flag
+        let mut args = vec![String::from("spigot")];
+
+        // WHEN we take the flag
+        let value = take_flag(&mut args, "--template-dir");
+
+        // THEN nothing should be returned nor removed
+        assert_eq!(None, value);
+        assert_eq!(vec![String::from("spigot")], args);
+    }
+
+    fn validate_lowercase(value: &str) -> Result<(), GeneratorError> {
+        if !value.is_empty() && value == value.to_lowercase() {
+            Ok(())
+        } else {
+            Err(GeneratorError::InvalidGroupId {
+                group_id: value.to_string(),
+                reason: String::from("not lowercase"),
+                suggestion: value.to_lowercase(),
+            })
+        }
+    }
+
+    #[test]
+    fn get_valid_parameter_should_reject_invalid_argument() {
+        // GIVEN an invalid value passed as argument
+        let mut args = vec![String::from("Com.Test")];
+        let mut buffer_reader = Cursor::new("com.test\n");
+
+        // WHEN we get the parameter
+        let parameter =
+            get_valid_parameter(&mut args, &mut buffer_reader, "", true, validate_lowercase);
+
+        // THEN the value should be rejected without prompting
+        assert!(matches!(
+            parameter,
+            Err(GeneratorError::InvalidGroupId { .. })
+        ));
+    }
+
+    #[test]
+    fn get_valid_parameter_should_prompt_again_after_invalid_input() {
+        // GIVEN an invalid then a valid user input
+        let mut args: Vec<String> = Vec::new();
+        let mut buffer_reader = Cursor::new("Com.Test\ncom.other\n");
+
+        // WHEN we get the parameter
+        let parameter =
+            get_valid_parameter(&mut args, &mut buffer_reader, "", true, validate_lowercase);
+
+        // THEN the second input should be returned
+        assert_eq!(Some(String::from("com.other")), parameter.unwrap());
+    }
+
+    #[test]
+    fn get_valid_parameter_should_use_suggestion_on_empty_input() {
+        // GIVEN an invalid user input followed by an empty one
+        let mut args: Vec<String> = Vec::new();
+        let mut buffer_reader = Cursor::new("Com.Test\n\n");
+
+        // WHEN we get the parameter
+        let parameter =
+            get_valid_parameter(&mut args, &mut buffer_reader, "", true, validate_lowercase);
+
+        // THEN the suggested value should be returned
+        assert_eq!(Some(String::from("com.test")), parameter.unwrap());
+    }
+
+    #[test]
+    fn get_valid_parameter_should_give_up_after_too_many_attempts() {
+        // GIVEN only invalid user inputs
+        let mut args: Vec<String> = Vec::new();
+        let mut buffer_reader = Cursor::new("A\nB\nC\nd\n");
+
+        // WHEN we get the parameter
+        let parameter =
+            get_valid_parameter(&mut args, &mut buffer_reader, "", true, validate_lowercase);
+
+        // THEN the last error should be returned
+        assert!(matches!(
+            parameter,
+            Err(GeneratorError::InvalidGroupId { group_id, .. }) if group_id == "C"
+        ));
+    }
+
+    #[test]
+    fn take_switch_should_remove_the_switch() {
+        // GIVEN args containing a switch
+        let mut args = vec![
+            String::from("PlayerJoinEvent"),
+            String::from("--ignore-cancelled"),
+        ];
+
+        // WHEN we take it twice
+        let first = take_switch(&mut args, "--ignore-cancelled");
+        let second = take_switch(&mut args, "--ignore-cancelled");
+
+        // THEN it should only be found once
+        assert!(first);
+        assert!(!second);
+        assert_eq!(vec![String::from("PlayerJoinEvent")], args);
+    }
 }
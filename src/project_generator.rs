@@ -1,7 +1,140 @@
-mod content_generator;
+pub mod api_catalog;
+pub mod api_snapshot;
+pub mod build_model;
+pub mod bytecode_scan;
+pub mod class_file;
+pub mod config_spec;
+pub mod content_generator;
+pub mod database;
+pub mod dependencies;
+pub mod dev_server;
+pub mod event_catalog;
+pub mod existing_project;
+pub mod file_change;
 mod file_operations;
+pub mod gradle_build;
+pub mod jar_inspector;
+pub mod library_catalog;
+pub mod maven_pom;
+pub mod merge;
+pub mod messages;
+pub mod metrics;
+pub mod naming;
+pub mod paper_migration;
+pub mod plugin_descriptor;
+pub mod plugin_graph;
+pub mod plugin_lint;
+pub mod project_info;
+pub mod project_metadata;
+mod registry;
+pub mod scaffold;
 pub mod spigot;
+pub mod template_directory;
+pub mod template_engine;
+pub mod template_pack;
 mod tests;
+pub mod upgrade;
+pub mod validation;
+pub mod version_catalog;
+pub mod version_update;
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::errors::GeneratorError;
+use crate::project_generator::file_operations::{
+    create_directory, create_file_with_parents, is_contained_path,
+};
+use crate::project_generator::project_metadata::{write_generated_files, ProjectMetadata};
+use crate::project_generator::spigot::SpigotGenerator;
+
+pub use registry::GeneratorRegistry;
+
+pub type Parameters = HashMap<String, String>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParameterDescriptor {
+    pub name: String,
+    pub prompt: String,
+    pub mandatory: bool,
+    pub default: Option<String>,
+}
+
+impl ParameterDescriptor {
+    pub fn new(name: &str, prompt: &str, mandatory: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            prompt: prompt.to_string(),
+            mandatory,
+            default: None,
+        }
+    }
+
+    pub fn with_default(mut self, default: &str) -> Self {
+        self.default = Some(default.to_string());
+        self
+    }
+}
+
+/// A generator able to describe, validate and generate a project from a set of parameters.
+pub trait ProjectGenerator {
+    /// The parameters the generator needs, in the order they should be asked for.
+    fn parameters(&self) -> Vec<ParameterDescriptor>;
+
+    fn validate(&self, parameters: &Parameters) -> Result<(), GeneratorError>;
+
+    /// Validates a single parameter as soon as it is read, so it can be asked for again.
+    fn validate_parameter(&self, _name: &str, _value: &str) -> Result<(), GeneratorError> {
+        Ok(())
+    }
+
+    /// Renders the project files without writing them.
+    fn render(&self, parameters: &Parameters) -> Result<RenderedProject, GeneratorError>;
+
+    fn generate(&self, parameters: &Parameters) -> Result<(), GeneratorError> {
+        self.render(parameters)?.write()
+    }
+}
+
+/// The rendered files of a project, by path relative to its root folder.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderedProject {
+    pub root: String,
+    pub files: Vec<(String, String)>,
+    pub metadata: ProjectMetadata,
+}
+
+impl RenderedProject {
+    /// Writes the files and the metadata recording them.
+    pub fn write(self) -> Result<(), GeneratorError> {
+        self.check_paths()?;
+        create_directory(&self.root)?;
+        for (path, content) in &self.files {
+            create_file_with_parents(&format!("{}/{}", self.root, path), content)?;
+        }
+        let root = PathBuf::from(&self.root);
+        self.record(&root)
+    }
+
+    /// Writes the metadata and a copy of the files in the project at `root`.
+    pub fn record(mut self, root: &Path) -> Result<(), GeneratorError> {
+        self.check_paths()?;
+        self.metadata.record_files(&self.files);
+        self.metadata.write(root)?;
+        write_generated_files(root, &self.files)
+    }
+
+    /// Fails when the root or a file path, once rendered, leaves the folder it is written to.
+    pub fn check_paths(&self) -> Result<(), GeneratorError> {
+        let outside = std::iter::once(&self.root)
+            .chain(self.files.iter().map(|(path, _)| path))
+            .find(|path| !is_contained_path(path));
+        match outside {
+            Some(path) => Err(GeneratorError::PathOutsideProject(path.clone())),
+            None => Ok(()),
+        }
+    }
+}
 
 pub fn get_project_type(project_generator_type: &str) -> Option<ProjectGeneratorType> {
     match project_generator_type.to_lowercase().as_str() {
@@ -13,3 +146,19 @@ pub fn get_project_type(project_generator_type: &str) -> Option<ProjectGenerator
 pub enum ProjectGeneratorType {
     Spigot,
 }
+
+impl ProjectGeneratorType {
+    pub const ALL: [ProjectGeneratorType; 1] = [ProjectGeneratorType::Spigot];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProjectGeneratorType::Spigot => "spigot",
+        }
+    }
+
+    pub fn generator(&self) -> Box<dyn ProjectGenerator> {
+        match self {
+            ProjectGeneratorType::Spigot => Box::new(SpigotGenerator),
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use crate::project_generator::version_catalog::parse_version;
+
+/// A Bukkit API class or member, and the Minecraft versions providing it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ApiSignature {
+    /// The internal name of the class, such as `org/bukkit/Particle`.
+    pub owner: &'static str,
+    /// The field or method, the whole class when none.
+    pub member: Option<&'static str>,
+    pub since: &'static str,
+    pub removed: Option<&'static str>,
+    pub replacement: Option<&'static str>,
+}
+
+impl ApiSignature {
+    const fn added(owner: &'static str, member: Option<&'static str>, since: &'static str) -> Self {
+        Self {
+            owner,
+            member,
+            since,
+            removed: None,
+            replacement: None,
+        }
+    }
+
+    const fn removed(
+        owner: &'static str,
+        member: &'static str,
+        removed: &'static str,
+        replacement: &'static str,
+    ) -> Self {
+        Self {
+            owner,
+            member: Some(member),
+            since: "1.8",
+            removed: Some(removed),
+            replacement: Some(replacement),
+        }
+    }
+
+    /// Tells whether the server API of the Minecraft version lacks it.
+    pub fn is_missing_in(&self, version: &str) -> bool {
+        let version = parse_version(version);
+        version < parse_version(self.since)
+            || self
+                .removed
+                .is_some_and(|removed| version >= parse_version(removed))
+    }
+
+    /// The dotted name, `Class.member` for members.
+    pub fn name(&self) -> String {
+        let owner = self.owner.replace('/', ".");
+        match self.member {
+            Some(member) => format!("{}.{}", owner, member),
+            None => owner,
+        }
+    }
+}
+
+/// The API additions and removals plugins most often trip on, explaining what a snapshot reports
+/// missing and checked alone when there is no snapshot for the target version.
+pub const API_SIGNATURES: &[ApiSignature] = &[
+    ApiSignature::added("org/bukkit/block/data/BlockData", None, "1.13"),
+    ApiSignature::added(
+        "org/bukkit/persistence/PersistentDataContainer",
+        None,
+        "1.14",
+    ),
+    ApiSignature::added(
+        "org/bukkit/inventory/meta/ItemMeta",
+        Some("setCustomModelData"),
+        "1.14",
+    ),
+    ApiSignature::added("org/bukkit/entity/Display", None, "1.19.4"),
+    ApiSignature::added(
+        "org/bukkit/entity/Player",
+        Some("sendHurtAnimation"),
+        "1.19.4",
+    ),
+    ApiSignature::added("org/bukkit/Material", Some("SHORT_GRASS"), "1.20.3"),
+    ApiSignature::added(
+        "org/bukkit/inventory/meta/components/FoodComponent",
+        None,
+        "1.20.5",
+    ),
+    ApiSignature::added("org/bukkit/Particle", Some("DUST"), "1.20.5"),
+    ApiSignature::added(
+        "org/bukkit/enchantments/Enchantment",
+        Some("UNBREAKING"),
+        "1.20.5",
+    ),
+    ApiSignature::added(
+        "org/bukkit/potion/PotionEffectType",
+        Some("STRENGTH"),
+        "1.20.5",
+    ),
+    ApiSignature::added("org/bukkit/entity/EntityType", Some("ITEM"), "1.20.5"),
+    ApiSignature::added("org/bukkit/inventory/view/AnvilView", None, "1.21"),
+    ApiSignature::added(
+        "org/bukkit/attribute/Attribute",
+        Some("MAX_HEALTH"),
+        "1.21.3",
+    ),
+    ApiSignature::removed("org/bukkit/Material", "GRASS", "1.20.3", "SHORT_GRASS"),
+    ApiSignature::removed("org/bukkit/Particle", "REDSTONE", "1.20.5", "DUST"),
+    ApiSignature::removed(
+        "org/bukkit/enchantments/Enchantment",
+        "DURABILITY",
+        "1.20.5",
+        "UNBREAKING",
+    ),
+    ApiSignature::removed(
+        "org/bukkit/potion/PotionEffectType",
+        "INCREASE_DAMAGE",
+        "1.20.5",
+        "STRENGTH",
+    ),
+    ApiSignature::removed(
+        "org/bukkit/entity/EntityType",
+        "DROPPED_ITEM",
+        "1.20.5",
+        "ITEM",
+    ),
+    ApiSignature::removed(
+        "org/bukkit/attribute/Attribute",
+        "GENERIC_MAX_HEALTH",
+        "1.21.3",
+        "MAX_HEALTH",
+    ),
+];
+
+/// The signatures the server API of the Minecraft version lacks.
+pub fn missing_apis(version: &str) -> Vec<&'static ApiSignature> {
+    API_SIGNATURES
+        .iter()
+        .filter(|signature| signature.is_missing_in(version))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_apis_should_follow_additions_and_removals() {
+        // GIVEN versions before and after the 1.20.5 renames
+
+        // WHEN we list the APIs they lack
+        let before = missing_apis("1.20.4");
+        let after = missing_apis("1.21");
+
+        // THEN the new names should be missing before and the old ones after
+        let names = |signatures: Vec<&ApiSignature>| {
+            signatures
+                .iter()
+                .map(|signature| signature.name())
+                .collect::<Vec<_>>()
+        };
+        assert!(names(before.clone()).contains(&String::from("org.bukkit.Particle.DUST")));
+        assert!(!names(before).contains(&String::from("org.bukkit.Particle.REDSTONE")));
+        assert_eq!(
+            vec![
+                "org.bukkit.attribute.Attribute.MAX_HEALTH",
+                "org.bukkit.Material.GRASS",
+                "org.bukkit.Particle.REDSTONE",
+                "org.bukkit.enchantments.Enchantment.DURABILITY",
+                "org.bukkit.potion.PotionEffectType.INCREASE_DAMAGE",
+                "org.bukkit.entity.EntityType.DROPPED_ITEM",
+            ],
+            names(after)
+        );
+    }
+}
@@ -0,0 +1,321 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::Path;
+
+use zip::ZipArchive;
+
+use crate::errors::GeneratorError;
+use crate::project_generator::class_file::{parse_class_file, ClassFile};
+
+const ACC_PUBLIC: u16 = 0x0001;
+const ACC_PROTECTED: u16 = 0x0004;
+
+/// Methods of the JDK supertypes every API class, interface or enum reaches, which are not in the snapshot.
+const OBJECT_METHODS: &[&str] = &[
+    "equals",
+    "hashCode",
+    "toString",
+    "getClass",
+    "notify",
+    "notifyAll",
+    "wait",
+    "clone",
+    "finalize",
+];
+const ENUM_METHODS: &[&str] = &[
+    "name",
+    "ordinal",
+    "compareTo",
+    "valueOf",
+    "getDeclaringClass",
+    "describeConstable",
+];
+
+/// A public class of the API, with what a plugin can link against.
+#[derive(Debug, Default, PartialEq)]
+pub struct ApiClass {
+    /// The super class and interfaces, by internal name.
+    pub supertypes: Vec<String>,
+    /// The public and protected fields and methods, as `name descriptor`.
+    pub members: BTreeSet<String>,
+}
+
+/// The classes and members of the server API of one Minecraft version, read from its API jar.
+#[derive(Debug, Default, PartialEq)]
+pub struct ApiSnapshot {
+    pub version: String,
+    pub classes: BTreeMap<String, ApiClass>,
+}
+
+impl ApiSnapshot {
+    /// Reads every public class of an API jar such as `spigot-api-1.21.4-R0.1-SNAPSHOT.jar`.
+    pub fn from_jar(path: &Path, version: &str) -> Result<Self, GeneratorError> {
+        let invalid =
+            |reason: String| GeneratorError::InvalidJar(path.display().to_string(), reason);
+        let file = File::open(path).map_err(|error| invalid(error.to_string()))?;
+        let mut archive = ZipArchive::new(file).map_err(|error| invalid(error.to_string()))?;
+        let mut snapshot = Self {
+            version: version.to_string(),
+            ..Self::default()
+        };
+        for index in 0..archive.len() {
+            let mut entry = archive
+                .by_index(index)
+                .map_err(|error| invalid(error.to_string()))?;
+            if !entry.name().ends_with(".class") {
+                continue;
+            }
+            let name = entry.name().to_string();
+            let mut bytes = Vec::new();
+            entry
+                .read_to_end(&mut bytes)
+                .map_err(|error| invalid(error.to_string()))?;
+            let class_file = parse_class_file(&bytes)
+                .map_err(|error| invalid(format!("{}: {}", name, error)))?;
+            snapshot.add_class(&class_file);
+        }
+        if snapshot.classes.is_empty() {
+            return Err(invalid(String::from("it has no public class")));
+        }
+        Ok(snapshot)
+    }
+
+    pub fn add_class(&mut self, class_file: &ClassFile) {
+        if class_file.access_flags & ACC_PUBLIC == 0 {
+            return;
+        }
+        let class = ApiClass {
+            supertypes: class_file
+                .super_name
+                .iter()
+                .chain(&class_file.interfaces)
+                .cloned()
+                .collect(),
+            members: class_file
+                .declared
+                .iter()
+                .filter(|member| member.access_flags & (ACC_PUBLIC | ACC_PROTECTED) != 0)
+                .map(|member| format!("{} {}", member.name, member.descriptor))
+                .collect(),
+        };
+        self.classes.insert(class_file.name.clone(), class);
+    }
+
+    pub fn load(path: &Path) -> Result<Self, GeneratorError> {
+        let invalid = |message: String| {
+            GeneratorError::InvalidConfiguration(path.display().to_string(), message)
+        };
+        let content = fs::read_to_string(path).map_err(|error| invalid(error.to_string()))?;
+        Self::parse(&content).map_err(invalid)
+    }
+
+    /// Reads the text form: a `# API <version>` header, then each class with its supertypes
+    /// after a colon, followed by its members indented by two spaces.
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let mut lines = content.lines().enumerate();
+        let version = lines
+            .next()
+            .and_then(|(_, header)| header.strip_prefix("# API "))
+            .ok_or_else(|| String::from("it does not start with a '# API <version>' header"))?;
+        let mut snapshot = Self {
+            version: version.trim().to_string(),
+            ..Self::default()
+        };
+        let mut current: Option<&mut ApiClass> = None;
+        for (index, line) in lines {
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(member) = line.strip_prefix("  ") {
+                match current.as_mut() {
+                    Some(class) => class.members.insert(member.to_string()),
+                    None => return Err(format!("line {} is a member outside a class", index + 1)),
+                };
+                continue;
+            }
+            let (name, supertypes) = line.split_once(" : ").unwrap_or((line, ""));
+            current = Some(
+                snapshot
+                    .classes
+                    .entry(name.to_string())
+                    .or_insert(ApiClass {
+                        supertypes: supertypes.split_whitespace().map(str::to_string).collect(),
+                        members: BTreeSet::new(),
+                    }),
+            );
+        }
+        Ok(snapshot)
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = format!("# API {}\n", self.version);
+        for (name, class) in &self.classes {
+            text.push_str(name);
+            if !class.supertypes.is_empty() {
+                text.push_str(" : ");
+                text.push_str(&class.supertypes.join(" "));
+            }
+            text.push('\n');
+            for member in &class.members {
+                text.push_str("  ");
+                text.push_str(member);
+                text.push('\n');
+            }
+        }
+        text
+    }
+
+    /// Tells whether the snapshot holds the package tree of the class, such as `org/bukkit`,
+    /// so classes of other libraries or platforms are not reported as missing.
+    pub fn covers(&self, class: &str) -> bool {
+        let mut segments = class.splitn(3, '/');
+        let (Some(first), Some(second), Some(_)) =
+            (segments.next(), segments.next(), segments.next())
+        else {
+            return false;
+        };
+        let prefix = format!("{}/{}/", first, second);
+        self.classes
+            .range(prefix.clone()..)
+            .next()
+            .is_some_and(|(name, _)| name.starts_with(&prefix))
+    }
+
+    /// Resolves a member the way the JVM does, through the class and its supertypes.
+    /// A supertype outside the snapshot, other than `Object` or `Enum`, may declare anything.
+    pub fn has_member(&self, owner: &str, name: &str, descriptor: &str) -> bool {
+        let member = format!("{} {}", name, descriptor);
+        let mut pending = vec![owner];
+        let mut visited = BTreeSet::new();
+        while let Some(class) = pending.pop() {
+            if !visited.insert(class) {
+                continue;
+            }
+            match self.classes.get(class) {
+                Some(api_class) if api_class.members.contains(&member) => return true,
+                Some(api_class) => pending.extend(api_class.supertypes.iter().map(String::as_str)),
+                None => {
+                    let known = match class {
+                        "java/lang/Object" => OBJECT_METHODS.contains(&name),
+                        "java/lang/Enum" => {
+                            OBJECT_METHODS.contains(&name) || ENUM_METHODS.contains(&name)
+                        }
+                        _ => true,
+                    };
+                    if known {
+                        return true;
+                    }
+                }
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project_generator::class_file::DeclaredMember;
+
+    fn class_file(
+        name: &str,
+        super_name: &str,
+        interfaces: &[&str],
+        members: &[(u16, &str, &str)],
+    ) -> ClassFile {
+        ClassFile {
+            access_flags: ACC_PUBLIC,
+            name: name.to_string(),
+            super_name: Some(super_name.to_string()),
+            interfaces: interfaces.iter().map(|name| name.to_string()).collect(),
+            declared: members
+                .iter()
+                .map(|(access_flags, name, descriptor)| DeclaredMember {
+                    access_flags: *access_flags,
+                    name: name.to_string(),
+                    descriptor: descriptor.to_string(),
+                })
+                .collect(),
+            ..ClassFile::default()
+        }
+    }
+
+    fn snapshot() -> ApiSnapshot {
+        let mut snapshot = ApiSnapshot {
+            version: String::from("1.21"),
+            ..ApiSnapshot::default()
+        };
+        for class_file in [
+            class_file(
+                "org/bukkit/Particle",
+                "java/lang/Enum",
+                &["org/bukkit/Keyed"],
+                &[
+                    (0x4019, "DUST", "Lorg/bukkit/Particle;"),
+                    (0x0002, "data", "Ljava/lang/Class;"),
+                ],
+            ),
+            class_file(
+                "org/bukkit/Keyed",
+                "java/lang/Object",
+                &[],
+                &[(0x0401, "getKey", "()Lorg/bukkit/NamespacedKey;")],
+            ),
+            class_file(
+                "org/bukkit/plugin/InvalidPluginException",
+                "java/lang/Exception",
+                &[],
+                &[],
+            ),
+        ] {
+            snapshot.add_class(&class_file);
+        }
+        snapshot
+    }
+
+    #[test]
+    fn has_member_should_resolve_through_supertypes() {
+        // GIVEN a snapshot with an enum implementing an interface
+        let snapshot = snapshot();
+
+        // WHEN we resolve members
+        // THEN declared, inherited and JDK members should be found, removed ones not
+        assert!(snapshot.has_member("org/bukkit/Particle", "DUST", "Lorg/bukkit/Particle;"));
+        assert!(snapshot.has_member(
+            "org/bukkit/Particle",
+            "getKey",
+            "()Lorg/bukkit/NamespacedKey;"
+        ));
+        assert!(snapshot.has_member("org/bukkit/Particle", "ordinal", "()I"));
+        assert!(!snapshot.has_member("org/bukkit/Particle", "REDSTONE", "Lorg/bukkit/Particle;"));
+        assert!(!snapshot.has_member("org/bukkit/Particle", "data", "Ljava/lang/Class;"));
+        assert!(snapshot.has_member(
+            "org/bukkit/plugin/InvalidPluginException",
+            "getMessage",
+            "()Ljava/lang/String;"
+        ));
+
+        // AND only the package trees of the snapshot should be covered
+        assert!(snapshot.covers("org/bukkit/entity/Display"));
+        assert!(!snapshot.covers("io/papermc/paper/event/player/AsyncChatEvent"));
+    }
+
+    #[test]
+    fn parse_should_read_back_the_text_form() {
+        // GIVEN a snapshot
+        let snapshot = snapshot();
+
+        // WHEN we write it and read it back
+        let text = snapshot.to_text();
+        let parsed = ApiSnapshot::parse(&text).unwrap();
+
+        // THEN it should be unchanged
+        assert!(text.starts_with(
+            "# API 1.21\norg/bukkit/Keyed : java/lang/Object\n  getKey ()Lorg/bukkit/NamespacedKey;\n"
+        ));
+        assert_eq!(snapshot, parsed);
+        assert!(ApiSnapshot::parse("org/bukkit/Particle\n").is_err());
+        assert!(ApiSnapshot::parse("# API 1.21\n  DUST Lorg/bukkit/Particle;\n").is_err());
+    }
+}
@@ -0,0 +1,186 @@
+use std::fs;
+use std::path::Path;
+
+use crate::errors::GeneratorError;
+use crate::project_generator::dependencies::{Dependency, Repository};
+use crate::project_generator::existing_project::POM_PATH;
+use crate::project_generator::file_change::FileChange;
+use crate::project_generator::project_metadata::METADATA_FILE_NAME;
+use crate::project_generator::{gradle_build, maven_pom, RenderedProject};
+
+pub const GRADLE_BUILD_PATH: &str = "build.gradle.kts";
+pub const GRADLE_SETTINGS_PATH: &str = "settings.gradle.kts";
+
+/// What Maven and Gradle builds of a plugin have in common, the unit both are converted through.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BuildModel {
+    pub group_id: String,
+    pub artifact_id: String,
+    pub version: String,
+    pub description: Option<String>,
+    pub java_version: Option<String>,
+    pub encoding: Option<String>,
+    /// User properties, such as `spigot.version`, in declaration order.
+    pub properties: Vec<(String, String)>,
+    pub repositories: Vec<Repository>,
+    pub dependencies: Vec<Dependency>,
+    pub shade: Option<Shade>,
+    pub resource_filtering: Option<ResourceFiltering>,
+}
+
+/// The maven-shade-plugin or Shadow configuration.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Shade {
+    /// Package and relocated package.
+    pub relocations: Vec<(String, String)>,
+    pub minimize: bool,
+    pub excludes: Vec<String>,
+}
+
+/// Property expansion in the resources, limited to `includes` when not empty.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResourceFiltering {
+    pub includes: Vec<String>,
+}
+
+/// A build converted to the other build system, and what could not be.
+#[derive(Debug, Default)]
+pub struct BuildConversion {
+    pub changes: Vec<FileChange>,
+    pub notes: Vec<String>,
+}
+
+/// Replaces the `pom.xml` with a `build.gradle.kts` and a `settings.gradle.kts`.
+pub fn convert_to_gradle(root: &Path) -> Result<BuildConversion, GeneratorError> {
+    let pom = read(root, POM_PATH)?;
+    let mut notes = Vec::new();
+    let model = maven_pom::read_build_model(&pom, &mut notes)?;
+    let mut changes = vec![
+        FileChange::created(GRADLE_BUILD_PATH, gradle_build::write_build(&model)),
+        FileChange::created(GRADLE_SETTINGS_PATH, gradle_build::write_settings(&model)),
+        FileChange::deleted(POM_PATH, pom),
+    ];
+    changes.extend(record_build_system(root, "gradle"));
+    Ok(BuildConversion { changes, notes })
+}
+
+/// Replaces the `build.gradle.kts` and `settings.gradle.kts` with a `pom.xml`.
+pub fn convert_to_maven(root: &Path) -> Result<BuildConversion, GeneratorError> {
+    let build = read(root, GRADLE_BUILD_PATH)?;
+    let settings = fs::read_to_string(root.join(GRADLE_SETTINGS_PATH)).ok();
+    let mut notes = Vec::new();
+    let model = gradle_build::read_build_model(&build, settings.as_deref(), &mut notes)?;
+    let mut changes = vec![
+        FileChange::created(POM_PATH, maven_pom::write_pom(&model)),
+        FileChange::deleted(GRADLE_BUILD_PATH, build),
+    ];
+    if let Some(settings) = settings {
+        changes.push(FileChange::deleted(GRADLE_SETTINGS_PATH, settings));
+    }
+    changes.extend(record_build_system(root, "maven"));
+    Ok(BuildConversion { changes, notes })
+}
+
+/// Replaces the rendered `pom.xml` with the Gradle build `convert-build gradle` writes from it.
+pub fn render_gradle(rendered: &mut RenderedProject) -> Result<(), GeneratorError> {
+    let Some(index) = rendered.files.iter().position(|(path, _)| path == POM_PATH) else {
+        return Ok(());
+    };
+    let (_, pom) = rendered.files.remove(index);
+    let model = maven_pom::read_build_model(&pom, &mut Vec::new())?;
+    rendered.files.insert(
+        index,
+        (
+            GRADLE_SETTINGS_PATH.to_string(),
+            gradle_build::write_settings(&model),
+        ),
+    );
+    rendered.files.insert(
+        index,
+        (
+            GRADLE_BUILD_PATH.to_string(),
+            gradle_build::write_build(&model),
+        ),
+    );
+    rendered.metadata.build_system = Some(String::from("gradle"));
+    Ok(())
+}
+
+fn read(root: &Path, path: &str) -> Result<String, GeneratorError> {
+    fs::read_to_string(root.join(path)).map_err(|error| {
+        GeneratorError::InvalidProject(
+            root.display().to_string(),
+            format!("unable to read {}: {}", path, error),
+        )
+    })
+}
+
+/// The `build-system` of the `.mcdev.toml`, when the project has one.
+fn record_build_system(root: &Path, build_system: &str) -> Option<FileChange> {
+    let before = fs::read_to_string(root.join(METADATA_FILE_NAME)).ok()?;
+    let after = before
+        .split_inclusive('\n')
+        .map(|line| match line.starts_with("build-system = ") {
+            true => format!("build-system = \"{}\"\n", build_system),
+            false => line.to_string(),
+        })
+        .collect::<String>();
+    (after != before).then(|| FileChange::edited(METADATA_FILE_NAME, before, after))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project_generator::spigot::SpigotGenerator;
+    use crate::project_generator::{Parameters, ProjectGenerator};
+
+    #[test]
+    fn convert_to_gradle_should_keep_generated_build() {
+        // GIVEN a generated project shading bStats and with an extra test dependency
+        let parameters = Parameters::from([
+            (String::from("name"), String::from("GradleConversion")),
+            (String::from("version"), String::from("1.21")),
+            (String::from("group_id"), String::from("com.test")),
+            (String::from("bstats_id"), String::from("1234")),
+        ]);
+        SpigotGenerator.generate(&parameters).unwrap();
+        let root = Path::new("gradleconversion");
+        let pom = fs::read_to_string(root.join(POM_PATH)).unwrap().replace(
+            "    </dependencies>",
+            "        <dependency>\n            <groupId>org.junit.jupiter</groupId>\n            <artifactId>junit-jupiter</artifactId>\n            <version>5.11.0</version>\n            <scope>test</scope>\n        </dependency>\n    </dependencies>",
+        );
+        fs::write(root.join(POM_PATH), &pom).unwrap();
+
+        // WHEN we convert it to Gradle and back
+        let conversion = convert_to_gradle(root).unwrap();
+        for change in &conversion.changes {
+            change.write(root).unwrap();
+        }
+        let build = fs::read_to_string(root.join(GRADLE_BUILD_PATH)).unwrap();
+        let back = convert_to_maven(root).unwrap();
+
+        // THEN the Gradle build should map scopes and shading
+        assert_eq!(vec!["<name> is not converted"], conversion.notes);
+        assert!(build.contains("    id(\"com.gradleup.shadow\") version \"8.3.5\"\n"));
+        assert!(build.contains("    compileOnly(\"org.spigotmc:spigot-api:${property(\"spigot.version\")}\")\n    implementation(\"org.bstats:bstats-bukkit:3.0.2\")\n    testImplementation(\"org.junit.jupiter:junit-jupiter:5.11.0\")\n"));
+        assert!(build.contains("    relocate(\"org.bstats\", \"com.test.libs.bstats\")\n"));
+        assert!(!root.join(POM_PATH).exists());
+        assert_eq!(
+            "rootProject.name = \"gradle-conversion\"\n",
+            fs::read_to_string(root.join(GRADLE_SETTINGS_PATH)).unwrap()
+        );
+
+        // AND converting back should describe the same build
+        let mut notes = Vec::new();
+        assert_eq!(
+            maven_pom::read_build_model(&pom, &mut notes).unwrap(),
+            maven_pom::read_build_model(back.changes[0].after.as_ref().unwrap(), &mut notes)
+                .unwrap()
+        );
+        assert!(fs::read_to_string(root.join(METADATA_FILE_NAME))
+            .unwrap()
+            .contains("build-system = \"gradle\"\n"));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
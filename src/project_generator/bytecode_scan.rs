@@ -0,0 +1,351 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::Path;
+
+use zip::ZipArchive;
+
+use crate::errors::GeneratorError;
+use crate::project_generator::api_catalog::{missing_apis, ApiSignature, API_SIGNATURES};
+use crate::project_generator::api_snapshot::ApiSnapshot;
+use crate::project_generator::class_file::{parse_class_file, ClassFile, MemberReference};
+use crate::project_generator::version_catalog::find_release;
+
+/// Server internals, which change with every Minecraft version and are not part of the API.
+const INTERNAL_PACKAGES: [&str; 2] = ["net/minecraft/", "org/bukkit/craftbukkit/"];
+const CRAFTBUKKIT_PACKAGE: &str = "org/bukkit/craftbukkit/";
+
+#[derive(Debug, Default)]
+pub struct BytecodeScan {
+    pub classes: usize,
+    /// Server internal classes referenced, and the classes referencing them, in dotted names.
+    pub internals: BTreeMap<String, BTreeSet<String>>,
+    /// The versioned CraftBukkit packages referenced, such as `v1_21_R1`.
+    pub craftbukkit_revisions: BTreeSet<String>,
+    /// APIs missing in the target version by dotted name, `Class.member(parameters)` for methods.
+    pub missing_apis: BTreeMap<String, MissingApi>,
+    pub notes: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct MissingApi {
+    /// The catalog entry telling when it changed, if the catalog lists it.
+    pub signature: Option<&'static ApiSignature>,
+    /// The classes using it, in dotted names.
+    pub users: BTreeSet<String>,
+}
+
+/// What the references are checked against: the API snapshot of the target version when there
+/// is one, else only the changes of the catalog.
+struct ApiCheck<'a> {
+    snapshot: Option<&'a ApiSnapshot>,
+    missing: Vec<&'static ApiSignature>,
+}
+
+/// Scans the classes of a jar or of a directory such as `target/classes` for server internals
+/// and APIs the Minecraft version lacks.
+pub fn scan_bytecode(
+    path: &Path,
+    version: &str,
+    snapshot: Option<&ApiSnapshot>,
+) -> Result<BytecodeScan, GeneratorError> {
+    find_release(version)?;
+    let check = ApiCheck {
+        snapshot,
+        missing: missing_apis(version),
+    };
+    let mut scan = BytecodeScan::default();
+    let scan_bytes =
+        |scan: &mut BytecodeScan, name: &str, bytes: &[u8]| match parse_class_file(bytes) {
+            Ok(class_file) => scan_class(scan, &class_file, &check),
+            Err(error) => scan.notes.push(format!("{}: {}", name, error)),
+        };
+
+    let invalid = |reason: String| GeneratorError::InvalidJar(path.display().to_string(), reason);
+    if path.is_dir() {
+        let mut directories = vec![path.to_path_buf()];
+        while let Some(directory) = directories.pop() {
+            for entry in fs::read_dir(&directory).map_err(|error| invalid(error.to_string()))? {
+                let entry = entry.map_err(|error| invalid(error.to_string()))?.path();
+                if entry.is_dir() {
+                    directories.push(entry);
+                } else if entry
+                    .extension()
+                    .is_some_and(|extension| extension == "class")
+                {
+                    let bytes = fs::read(&entry).map_err(|error| invalid(error.to_string()))?;
+                    scan_bytes(&mut scan, &entry.display().to_string(), &bytes);
+                }
+            }
+        }
+    } else {
+        let file = File::open(path).map_err(|error| invalid(error.to_string()))?;
+        let mut archive = ZipArchive::new(file).map_err(|error| invalid(error.to_string()))?;
+        for index in 0..archive.len() {
+            let mut entry = archive
+                .by_index(index)
+                .map_err(|error| invalid(error.to_string()))?;
+            if !entry.name().ends_with(".class") {
+                continue;
+            }
+            let name = entry.name().to_string();
+            let mut bytes = Vec::new();
+            entry
+                .read_to_end(&mut bytes)
+                .map_err(|error| invalid(error.to_string()))?;
+            scan_bytes(&mut scan, &name, &bytes);
+        }
+    }
+    Ok(scan)
+}
+
+fn scan_class(scan: &mut BytecodeScan, class_file: &ClassFile, check: &ApiCheck) {
+    scan.classes += 1;
+    let user = dotted(&class_file.name);
+    for class in &class_file.classes {
+        if !is_internal(class) {
+            continue;
+        }
+        scan.internals
+            .entry(dotted(class))
+            .or_default()
+            .insert(user.clone());
+        let revision = class
+            .strip_prefix(CRAFTBUKKIT_PACKAGE)
+            .and_then(|rest| rest.split('/').next())
+            .filter(|package| package.starts_with("v1_"));
+        if let Some(revision) = revision {
+            scan.craftbukkit_revisions.insert(revision.to_string());
+        }
+    }
+
+    let mut add_missing = |name: String, signature: Option<&'static ApiSignature>| {
+        let missing = scan.missing_apis.entry(name).or_default();
+        missing.signature = signature;
+        missing.users.insert(user.clone());
+    };
+    let Some(snapshot) = check.snapshot else {
+        for signature in &check.missing {
+            let used = match signature.member {
+                Some(member) => class_file.members.iter().any(|reference| {
+                    reference.owner == signature.owner && reference.name == member
+                }),
+                None => class_file.classes.contains(signature.owner),
+            };
+            if used {
+                add_missing(signature.name(), Some(signature));
+            }
+        }
+        return;
+    };
+    let known = |class: &str| {
+        is_internal(class) || !snapshot.covers(class) || snapshot.classes.contains_key(class)
+    };
+    for class in class_file.classes.iter().filter(|class| !known(class)) {
+        let signature = API_SIGNATURES
+            .iter()
+            .find(|signature| signature.owner == class && signature.member.is_none());
+        add_missing(dotted(class), signature);
+    }
+    for reference in &class_file.members {
+        if !snapshot.classes.contains_key(&reference.owner)
+            || snapshot.has_member(&reference.owner, &reference.name, &reference.descriptor)
+        {
+            continue;
+        }
+        let signature = API_SIGNATURES.iter().find(|signature| {
+            signature.owner == reference.owner && signature.member == Some(reference.name.as_str())
+        });
+        add_missing(member_name(reference), signature);
+    }
+}
+
+fn is_internal(class: &str) -> bool {
+    INTERNAL_PACKAGES
+        .iter()
+        .any(|package| class.starts_with(package))
+}
+
+/// `Class.FIELD`, or `Class.method(Type, int)` with the simple names of the parameter types.
+fn member_name(reference: &MemberReference) -> String {
+    let name = format!("{}.{}", dotted(&reference.owner), reference.name);
+    let Some(parameters) = reference
+        .descriptor
+        .strip_prefix('(')
+        .and_then(|rest| rest.split(')').next())
+    else {
+        return name;
+    };
+    let mut types = Vec::new();
+    let mut chars = parameters.chars();
+    let mut dimensions = 0;
+    while let Some(c) = chars.next() {
+        let java_type = match c {
+            '[' => {
+                dimensions += 1;
+                continue;
+            }
+            'L' => {
+                let class: String = chars.by_ref().take_while(|c| *c != ';').collect();
+                class
+                    .rsplit(['/', '$'])
+                    .next()
+                    .unwrap_or_default()
+                    .to_string()
+            }
+            'B' => String::from("byte"),
+            'C' => String::from("char"),
+            'D' => String::from("double"),
+            'F' => String::from("float"),
+            'I' => String::from("int"),
+            'J' => String::from("long"),
+            'S' => String::from("short"),
+            _ => String::from("boolean"),
+        };
+        types.push(format!("{}{}", java_type, "[]".repeat(dimensions)));
+        dimensions = 0;
+    }
+    format!("{}({})", name, types.join(", "))
+}
+
+fn dotted(internal_name: &str) -> String {
+    internal_name.replace('/', ".")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project_generator::class_file::MemberReference;
+
+    fn class_file(name: &str, classes: &[&str], members: &[(&str, &str)]) -> ClassFile {
+        ClassFile {
+            major_version: 65,
+            name: name.to_string(),
+            classes: classes.iter().map(|class| class.to_string()).collect(),
+            members: members
+                .iter()
+                .map(|(owner, name)| MemberReference {
+                    owner: owner.to_string(),
+                    name: name.to_string(),
+                    descriptor: String::from("Lorg/bukkit/Particle;"),
+                })
+                .collect(),
+            ..ClassFile::default()
+        }
+    }
+
+    #[test]
+    fn scan_class_should_report_internals_and_missing_apis() {
+        // GIVEN classes using CraftBukkit, NMS and a particle removed in 1.20.5
+        let classes = [
+            class_file(
+                "com/test/Nms",
+                &[
+                    "org/bukkit/craftbukkit/v1_20_R3/entity/CraftPlayer",
+                    "net/minecraft/server/level/ServerPlayer",
+                    "org/bukkit/Particle",
+                ],
+                &[("org/bukkit/Particle", "REDSTONE")],
+            ),
+            class_file(
+                "com/test/Effects",
+                &["org/bukkit/Particle"],
+                &[
+                    ("org/bukkit/Particle", "REDSTONE"),
+                    ("org/bukkit/Particle", "FLAME"),
+                ],
+            ),
+        ];
+
+        // WHEN we scan them against 1.21
+        let mut scan = BytecodeScan::default();
+        let check = ApiCheck {
+            snapshot: None,
+            missing: missing_apis("1.21"),
+        };
+        for class in &classes {
+            scan_class(&mut scan, class, &check);
+        }
+
+        // THEN the internals and the removed particle should be reported with their users
+        assert_eq!(2, scan.classes);
+        assert_eq!(
+            vec![
+                "net.minecraft.server.level.ServerPlayer",
+                "org.bukkit.craftbukkit.v1_20_R3.entity.CraftPlayer",
+            ],
+            scan.internals.keys().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            BTreeSet::from([String::from("v1_20_R3")]),
+            scan.craftbukkit_revisions
+        );
+        assert_eq!(
+            vec!["org.bukkit.Particle.REDSTONE"],
+            scan.missing_apis.keys().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            BTreeSet::from([
+                String::from("com.test.Effects"),
+                String::from("com.test.Nms")
+            ]),
+            scan.missing_apis["org.bukkit.Particle.REDSTONE"].users
+        );
+    }
+
+    #[test]
+    fn scan_class_should_check_every_reference_against_the_snapshot() {
+        // GIVEN the API snapshot of a version with Particle.DUST but without Display
+        let snapshot = ApiSnapshot::parse(
+            "# API 1.19.3\norg/bukkit/Particle : java/lang/Enum\n  DUST Lorg/bukkit/Particle;\norg/bukkit/entity/Player : java/lang/Object\n  sendMessage (Ljava/lang/String;)V\n",
+        )
+        .unwrap();
+        let mut class = class_file(
+            "com/test/Effects",
+            &[
+                "org/bukkit/Particle",
+                "org/bukkit/entity/Display",
+                "org/bukkit/craftbukkit/v1_19_R2/CraftServer",
+                "io/papermc/paper/event/player/AsyncChatEvent",
+            ],
+            &[
+                ("org/bukkit/Particle", "DUST"),
+                ("org/bukkit/Particle", "REDSTONE"),
+            ],
+        );
+        class.members.insert(MemberReference {
+            owner: String::from("org/bukkit/entity/Player"),
+            name: String::from("sendTitle"),
+            descriptor: String::from("(Ljava/lang/String;[Ljava/lang/String;IZ)V"),
+        });
+
+        // WHEN we scan the class against it
+        let mut scan = BytecodeScan::default();
+        let check = ApiCheck {
+            snapshot: Some(&snapshot),
+            missing: Vec::new(),
+        };
+        scan_class(&mut scan, &class, &check);
+
+        // THEN every API class or member absent from the snapshot should be reported,
+        // with the catalog entry when it lists the change
+        assert_eq!(
+            vec![
+                "org.bukkit.Particle.REDSTONE",
+                "org.bukkit.entity.Display",
+                "org.bukkit.entity.Player.sendTitle(String, String[], int, boolean)",
+            ],
+            scan.missing_apis.keys().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some("1.19.4"),
+            scan.missing_apis["org.bukkit.entity.Display"]
+                .signature
+                .map(|signature| signature.since)
+        );
+        assert!(scan.missing_apis
+            ["org.bukkit.entity.Player.sendTitle(String, String[], int, boolean)"]
+            .signature
+            .is_none());
+    }
+}
@@ -0,0 +1,327 @@
+use std::collections::BTreeSet;
+
+const MAGIC: [u8; 4] = [0xCA, 0xFE, 0xBA, 0xBE];
+
+/// A field or method of another class, as a class file references it.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MemberReference {
+    /// The internal name of the declaring class, such as `org/bukkit/entity/Player`.
+    pub owner: String,
+    pub name: String,
+    pub descriptor: String,
+}
+
+/// A field or method the class itself declares.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct DeclaredMember {
+    pub access_flags: u16,
+    pub name: String,
+    pub descriptor: String,
+}
+
+/// What a class file tells about the classes it uses and the members it declares.
+#[derive(Debug, Default, PartialEq)]
+pub struct ClassFile {
+    pub major_version: u16,
+    pub access_flags: u16,
+    /// The internal name of the class.
+    pub name: String,
+    /// The super class, absent for `java/lang/Object` and `module-info`.
+    pub super_name: Option<String>,
+    pub interfaces: Vec<String>,
+    /// Internal names of every class referenced, directly or through a member descriptor.
+    pub classes: BTreeSet<String>,
+    pub members: BTreeSet<MemberReference>,
+    pub declared: Vec<DeclaredMember>,
+}
+
+enum Constant {
+    Utf8(String),
+    Class(u16),
+    Member(u16, u16),
+    NameAndType(u16, u16),
+    Other,
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, length: usize) -> Result<&'a [u8], String> {
+        let end = self.position + length;
+        let bytes = self
+            .bytes
+            .get(self.position..end)
+            .ok_or_else(|| format!("truncated at byte {}", self.position))?;
+        self.position = end;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, String> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+}
+
+/// Parses a class file up to its methods, the attributes being of no use to find references.
+pub fn parse_class_file(bytes: &[u8]) -> Result<ClassFile, String> {
+    let mut reader = Reader { bytes, position: 0 };
+    if reader.take(4)? != MAGIC {
+        return Err(String::from("not a class file"));
+    }
+    reader.u16()?;
+    let major_version = reader.u16()?;
+
+    let count = reader.u16()? as usize;
+    let mut pool: Vec<Constant> = Vec::with_capacity(count);
+    pool.push(Constant::Other);
+    while pool.len() < count {
+        let tag = reader.u8()?;
+        let constant = match tag {
+            1 => {
+                let length = reader.u16()? as usize;
+                Constant::Utf8(String::from_utf8_lossy(reader.take(length)?).to_string())
+            }
+            7 => Constant::Class(reader.u16()?),
+            9..=11 => Constant::Member(reader.u16()?, reader.u16()?),
+            12 => Constant::NameAndType(reader.u16()?, reader.u16()?),
+            3 | 4 | 17 | 18 => reader.take(4).map(|_| Constant::Other)?,
+            5 | 6 => reader.take(8).map(|_| Constant::Other)?,
+            8 | 16 | 19 | 20 => reader.take(2).map(|_| Constant::Other)?,
+            15 => reader.take(3).map(|_| Constant::Other)?,
+            _ => return Err(format!("unknown constant pool tag {}", tag)),
+        };
+        // Long and double constants take two entries.
+        let wide = matches!(tag, 5 | 6);
+        pool.push(constant);
+        if wide {
+            pool.push(Constant::Other);
+        }
+    }
+    let access_flags = reader.u16()?;
+    let this_class = reader.u16()?;
+    let super_class = reader.u16()?;
+    let interfaces = (0..reader.u16()?)
+        .map(|_| reader.u16())
+        .collect::<Result<Vec<u16>, String>>()?;
+    let mut members = Vec::new();
+    // Fields, then methods, share the same layout.
+    for _ in 0..2 {
+        for _ in 0..reader.u16()? {
+            let member = (reader.u16()?, reader.u16()?, reader.u16()?);
+            for _ in 0..reader.u16()? {
+                reader.u16()?;
+                let length = reader.u32()? as usize;
+                reader.take(length)?;
+            }
+            members.push(member);
+        }
+    }
+
+    let utf8 = |index: u16| match pool.get(index as usize) {
+        Some(Constant::Utf8(text)) => Some(text.as_str()),
+        _ => None,
+    };
+    let class_name = |index: u16| match pool.get(index as usize) {
+        Some(Constant::Class(name)) => utf8(*name),
+        _ => None,
+    };
+
+    let mut class_file = ClassFile {
+        major_version,
+        access_flags,
+        name: class_name(this_class).unwrap_or_default().to_string(),
+        super_name: class_name(super_class).map(str::to_string),
+        interfaces: interfaces
+            .into_iter()
+            .filter_map(|index| class_name(index).map(str::to_string))
+            .collect(),
+        declared: members
+            .into_iter()
+            .filter_map(|(access_flags, name, descriptor)| {
+                Some(DeclaredMember {
+                    access_flags,
+                    name: utf8(name)?.to_string(),
+                    descriptor: utf8(descriptor)?.to_string(),
+                })
+            })
+            .collect(),
+        ..ClassFile::default()
+    };
+    for constant in &pool {
+        match constant {
+            Constant::Class(name) => {
+                let Some(name) = utf8(*name) else {
+                    continue;
+                };
+                match name.starts_with('[') {
+                    true => class_file.classes.extend(descriptor_classes(name)),
+                    false => {
+                        class_file.classes.insert(name.to_string());
+                    }
+                }
+            }
+            Constant::Member(owner, name_and_type) => {
+                let Some(Constant::NameAndType(name, descriptor)) =
+                    pool.get(*name_and_type as usize)
+                else {
+                    continue;
+                };
+                if let (Some(owner), Some(name), Some(descriptor)) =
+                    (class_name(*owner), utf8(*name), utf8(*descriptor))
+                {
+                    class_file.classes.extend(descriptor_classes(descriptor));
+                    class_file.members.insert(MemberReference {
+                        owner: owner.to_string(),
+                        name: name.to_string(),
+                        descriptor: descriptor.to_string(),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    class_file.classes.remove(&class_file.name);
+    Ok(class_file)
+}
+
+/// The internal names of the object types of a field or method descriptor.
+pub fn descriptor_classes(descriptor: &str) -> Vec<String> {
+    let mut classes = Vec::new();
+    let mut rest = descriptor;
+    while let Some(start) = rest.find('L') {
+        // Primitive and array markers are single characters, so an `L` always starts a type.
+        let Some(end) = rest[start..].find(';') else {
+            break;
+        };
+        classes.push(rest[start + 1..start + end].to_string());
+        rest = &rest[start + end + 1..];
+    }
+    classes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A class file extending `java/lang/Object`, whose constant pool holds the given classes and
+    /// `owner.name:descriptor` methods, and declaring a public `run()V` method.
+    fn class_file(name: &str, classes: &[&str], methods: &[(&str, &str, &str)]) -> Vec<u8> {
+        let mut pool: Vec<Vec<u8>> = Vec::new();
+        let add = |pool: &mut Vec<Vec<u8>>, entry: Vec<u8>| {
+            pool.push(entry);
+            pool.len() as u16
+        };
+        let utf8 = |text: &str| {
+            let mut entry = vec![1];
+            entry.extend((text.len() as u16).to_be_bytes());
+            entry.extend(text.as_bytes());
+            entry
+        };
+        let pair = |tag: u8, first: u16, second: u16| {
+            let mut entry = vec![tag];
+            entry.extend(first.to_be_bytes());
+            entry.extend(second.to_be_bytes());
+            entry
+        };
+        let class = |pool: &mut Vec<Vec<u8>>, name: &str| {
+            let name = add(pool, utf8(name));
+            let mut entry = vec![7];
+            entry.extend(name.to_be_bytes());
+            add(pool, entry)
+        };
+        let this_class = class(&mut pool, name);
+        let super_class = class(&mut pool, "java/lang/Object");
+        let run = add(&mut pool, utf8("run"));
+        let run_descriptor = add(&mut pool, utf8("()V"));
+        for name in classes {
+            class(&mut pool, name);
+        }
+        // A long constant takes two entries.
+        add(&mut pool, vec![5, 0, 0, 0, 0, 0, 0, 0, 1]);
+        pool.push(Vec::new());
+        for (owner, method, descriptor) in methods {
+            let owner = class(&mut pool, owner);
+            let method = add(&mut pool, utf8(method));
+            let descriptor = add(&mut pool, utf8(descriptor));
+            let name_and_type = add(&mut pool, pair(12, method, descriptor));
+            add(&mut pool, pair(10, owner, name_and_type));
+        }
+
+        let mut bytes = MAGIC.to_vec();
+        bytes.extend([0, 0, 0, 65]);
+        bytes.extend((pool.len() as u16 + 1).to_be_bytes());
+        for entry in pool {
+            bytes.extend(entry);
+        }
+        bytes.extend([0, 0x21]);
+        bytes.extend(this_class.to_be_bytes());
+        bytes.extend(super_class.to_be_bytes());
+        // No interface nor field, one method with an empty attribute.
+        bytes.extend([0, 0, 0, 0, 0, 1, 0, 1]);
+        bytes.extend(run.to_be_bytes());
+        bytes.extend(run_descriptor.to_be_bytes());
+        bytes.extend([0, 1]);
+        bytes.extend(run.to_be_bytes());
+        bytes.extend([0, 0, 0, 0]);
+        bytes
+    }
+
+    #[test]
+    fn parse_class_file_should_list_references() {
+        // GIVEN a class referencing CraftPlayer and a Player method through its constant pool
+        let bytes = class_file(
+            "com/test/Homes",
+            &[
+                "org/bukkit/craftbukkit/v1_21_R1/entity/CraftPlayer",
+                "[Ljava/lang/String;",
+            ],
+            &[(
+                "org/bukkit/entity/Player",
+                "sendTitle",
+                "(Ljava/lang/String;Lnet/minecraft/network/chat/Component;II)V",
+            )],
+        );
+
+        // WHEN we parse it
+        let class_file = parse_class_file(&bytes).unwrap();
+
+        // THEN every referenced class and member should be found
+        assert_eq!("com/test/Homes", class_file.name);
+        assert_eq!(65, class_file.major_version);
+        assert_eq!(Some("java/lang/Object"), class_file.super_name.as_deref());
+        assert_eq!(
+            BTreeSet::from([
+                String::from("java/lang/Object"),
+                String::from("java/lang/String"),
+                String::from("net/minecraft/network/chat/Component"),
+                String::from("org/bukkit/craftbukkit/v1_21_R1/entity/CraftPlayer"),
+                String::from("org/bukkit/entity/Player"),
+            ]),
+            class_file.classes
+        );
+        assert_eq!(1, class_file.members.len());
+
+        // AND the declared method should be read past the attributes
+        assert_eq!(
+            vec![DeclaredMember {
+                access_flags: 0x0001,
+                name: String::from("run"),
+                descriptor: String::from("()V"),
+            }],
+            class_file.declared
+        );
+        assert!(parse_class_file(&bytes[..20]).is_err());
+    }
+}
@@ -0,0 +1,404 @@
+use std::collections::BTreeMap;
+
+use serde_yaml::{Mapping, Value as YamlValue};
+
+use crate::errors::GeneratorError;
+use crate::project_generator::naming::{camel_case, pascal_case};
+use crate::project_generator::template_engine::Value;
+use crate::project_generator::validation::{invalid_parameter, validate_java_identifier};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConfigType {
+    String,
+    Int,
+    Double,
+    Boolean,
+    List,
+}
+
+impl ConfigType {
+    pub const ALL: [ConfigType; 5] = [
+        ConfigType::String,
+        ConfigType::Int,
+        ConfigType::Double,
+        ConfigType::Boolean,
+        ConfigType::List,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConfigType::String => "string",
+            ConfigType::Int => "int",
+            ConfigType::Double => "double",
+            ConfigType::Boolean => "boolean",
+            ConfigType::List => "list",
+        }
+    }
+
+    pub fn java_type(&self) -> &'static str {
+        match self {
+            ConfigType::String => "String",
+            ConfigType::Int => "int",
+            ConfigType::Double => "double",
+            ConfigType::Boolean => "boolean",
+            ConfigType::List => "List<String>",
+        }
+    }
+
+    /// The method of the generated config class reading and validating a value of this type.
+    pub fn reader(&self) -> &'static str {
+        match self {
+            ConfigType::String => "readString",
+            ConfigType::Int => "readInt",
+            ConfigType::Double => "readDouble",
+            ConfigType::Boolean => "readBoolean",
+            ConfigType::List => "readStringList",
+        }
+    }
+}
+
+/// A `config.yml` key declared as `key:type=default`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigEntry {
+    pub key: String,
+    pub kind: ConfigType,
+    pub default: YamlValue,
+}
+
+impl ConfigEntry {
+    pub fn field(&self) -> String {
+        camel_case(&self.key)
+    }
+
+    pub fn getter(&self) -> String {
+        match self.kind {
+            ConfigType::Boolean => format!("is{}", pascal_case(&self.key)),
+            _ => format!("get{}", pascal_case(&self.key)),
+        }
+    }
+
+    /// The default value as a Java expression.
+    pub fn java_default(&self) -> String {
+        match &self.default {
+            YamlValue::String(value) => java_string(value),
+            YamlValue::Sequence(values) => format!(
+                "Arrays.asList({})",
+                values
+                    .iter()
+                    .filter_map(YamlValue::as_str)
+                    .map(java_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            YamlValue::Number(number) if self.kind == ConfigType::Double => {
+                format!("{:?}", number.as_f64().unwrap_or_default())
+            }
+            other => serde_yaml::to_string(other)
+                .unwrap_or_default()
+                .trim()
+                .to_string(),
+        }
+    }
+
+    /// The values the config class template uses for this entry.
+    pub fn context_value(&self) -> Value {
+        Value::from(BTreeMap::from([
+            (String::from("key"), Value::from(java_string(&self.key))),
+            (String::from("field"), Value::from(self.field())),
+            (String::from("getter"), Value::from(self.getter())),
+            (
+                String::from("java_type"),
+                Value::from(self.kind.java_type()),
+            ),
+            (String::from("reader"), Value::from(self.kind.reader())),
+            (String::from("default"), Value::from(self.java_default())),
+        ]))
+    }
+}
+
+/// Parses `spawn.world:string=world,max-homes:int=3`, list defaults being separated by `|`.
+pub fn parse_config_spec(spec: &str) -> Result<Vec<ConfigEntry>, GeneratorError> {
+    let mut entries: Vec<ConfigEntry> = Vec::new();
+    for declaration in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+        let (declaration_key, default) = match declaration.split_once('=') {
+            Some((key, default)) => (key, Some(default.trim())),
+            None => (declaration, None),
+        };
+        let (key, kind) = match declaration_key.split_once(':') {
+            Some((key, kind)) => (key.trim(), parse_config_type(kind.trim())?),
+            None => (declaration_key.trim(), ConfigType::String),
+        };
+        validate_dotted_key("config key", key)?;
+        let entry = ConfigEntry {
+            key: key.to_string(),
+            kind,
+            default: parse_default(key, kind, default)?,
+        };
+        validate_java_identifier("config key", &entry.field())?;
+        if let Some(existing) = entries.iter().find(|existing| {
+            existing.field() == entry.field() || is_section_of(&existing.key, &entry.key)
+        }) {
+            return Err(invalid_parameter(
+                "config key",
+                key,
+                &format!("it conflicts with {}", existing.key),
+                format!("{}-value", key),
+            ));
+        }
+        entries.push(entry);
+    }
+    if entries.is_empty() {
+        return Err(GeneratorError::MissingParameter(String::from("config")));
+    }
+    Ok(entries)
+}
+
+/// The `config.yml` holding the defaults, dotted keys becoming nested sections.
+pub fn config_yml(entries: &[ConfigEntry]) -> Result<String, GeneratorError> {
+    let mut root = Mapping::new();
+    for entry in entries {
+        insert_dotted_key(&mut root, &entry.key, entry.default.clone());
+    }
+    serde_yaml::to_string(&root).map_err(|error| GeneratorError::TemplateError(error.to_string()))
+}
+
+/// Adds the defaults to an existing `config.yml`, failing if one of the keys is already declared.
+pub fn merge_config_yml(existing: &str, entries: &[ConfigEntry]) -> Result<String, GeneratorError> {
+    let mut root: Mapping = match serde_yaml::from_str(existing) {
+        Ok(YamlValue::Mapping(root)) => root,
+        Ok(_) => Mapping::new(),
+        Err(error) => {
+            return Err(GeneratorError::InvalidConfiguration(
+                String::from("config.yml"),
+                error.to_string(),
+            ))
+        }
+    };
+    for entry in entries {
+        if !insert_dotted_key(&mut root, &entry.key, entry.default.clone()) {
+            return Err(invalid_parameter(
+                "config key",
+                &entry.key,
+                "it is already declared in config.yml",
+                format!("{}-value", entry.key),
+            ));
+        }
+    }
+    serde_yaml::to_string(&root).map_err(|error| GeneratorError::TemplateError(error.to_string()))
+}
+
+/// Inserts the value under the dotted key, creating the missing sections.
+/// Returns false, leaving the mapping untouched, when the key or one of its sections is taken.
+pub fn insert_dotted_key(root: &mut Mapping, key: &str, value: YamlValue) -> bool {
+    let mut section = root;
+    let mut segments: Vec<&str> = key.split('.').collect();
+    let last = segments.pop().unwrap_or_default();
+    for segment in segments {
+        let child = section
+            .entry(YamlValue::from(segment))
+            .or_insert_with(|| YamlValue::Mapping(Mapping::new()));
+        section = match child.as_mapping_mut() {
+            Some(child) => child,
+            None => return false,
+        };
+    }
+    if section.contains_key(last) {
+        return false;
+    }
+    section.insert(YamlValue::from(last), value);
+    true
+}
+
+fn is_section_of(left: &str, right: &str) -> bool {
+    left.starts_with(&format!("{}.", right)) || right.starts_with(&format!("{}.", left))
+}
+
+fn parse_config_type(value: &str) -> Result<ConfigType, GeneratorError> {
+    ConfigType::ALL
+        .into_iter()
+        .find(|kind| kind.name() == value.to_lowercase())
+        .ok_or_else(|| {
+            invalid_parameter(
+                "config type",
+                value,
+                "it must be string, int, double, boolean or list",
+                String::from("string"),
+            )
+        })
+}
+
+/// Dotted YAML keys such as `spawn.world`, `name` naming the key in errors.
+pub fn validate_dotted_key(name: &str, key: &str) -> Result<(), GeneratorError> {
+    let valid = key.split('.').all(|segment| {
+        !segment.is_empty()
+            && segment
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    });
+    if valid {
+        Ok(())
+    } else {
+        Err(invalid_parameter(
+            name,
+            key,
+            "it must be dot separated words of letters, digits, '-' and '_'",
+            key.split('.')
+                .map(|segment| {
+                    segment
+                        .chars()
+                        .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+                        .collect::<String>()
+                })
+                .filter(|segment| !segment.is_empty())
+                .collect::<Vec<_>>()
+                .join("."),
+        ))
+    }
+}
+
+fn parse_default(
+    key: &str,
+    kind: ConfigType,
+    value: Option<&str>,
+) -> Result<YamlValue, GeneratorError> {
+    let invalid = |value: &str, suggestion: &str| {
+        invalid_parameter(
+            &format!("default of {}", key),
+            value,
+            &format!("it is not a valid {}", kind.name()),
+            suggestion.to_string(),
+        )
+    };
+    Ok(match (kind, value) {
+        (ConfigType::String, value) => YamlValue::from(value.unwrap_or_default()),
+        (ConfigType::Int, None) => YamlValue::from(0),
+        (ConfigType::Int, Some(value)) => {
+            YamlValue::from(value.parse::<i32>().map_err(|_| invalid(value, "0"))?)
+        }
+        (ConfigType::Double, None) => YamlValue::from(0.0),
+        (ConfigType::Double, Some(value)) => YamlValue::from(
+            value
+                .parse::<f64>()
+                .ok()
+                .filter(|value| value.is_finite())
+                .ok_or_else(|| invalid(value, "0.0"))?,
+        ),
+        (ConfigType::Boolean, None) => YamlValue::from(false),
+        (ConfigType::Boolean, Some(value)) => {
+            YamlValue::from(value.parse::<bool>().map_err(|_| invalid(value, "false"))?)
+        }
+        (ConfigType::List, value) => YamlValue::Sequence(
+            value
+                .unwrap_or_default()
+                .split('|')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(YamlValue::from)
+                .collect(),
+        ),
+    })
+}
+
+fn java_string(value: &str) -> String {
+    let mut literal = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\t' => literal.push_str("\\t"),
+            c => literal.push(c),
+        }
+    }
+    literal.push('"');
+    literal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_config_spec_should_read_types_and_defaults() {
+        // GIVEN a config spec with every type
+        let spec = "spawn.world:string=world, max-homes:int=3, cost:double=2.5, pvp:boolean, worlds:list=world|world_nether, motd";
+
+        // WHEN we parse it
+        let entries = parse_config_spec(spec).unwrap();
+
+        // THEN every entry should have its java field and default
+        let java: Vec<(String, String, String)> = entries
+            .iter()
+            .map(|entry| (entry.field(), entry.getter(), entry.java_default()))
+            .collect();
+        assert_eq!(
+            vec![
+                (
+                    "spawnWorld".into(),
+                    "getSpawnWorld".into(),
+                    "\"world\"".into()
+                ),
+                ("maxHomes".into(), "getMaxHomes".into(), "3".into()),
+                ("cost".into(), "getCost".into(), "2.5".into()),
+                ("pvp".into(), "isPvp".into(), "false".into()),
+                (
+                    "worlds".into(),
+                    "getWorlds".into(),
+                    "Arrays.asList(\"world\", \"world_nether\")".into()
+                ),
+                ("motd".into(), "getMotd".into(), "\"\"".into()),
+            ],
+            java
+        );
+    }
+
+    #[test]
+    fn config_yml_should_nest_dotted_keys() {
+        // GIVEN entries sharing a section
+        let entries =
+            parse_config_spec("spawn.world:string=world,spawn.radius:double=10,max-homes:int=3")
+                .unwrap();
+
+        // WHEN we write the config.yml
+        let yaml = config_yml(&entries).unwrap();
+
+        // THEN the section should hold both keys
+        assert_eq!(
+            "spawn:\n  world: world\n  radius: 10.0\nmax-homes: 3\n",
+            yaml
+        );
+    }
+
+    #[test]
+    fn merge_config_yml_should_keep_existing_settings() {
+        // GIVEN a config.yml with a database section
+        let existing = "database:\n  type: sqlite\n";
+
+        // WHEN we merge new entries into it
+        let entries = parse_config_spec("database.pool-size:int=4,max-homes:int=3").unwrap();
+        let yaml = merge_config_yml(existing, &entries).unwrap();
+
+        // THEN both should be kept
+        assert_eq!(
+            "database:\n  type: sqlite\n  pool-size: 4\nmax-homes: 3\n",
+            yaml
+        );
+
+        // AND redeclaring a key should be refused
+        let entries = parse_config_spec("database.type").unwrap();
+        assert!(merge_config_yml(&yaml, &entries).is_err());
+    }
+
+    #[test]
+    fn parse_config_spec_should_reject_invalid_declarations() {
+        // GIVEN malformed specs
+        // WHEN we parse them
+        // THEN they should be rejected
+        assert!(parse_config_spec("max-homes:integer=3").is_err());
+        assert!(parse_config_spec("max-homes:int=three").is_err());
+        assert!(parse_config_spec("spawn..world").is_err());
+        assert!(parse_config_spec("spawn:int=1,spawn.world").is_err());
+        assert!(parse_config_spec("max-homes,max_homes").is_err());
+        assert!(parse_config_spec(" ").is_err());
+    }
+}
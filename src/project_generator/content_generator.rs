@@ -1,87 +1,889 @@
-fn generate_file_content(template: &str, name: &str, version: &str, group_id: &str) -> String {
-    template
-        .replace("{name}", name)
-        .replace("{version}", version)
-        .replace("{group_id}", group_id)
+use crate::errors::GeneratorError;
+use crate::project_generator::dependencies::{
+    extend_context as extend_dependencies_context, Dependency,
+};
+use crate::project_generator::messages::MessagesOptions;
+use crate::project_generator::naming::ProjectNames;
+use crate::project_generator::plugin_descriptor::PluginDescriptor;
+use crate::project_generator::project_info::ProjectInfo;
+use crate::project_generator::template_engine::{render, Context};
+use crate::project_generator::version_catalog::java_version;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateFile {
+    pub path: String,
+    pub template: String,
+    pub condition: Option<String>,
 }
 
-pub fn generate_pom_xml_content(name: &str, version: &str, group_id: &str) -> String {
-    generate_file_content(
-        r#"<?xml version="1.0" encoding="UTF-8"?>
+impl TemplateFile {
+    pub fn new(path: &str, template: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            template: template.to_string(),
+            condition: None,
+        }
+    }
+
+    /// Only generates the file when the condition, evaluated like an `if` block, holds.
+    pub fn when(mut self, condition: &str) -> Self {
+        self.condition = Some(condition.to_string());
+        self
+    }
+}
+
+fn generate_file_content(template: &str, context: &Context) -> Result<String, GeneratorError> {
+    render(template, context)
+}
+
+pub fn render_template_files(
+    files: &[TemplateFile],
+    context: &Context,
+) -> Result<Vec<(String, String)>, GeneratorError> {
+    files
+        .iter()
+        .filter(|file| match &file.condition {
+            Some(condition) => context.is_truthy(condition),
+            None => true,
+        })
+        .map(|file| {
+            Ok((
+                generate_file_content(&file.path, context)?,
+                generate_file_content(&file.template, context)?,
+            ))
+        })
+        .collect()
+}
+
+pub fn project_context(
+    names: &ProjectNames,
+    info: &ProjectInfo,
+    version: &str,
+    group_id: &str,
+) -> Context {
+    let mut context = Context::new();
+    info.extend_context(&mut context);
+    context
+        .insert("name", names.main_class.as_str())
+        .insert("display_name", names.display_name.as_str())
+        .insert("artifact_id", names.artifact_id.as_str())
+        .insert("main_class", names.main_class.as_str())
+        .insert("plugin_name", names.plugin_name.as_str())
+        .insert("mod_id", names.mod_id.as_str())
+        .insert("version", version)
+        .insert("java_version", java_version(version).to_string())
+        .insert("group_id", group_id);
+    context
+}
+
+pub const POM_XML_TEMPLATE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
 <project xsi:schemaLocation="http://maven.apache.org/POM/4.0.0 https://maven.apache.org/xsd/maven-4.0.0.xsd"
     xmlns="http://maven.apache.org/POM/4.0.0"
     xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">
     <modelVersion>4.0.0</modelVersion>
-    <groupId>{group_id}</groupId>
-    <artifactId>{name}</artifactId>
-    <version>1.0.0</version>
+    <groupId>{{ group_id }}</groupId>
+    <artifactId>{{ artifact_id }}</artifactId>
+    <version>{{ project_version | xml }}</version>
     <packaging>jar</packaging>
-    <name>{name}</name>
-    <description>Test project</description>
+    <name>{{ display_name | xml }}</name>
+{% if description %}
+    <description>{{ description | xml }}</description>
+{% endif %}
+{% if website %}
+    <url>{{ website | xml }}</url>
+{% endif %}
+{% if license %}
+    <licenses>
+        <license>
+            <name>{{ license | xml }}</name>
+            <url>https://spdx.org/licenses/{{ license | xml }}.html</url>
+        </license>
+    </licenses>
+{% endif %}
+{% if authors %}
+    <developers>
+{% for author in authors %}
+        <developer>
+            <name>{{ author | xml }}</name>
+        </developer>
+{% endfor %}
+    </developers>
+{% endif %}
     <properties>
-        <maven.compiler.target>21</maven.compiler.target>
-        <maven.compiler.source>21</maven.compiler.source>
+        <maven.compiler.target>{{ java_version }}</maven.compiler.target>
+        <maven.compiler.source>{{ java_version }}</maven.compiler.source>
         <project.build.sourceEncoding>UTF-8</project.build.sourceEncoding>
-        <spigot.version>{version}-R0.1-SNAPSHOT</spigot.version>
+        <spigot.version>{{ version }}-R0.1-SNAPSHOT</spigot.version>
     </properties>
     <repositories>
+{% for repository in repositories %}
         <repository>
-            <id>spigot-repo</id>
-            <url>https://hub.spigotmc.org/nexus/content/repositories/snapshots/</url>
+            <id>{{ repository.id }}</id>
+            <url>{{ repository.url | xml }}</url>
         </repository>
+{% endfor %}
     </repositories>
     <dependencies>
+{% for dependency in dependencies %}
         <dependency>
-            <groupId>org.spigotmc</groupId>
-            <artifactId>spigot-api</artifactId>
-            <version>${spigot.version}</version>
-            <scope>provided</scope>
+            <groupId>{{ dependency.group_id }}</groupId>
+            <artifactId>{{ dependency.artifact_id }}</artifactId>
+            <version>{{ dependency.version }}</version>
+            <scope>{{ dependency.scope }}</scope>
         </dependency>
+{% endfor %}
     </dependencies>
+{% if relocations %}
+    <build>
+        <plugins>
+{{ shade_plugin }}
+        </plugins>
+    </build>
+{% endif %}
 </project>
-    "#,
-        name,
-        version,
-        group_id,
-    )
-}
+    "#;
 
-pub fn generate_main_java_content(name: &str, group_id: &str) -> String {
-    generate_file_content(
-        r#"package {group_id};
+/// The `maven-shade-plugin` of the pom `<build>` plugins, relocating every shaded library.
+pub const MAVEN_SHADE_PLUGIN_TEMPLATE: &str = r#"            <plugin>
+                <groupId>org.apache.maven.plugins</groupId>
+                <artifactId>maven-shade-plugin</artifactId>
+                <version>3.6.0</version>
+                <executions>
+                    <execution>
+                        <phase>package</phase>
+                        <goals>
+                            <goal>shade</goal>
+                        </goals>
+                        <configuration>
+                            <minimizeJar>true</minimizeJar>
+                            <createDependencyReducedPom>false</createDependencyReducedPom>
+                            <filters>
+                                <filter>
+                                    <artifact>*:*</artifact>
+                                    <excludes>
+                                        <exclude>META-INF/*.SF</exclude>
+                                        <exclude>META-INF/*.DSA</exclude>
+                                        <exclude>META-INF/*.RSA</exclude>
+                                    </excludes>
+                                </filter>
+                            </filters>
+                            <relocations>
+{% for relocation in relocations %}
+                                <relocation>
+                                    <pattern>{{ relocation.pattern }}</pattern>
+                                    <shadedPattern>{{ relocation.shaded_pattern }}</shadedPattern>
+                                </relocation>
+{% endfor %}
+                            </relocations>
+                        </configuration>
+                    </execution>
+                </executions>
+            </plugin>"#;
 
+pub const MAIN_JAVA_TEMPLATE: &str = r#"package {{ group_id }};
+
+{% if database %}
+import java.sql.SQLException;
+import java.util.logging.Level;
+{% endif %}
+{% if bstats_id %}
+import org.bstats.bukkit.Metrics;
+{% endif %}
 import org.bukkit.plugin.java.JavaPlugin;
+{% if database %}
+import {{ group_id }}.database.Database;
+{% endif %}
+{% if messages %}
+import {{ group_id }}.messages.Messages;
+{% endif %}
+
+public class {{ main_class }} extends JavaPlugin {
+{% if database %}
+
+    private Database database;
+{% endif %}
+{% if messages %}
 
-public class {name} extends JavaPlugin {
+    private Messages messages;
+{% endif %}
 
     @Override
     public void onEnable() {
+{% if messages %}
+        messages = new Messages(this);
+        messages.load(getConfig().getString("locale", "{{ messages.default_locale }}"));
+{% endif %}
+{% if database %}
+        saveDefaultConfig();
+        database = new Database(this);
+        try {
+            database.connect(getFile());
+        } catch (SQLException e) {
+            getLogger().log(Level.SEVERE, "Unable to connect to the database", e);
+            getServer().getPluginManager().disablePlugin(this);
+            return;
+        }
+{% endif %}
+{% if bstats_id %}
+        new Metrics(this, {{ bstats_id }});
+{% endif %}
         getLogger().info("Hello, SpigotMC!");
     }
 
     @Override
     public void onDisable() {
+{% if database %}
+        database.close();
+{% endif %}
+{% if messages.minimessage %}
+        messages.close();
+{% endif %}
         getLogger().info("Goodbye, SpigotMC!");
     }
+{% if database %}
+
+    public Database getDatabase() {
+        return database;
+    }
+{% endif %}
+{% if messages %}
+
+    public Messages getMessages() {
+        return messages;
+    }
+{% endif %}
+}
+    "#;
+
+pub const MESSAGES_JAVA_TEMPLATE: &str = r#"package {{ group_id }}.messages;
+
+import java.io.File;
+import java.io.InputStream;
+import java.io.InputStreamReader;
+import java.nio.charset.StandardCharsets;
+
+{% if messages.minimessage %}
+import net.kyori.adventure.platform.bukkit.BukkitAudiences;
+import net.kyori.adventure.text.Component;
+import net.kyori.adventure.text.minimessage.MiniMessage;
+import net.kyori.adventure.text.minimessage.tag.resolver.Placeholder;
+import net.kyori.adventure.text.minimessage.tag.resolver.TagResolver;
+{% else %}
+import org.bukkit.ChatColor;
+{% endif %}
+import org.bukkit.command.CommandSender;
+import org.bukkit.configuration.file.YamlConfiguration;
+import org.bukkit.plugin.java.JavaPlugin;
+
+public class Messages {
+
+    private static final String DEFAULT_LOCALE = "{{ messages.default_locale }}";
+    private static final String[] LOCALES = { {{ messages.locales }} };
+
+    private final JavaPlugin plugin;
+    private YamlConfiguration messages;
+    private YamlConfiguration fallback;
+{% if messages.minimessage %}
+    private BukkitAudiences audiences;
+{% endif %}
+
+    public Messages(JavaPlugin plugin) {
+        this.plugin = plugin;
+    }
+
+    /** Loads the messages of the locale, missing ones falling back to the default locale. */
+    public void load(String locale) {
+        for (String available : LOCALES) {
+            String fileName = "messages_" + available + ".yml";
+            if (!new File(plugin.getDataFolder(), fileName).exists()) {
+                plugin.saveResource(fileName, false);
+            }
+        }
+        messages = read(locale);
+        fallback = read(DEFAULT_LOCALE);
+{% if messages.minimessage %}
+        if (audiences == null) {
+            audiences = BukkitAudiences.create(plugin);
+        }
+{% endif %}
+    }
+{% if messages.minimessage %}
+
+    public void close() {
+        if (audiences != null) {
+            audiences.close();
+            audiences = null;
+        }
+    }
+
+    /** The message with its <name> placeholders replaced by the given name and value pairs. */
+    public Component get(String key, String... placeholders) {
+        TagResolver.Builder resolver = TagResolver.builder();
+        for (int i = 0; i + 1 < placeholders.length; i += 2) {
+            resolver.resolver(Placeholder.unparsed(placeholders[i], placeholders[i + 1]));
+        }
+        return MiniMessage.miniMessage().deserialize(raw(key), resolver.build());
+    }
+
+    public void send(CommandSender sender, String key, String... placeholders) {
+        audiences.sender(sender).sendMessage(get("prefix").append(get(key, placeholders)));
+    }
+{% else %}
+
+    /** The message with its {name} placeholders replaced by the given name and value pairs. */
+    public String get(String key, String... placeholders) {
+        String message = raw(key);
+        for (int i = 0; i + 1 < placeholders.length; i += 2) {
+            message = message.replace("{" + placeholders[i] + "}", placeholders[i + 1]);
+        }
+        return ChatColor.translateAlternateColorCodes('&', message);
+    }
+
+    public void send(CommandSender sender, String key, String... placeholders) {
+        sender.sendMessage(get("prefix") + get(key, placeholders));
+    }
+{% endif %}
+
+    private String raw(String key) {
+        return messages.getString(key, fallback.getString(key, key));
+    }
+
+    private YamlConfiguration read(String locale) {
+        String fileName = "messages_" + locale + ".yml";
+        YamlConfiguration configuration = YamlConfiguration.loadConfiguration(new File(plugin.getDataFolder(), fileName));
+        InputStream resource = plugin.getResource(fileName);
+        if (resource != null) {
+            configuration.setDefaults(YamlConfiguration.loadConfiguration(new InputStreamReader(resource, StandardCharsets.UTF_8)));
+        }
+        return configuration;
+    }
+}
+"#;
+
+/// The `plugin.yml` is serialized from a [`PluginDescriptor`] into the `plugin_yml` value.
+pub const PLUGIN_YML_TEMPLATE: &str = "{{ plugin_yml }}";
+
+pub const LICENSE_TEMPLATE: &str = "{{ license_text }}";
+
+pub const COMMAND_JAVA_TEMPLATE: &str = r#"package {{ package }};
+
+import java.util.Collections;
+import java.util.List;
+
+import org.bukkit.command.Command;
+import org.bukkit.command.CommandExecutor;
+import org.bukkit.command.CommandSender;
+import org.bukkit.command.TabCompleter;
+
+public class {{ class_name }} implements CommandExecutor, TabCompleter {
+
+    @Override
+    public boolean onCommand(CommandSender sender, Command command, String label, String[] args) {
+        sender.sendMessage("/" + label + " is not implemented yet.");
+        return true;
+    }
+
+    @Override
+    public List<String> onTabComplete(CommandSender sender, Command command, String alias, String[] args) {
+        return Collections.emptyList();
+    }
+}
+"#;
+
+pub const LISTENER_JAVA_TEMPLATE: &str = r#"package {{ package }};
+
+{% for import in imports %}
+import {{ import }};
+{% endfor %}
+
+public class {{ class_name }} implements Listener {
+{% for handler in handlers %}
+
+    {{ handler.annotation }}
+    public void {{ handler.method }}({{ handler.event }} event) {
+    }
+{% endfor %}
+}
+"#;
+
+pub const CONFIG_JAVA_TEMPLATE: &str = r#"package {{ package }};
+
+{% for import in imports %}
+import {{ import }};
+{% endfor %}
+
+public class {{ class_name }} {
+
+    private final JavaPlugin plugin;
+{% for entry in entries %}
+    private {{ entry.java_type }} {{ entry.field }};
+{% endfor %}
+
+    public {{ class_name }}(JavaPlugin plugin) {
+        this.plugin = plugin;
+    }
+
+    public void load() {
+        plugin.reloadConfig();
+        FileConfiguration config = plugin.getConfig();
+{% for entry in entries %}
+        {{ entry.field }} = {{ entry.reader }}(config, {{ entry.key }}, {{ entry.default }});
+{% endfor %}
+    }
+{% for entry in entries %}
+
+    public {{ entry.java_type }} {{ entry.getter }}() {
+        return {{ entry.field }};
+    }
+{% endfor %}
+{% if types.string %}
+
+    private String readString(FileConfiguration config, String key, String fallback) {
+        if (config.isString(key)) {
+            return config.getString(key);
+        }
+        return invalid(key, fallback);
+    }
+{% endif %}
+{% if types.int %}
+
+    private int readInt(FileConfiguration config, String key, int fallback) {
+        if (config.isInt(key)) {
+            return config.getInt(key);
+        }
+        return invalid(key, fallback);
+    }
+{% endif %}
+{% if types.double %}
+
+    private double readDouble(FileConfiguration config, String key, double fallback) {
+        if (config.isDouble(key) || config.isInt(key)) {
+            return config.getDouble(key);
+        }
+        return invalid(key, fallback);
+    }
+{% endif %}
+{% if types.boolean %}
+
+    private boolean readBoolean(FileConfiguration config, String key, boolean fallback) {
+        if (config.isBoolean(key)) {
+            return config.getBoolean(key);
+        }
+        return invalid(key, fallback);
+    }
+{% endif %}
+{% if types.list %}
+
+    private List<String> readStringList(FileConfiguration config, String key, List<String> fallback) {
+        if (config.isList(key)) {
+            return config.getStringList(key);
+        }
+        return invalid(key, fallback);
+    }
+{% endif %}
+
+    private <T> T invalid(String key, T fallback) {
+        plugin.getLogger().warning("Invalid value for " + key + " in config.yml, using " + fallback);
+        return fallback;
+    }
+}
+"#;
+
+pub const RELOAD_COMMAND_JAVA_TEMPLATE: &str = r#"package {{ package }};
+
+import java.util.Collections;
+import java.util.List;
+
+import org.bukkit.command.Command;
+import org.bukkit.command.CommandExecutor;
+import org.bukkit.command.CommandSender;
+import org.bukkit.command.TabCompleter;
+
+import {{ config_package }}.{{ config_class }};
+
+public class {{ class_name }} implements CommandExecutor, TabCompleter {
+
+    private final {{ config_class }} config;
+
+    public {{ class_name }}({{ config_class }} config) {
+        this.config = config;
+    }
+
+    @Override
+    public boolean onCommand(CommandSender sender, Command command, String label, String[] args) {
+        if (args.length == 1 && args[0].equalsIgnoreCase("reload")) {
+            config.load();
+            sender.sendMessage("Configuration reloaded.");
+            return true;
+        }
+        return false;
+    }
+
+    @Override
+    public List<String> onTabComplete(CommandSender sender, Command command, String alias, String[] args) {
+        if (args.length == 1 && "reload".startsWith(args[0].toLowerCase())) {
+            return Collections.singletonList("reload");
+        }
+        return Collections.emptyList();
+    }
 }
-    "#,
-        name,
-        "",
-        group_id,
-    )
+"#;
+
+pub const DATABASE_JAVA_TEMPLATE: &str = r#"package {{ group_id }}.database;
+
+import com.zaxxer.hikari.HikariConfig;
+import com.zaxxer.hikari.HikariDataSource;
+import java.io.File;
+import java.sql.Connection;
+import java.sql.SQLException;
+import java.util.concurrent.CompletableFuture;
+import java.util.concurrent.CompletionException;
+import java.util.concurrent.Executor;
+import org.bukkit.configuration.ConfigurationSection;
+import org.bukkit.plugin.java.JavaPlugin;
+
+/**
+ * The connection pool configured by the database section of the config.yml.
+ * Queries run off the main thread, use the scheduler to get back to it.
+ */
+public class Database {
+
+    private final JavaPlugin plugin;
+    private final Executor executor;
+    private HikariDataSource dataSource;
+
+    public Database(JavaPlugin plugin) {
+        this.plugin = plugin;
+        this.executor = task -> plugin.getServer().getScheduler().runTaskAsynchronously(plugin, task);
+    }
+
+    /** Opens the pool and applies the migrations bundled in the plugin jar. */
+    public void connect(File pluginJar) throws SQLException {
+        ConfigurationSection section = plugin.getConfig().getConfigurationSection("database");
+        if (section == null) {
+            throw new SQLException("Missing database section in config.yml");
+        }
+        HikariConfig config = new HikariConfig();
+        config.setPoolName(plugin.getName() + "-pool");
+        if ("mysql".equalsIgnoreCase(section.getString("type"))) {
+            config.setDriverClassName("com.mysql.cj.jdbc.Driver");
+            config.setJdbcUrl("jdbc:mysql://" + section.getString("host", "localhost") + ":"
+                    + section.getInt("port", 3306) + "/" + section.getString("name"));
+            config.setUsername(section.getString("user"));
+            config.setPassword(section.getString("password"));
+            config.setMaximumPoolSize(section.getInt("pool-size", 10));
+        } else {
+            File file = new File(plugin.getDataFolder(), section.getString("file", "database.db"));
+            config.setDriverClassName("org.sqlite.JDBC");
+            config.setJdbcUrl("jdbc:sqlite:" + file.getAbsolutePath());
+            // SQLite only supports a single writer
+            config.setMaximumPoolSize(1);
+        }
+        dataSource = new HikariDataSource(config);
+        new MigrationRunner(dataSource, plugin.getLogger()).migrate(pluginJar);
+    }
+
+    /** Runs the query asynchronously with a pooled connection. */
+    public <T> CompletableFuture<T> supplyAsync(Query<T> query) {
+        return CompletableFuture.supplyAsync(() -> {
+            try (Connection connection = dataSource.getConnection()) {
+                return query.execute(connection);
+            } catch (SQLException e) {
+                throw new CompletionException(e);
+            }
+        }, executor);
+    }
+
+    public void close() {
+        if (dataSource != null) {
+            dataSource.close();
+        }
+    }
+
+    @FunctionalInterface
+    public interface Query<T> {
+        T execute(Connection connection) throws SQLException;
+    }
+}
+"#;
+
+pub const MIGRATION_RUNNER_JAVA_TEMPLATE: &str = r#"package {{ group_id }}.database;
+
+import java.io.ByteArrayOutputStream;
+import java.io.File;
+import java.io.IOException;
+import java.io.InputStream;
+import java.nio.charset.StandardCharsets;
+import java.sql.Connection;
+import java.sql.PreparedStatement;
+import java.sql.ResultSet;
+import java.sql.SQLException;
+import java.sql.Statement;
+import java.util.Enumeration;
+import java.util.Map;
+import java.util.TreeMap;
+import java.util.jar.JarEntry;
+import java.util.jar.JarFile;
+import java.util.logging.Logger;
+import java.util.regex.Matcher;
+import java.util.regex.Pattern;
+import javax.sql.DataSource;
+
+/**
+ * Applies the migrations/V<version>__<description>.sql files of the plugin jar, in version order,
+ * each one once and in its own transaction.
+ */
+public class MigrationRunner {
+
+    private static final Pattern MIGRATION = Pattern.compile("migrations/V(\\d+)__([\\w-]+)\\.sql");
+
+    private final DataSource dataSource;
+    private final Logger logger;
+
+    public MigrationRunner(DataSource dataSource, Logger logger) {
+        this.dataSource = dataSource;
+        this.logger = logger;
+    }
+
+    public void migrate(File pluginJar) throws SQLException {
+        try (Connection connection = dataSource.getConnection()) {
+            try (Statement statement = connection.createStatement()) {
+                statement.executeUpdate("CREATE TABLE IF NOT EXISTS schema_version ("
+                        + "version INT NOT NULL PRIMARY KEY, "
+                        + "description VARCHAR(255) NOT NULL, "
+                        + "applied_at BIGINT NOT NULL)");
+            }
+            int current = currentVersion(connection);
+            for (Map.Entry<Integer, Migration> migration : readMigrations(pluginJar).entrySet()) {
+                if (migration.getKey() > current) {
+                    apply(connection, migration.getKey(), migration.getValue());
+                }
+            }
+        }
+    }
+
+    private int currentVersion(Connection connection) throws SQLException {
+        try (Statement statement = connection.createStatement();
+             ResultSet result = statement.executeQuery("SELECT MAX(version) FROM schema_version")) {
+            return result.next() ? result.getInt(1) : 0;
+        }
+    }
+
+    private Map<Integer, Migration> readMigrations(File pluginJar) throws SQLException {
+        Map<Integer, Migration> migrations = new TreeMap<>();
+        try (JarFile jar = new JarFile(pluginJar)) {
+            Enumeration<JarEntry> entries = jar.entries();
+            while (entries.hasMoreElements()) {
+                JarEntry entry = entries.nextElement();
+                Matcher matcher = MIGRATION.matcher(entry.getName());
+                if (matcher.matches()) {
+                    try (InputStream input = jar.getInputStream(entry)) {
+                        String sql = new String(readFully(input), StandardCharsets.UTF_8);
+                        migrations.put(Integer.parseInt(matcher.group(1)), new Migration(matcher.group(2), sql));
+                    }
+                }
+            }
+        } catch (IOException e) {
+            throw new SQLException("Unable to read the migrations of " + pluginJar, e);
+        }
+        return migrations;
+    }
+
+    private static byte[] readFully(InputStream input) throws IOException {
+        ByteArrayOutputStream output = new ByteArrayOutputStream();
+        byte[] buffer = new byte[8192];
+        int read;
+        while ((read = input.read(buffer)) != -1) {
+            output.write(buffer, 0, read);
+        }
+        return output.toByteArray();
+    }
+
+    private void apply(Connection connection, int version, Migration migration) throws SQLException {
+        logger.info("Applying database migration V" + version + " " + migration.description);
+        connection.setAutoCommit(false);
+        try (Statement statement = connection.createStatement()) {
+            for (String sql : migration.sql.split(";")) {
+                if (!sql.trim().isEmpty()) {
+                    statement.executeUpdate(sql);
+                }
+            }
+            try (PreparedStatement insert = connection.prepareStatement(
+                    "INSERT INTO schema_version (version, description, applied_at) VALUES (?, ?, ?)")) {
+                insert.setInt(1, version);
+                insert.setString(2, migration.description);
+                insert.setLong(3, System.currentTimeMillis());
+                insert.executeUpdate();
+            }
+            connection.commit();
+        } catch (SQLException e) {
+            connection.rollback();
+            throw e;
+        } finally {
+            connection.setAutoCommit(true);
+        }
+    }
+
+    private static final class Migration {
+
+        private final String description;
+        private final String sql;
+
+        private Migration(String description, String sql) {
+            this.description = description;
+            this.sql = sql;
+        }
+    }
 }
+"#;
+
+pub const PLAYER_DAO_JAVA_TEMPLATE: &str = r#"package {{ group_id }}.database;
+
+import java.sql.PreparedStatement;
+import java.sql.ResultSet;
+import java.util.Optional;
+import java.util.UUID;
+import java.util.concurrent.CompletableFuture;
+
+/** An example data access object for the players table of the V1 migration. */
+public class PlayerDao {
 
-pub fn generate_plugin_yml_content(name: &str, group_id: &str) -> String {
-    generate_file_content(
-        r#"name: {name}
-version: 1.0
-main: {group_id}.{name}
-author: Notch # Set yours
-    "#,
-        name,
-        "",
-        group_id,
-    )
+    private final Database database;
+
+    public PlayerDao(Database database) {
+        this.database = database;
+    }
+
+    public CompletableFuture<Optional<Long>> findLastSeen(UUID uuid) {
+        return database.supplyAsync(connection -> {
+            try (PreparedStatement statement = connection.prepareStatement(
+                    "SELECT last_seen FROM players WHERE uuid = ?")) {
+                statement.setString(1, uuid.toString());
+                try (ResultSet result = statement.executeQuery()) {
+                    return result.next() ? Optional.of(result.getLong("last_seen")) : Optional.empty();
+                }
+            }
+        });
+    }
+
+    public CompletableFuture<Void> saveLastSeen(UUID uuid, long lastSeen) {
+        return database.supplyAsync(connection -> {
+            try (PreparedStatement update = connection.prepareStatement(
+                    "UPDATE players SET last_seen = ? WHERE uuid = ?")) {
+                update.setLong(1, lastSeen);
+                update.setString(2, uuid.toString());
+                if (update.executeUpdate() > 0) {
+                    return null;
+                }
+            }
+            try (PreparedStatement insert = connection.prepareStatement(
+                    "INSERT INTO players (uuid, last_seen) VALUES (?, ?)")) {
+                insert.setString(1, uuid.toString());
+                insert.setLong(2, lastSeen);
+                insert.executeUpdate();
+            }
+            return null;
+        });
+    }
+}
+"#;
+
+pub const FIRST_MIGRATION_SQL_TEMPLATE: &str = r#"CREATE TABLE players (
+    uuid CHAR(36) NOT NULL PRIMARY KEY,
+    last_seen BIGINT NOT NULL
+);
+"#;
+
+pub const SOFT_HOOK_JAVA_TEMPLATE: &str = r#"package {{ package }};
+
+import org.bukkit.Bukkit;
+import org.bukkit.plugin.Plugin;
+
+/**
+ * Guards the use of {{ plugin }}: check isAvailable() before touching its API
+ * so the plugin still works when {{ plugin }} is not installed.
+ */
+public final class {{ class_name }} {
+
+    private {{ class_name }}() {
+    }
+
+    public static boolean isAvailable() {
+        Plugin plugin = Bukkit.getPluginManager().getPlugin("{{ plugin }}");
+        return plugin != null && plugin.isEnabled();
+    }
+}
+"#;
+
+pub const PAPER_BOOTSTRAP_JAVA_TEMPLATE: &str = r#"package {{ package }};
+
+import io.papermc.paper.plugin.bootstrap.BootstrapContext;
+import io.papermc.paper.plugin.bootstrap.PluginBootstrap;
+
+/**
+ * Runs before the server loads the worlds, e.g. to register commands
+ * through context.getLifecycleManager().
+ */
+public class {{ class_name }} implements PluginBootstrap {
+
+    @Override
+    public void bootstrap(BootstrapContext context) {
+    }
+}
+"#;
+
+pub const PAPER_LOADER_JAVA_TEMPLATE: &str = r#"package {{ package }};
+
+import io.papermc.paper.plugin.loader.PluginClasspathBuilder;
+import io.papermc.paper.plugin.loader.PluginLoader;
+import io.papermc.paper.plugin.loader.library.impl.MavenLibraryResolver;
+import org.eclipse.aether.artifact.DefaultArtifact;
+import org.eclipse.aether.graph.Dependency;
+import org.eclipse.aether.repository.RemoteRepository;
+
+/**
+ * Downloads the libraries the plugin.yml used to declare.
+ */
+public class {{ class_name }} implements PluginLoader {
+
+    @Override
+    public void classloader(PluginClasspathBuilder classpathBuilder) {
+        MavenLibraryResolver resolver = new MavenLibraryResolver();
+        resolver.addRepository(new RemoteRepository.Builder("central", "default", "https://repo.maven.apache.org/maven2/").build());
+{% for library in libraries %}
+        resolver.addDependency(new Dependency(new DefaultArtifact("{{ library }}"), null));
+{% endfor %}
+        classpathBuilder.addLibrary(resolver);
+    }
+}
+"#;
+
+pub fn generate_pom_xml_content(
+    names: &ProjectNames,
+    info: &ProjectInfo,
+    version: &str,
+    group_id: &str,
+    dependencies: &[Dependency],
+) -> Result<String, GeneratorError> {
+    let mut context = project_context(names, info, version, group_id);
+    extend_dependencies_context(&mut context, dependencies, group_id)?;
+    generate_file_content(POM_XML_TEMPLATE, &context)
+}
+
+pub fn generate_main_java_content(
+    names: &ProjectNames,
+    group_id: &str,
+    messages: Option<&MessagesOptions>,
+) -> Result<String, GeneratorError> {
+    let mut context = project_context(names, &ProjectInfo::default(), "", group_id);
+    if let Some(messages) = messages {
+        messages.extend_context(&mut context, &names.plugin_name)?;
+    }
+    generate_file_content(MAIN_JAVA_TEMPLATE, &context)
+}
+
+pub fn generate_plugin_yml_content(plugin: &PluginDescriptor) -> Result<String, GeneratorError> {
+    plugin.to_yaml()
 }
 
 #[cfg(test)]
@@ -90,41 +892,170 @@ mod tests {
 
     #[test]
     fn test_generate_file_content() {
-        let template = "Name: {name}, Version: {version}, Group ID: {group_id}";
-        let name = "TestName";
+        let template = "Name: {{ name }}, Version: {{ version }}, Group ID: {{ group_id }}";
+        let names = ProjectNames::from_name("TestName");
         let version = "1.0.0";
         let group_id = "com.test";
-        let result = generate_file_content(template, name, version, group_id);
+        let result = generate_file_content(
+            template,
+            &project_context(&names, &ProjectInfo::default(), version, group_id),
+        )
+        .unwrap();
         let expected = "Name: TestName, Version: 1.0.0, Group ID: com.test";
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_generate_file_content_keeps_single_braces() {
+        let template = "String placeholder = \"{name}\"; // {{ name }}";
+        let result = generate_file_content(
+            template,
+            &project_context(
+                &ProjectNames::from_name("TestName"),
+                &ProjectInfo::default(),
+                "",
+                "com.test",
+            ),
+        )
+        .unwrap();
+        assert_eq!(result, "String placeholder = \"{name}\"; // TestName");
+    }
+
     #[test]
     fn test_generate_pom_xml_content() {
-        let name = "TestName";
+        let names = ProjectNames::from_name("TestName");
         let version = "1.0.0";
         let group_id = "com.test";
-        let result = generate_pom_xml_content(name, version, group_id);
-        assert!(result.contains("<artifactId>TestName</artifactId>"));
+        let result =
+            generate_pom_xml_content(&names, &ProjectInfo::default(), version, group_id, &[])
+                .unwrap();
+        assert!(result.contains("<artifactId>test-name</artifactId>"));
+        assert!(result.contains("<name>TestName</name>"));
         assert!(result.contains("<version>1.0.0</version>"));
         assert!(result.contains("<groupId>com.test</groupId>"));
     }
 
+    #[test]
+    fn test_generate_pom_xml_content_shades_compile_dependencies() {
+        let names = ProjectNames::from_name("TestName");
+        let dependencies = [
+            Dependency::provided("org.spigotmc", "spigot-api", "${spigot.version}"),
+            Dependency::shaded("org.bstats", "bstats-bukkit", "3.0.2"),
+        ];
+        let pom = generate_pom_xml_content(
+            &names,
+            &ProjectInfo::default(),
+            "1.21",
+            "com.test",
+            &dependencies,
+        )
+        .unwrap();
+        assert!(pom.contains("<artifactId>maven-shade-plugin</artifactId>"));
+        assert!(pom.contains("<minimizeJar>true</minimizeJar>"));
+        assert!(pom.contains("<exclude>META-INF/*.SF</exclude>"));
+        assert!(pom.contains("<pattern>org.bstats</pattern>\n                                    <shadedPattern>com.test.libs.bstats</shadedPattern>"));
+
+        let pom = generate_pom_xml_content(
+            &names,
+            &ProjectInfo::default(),
+            "1.21",
+            "com.test",
+            &dependencies[..1],
+        )
+        .unwrap();
+        assert!(!pom.contains("<build>"));
+    }
+
     #[test]
     fn test_generate_main_java_content() {
-        let name = "TestName";
+        let names = ProjectNames::from_name("TestName");
         let group_id = "com.test";
-        let result = generate_main_java_content(name, group_id);
+        let result = generate_main_java_content(&names, group_id, None).unwrap();
         assert!(result.contains("package com.test;"));
         assert!(result.contains("public class TestName extends JavaPlugin {"));
     }
 
     #[test]
     fn test_generate_plugin_yml_content() {
-        let name = "TestName";
+        let names = ProjectNames::from_name("TestName");
         let group_id = "com.test";
-        let result = generate_plugin_yml_content(name, group_id);
+        let plugin = PluginDescriptor::new(&names, &ProjectInfo::default(), group_id);
+        let result = generate_plugin_yml_content(&plugin).unwrap();
         assert!(result.contains("name: TestName"));
         assert!(result.contains("main: com.test.TestName"));
     }
+
+    #[test]
+    fn test_generate_content_uses_overridden_names() {
+        let names = ProjectNames {
+            display_name: String::from("Cool & Useful"),
+            artifact_id: String::from("cool-useful"),
+            main_class: String::from("CoolMain"),
+            plugin_name: String::from("Cool"),
+            mod_id: String::from("cool"),
+        };
+        let info = ProjectInfo::default();
+        let pom = generate_pom_xml_content(&names, &info, "1.21", "com.test", &[]).unwrap();
+        let plugin_yml =
+            generate_plugin_yml_content(&PluginDescriptor::new(&names, &info, "com.test")).unwrap();
+        let main_java = generate_main_java_content(&names, "com.test", None).unwrap();
+        assert!(pom.contains("<artifactId>cool-useful</artifactId>"));
+        assert!(pom.contains("<name>Cool &amp; Useful</name>"));
+        assert!(plugin_yml.contains("name: Cool\n"));
+        assert!(plugin_yml.contains("main: com.test.CoolMain"));
+        assert!(main_java.contains("public class CoolMain extends JavaPlugin {"));
+    }
+
+    #[test]
+    fn test_generate_content_uses_project_info() {
+        let names = ProjectNames::from_name("TestName");
+        let info = ProjectInfo {
+            project_version: String::from("2.0.0"),
+            description: String::from("Homes & warps: everything"),
+            authors: vec![String::from("Notch"), String::from("jeb_")],
+            website: Some(String::from("https://example.com")),
+            license: Some(String::from("MIT")),
+        };
+        let pom = generate_pom_xml_content(&names, &info, "1.21", "com.test", &[]).unwrap();
+        let plugin_yml =
+            generate_plugin_yml_content(&PluginDescriptor::new(&names, &info, "com.test")).unwrap();
+        assert!(pom.contains("<version>2.0.0</version>"));
+        assert!(pom.contains("<description>Homes &amp; warps: everything</description>"));
+        assert!(pom.contains("<url>https://example.com</url>"));
+        assert!(pom.contains("<name>MIT</name>"));
+        assert!(pom.contains("<developer>\n            <name>jeb_</name>"));
+        assert!(plugin_yml.contains("version: 2.0.0\n"));
+        assert!(plugin_yml.contains("description: 'Homes & warps: everything'\n"));
+        assert!(plugin_yml.contains("authors:\n- Notch\n- jeb_\n"));
+        assert!(plugin_yml.contains("website: https://example.com\n"));
+    }
+
+    #[test]
+    fn test_generate_content_omits_empty_project_info() {
+        let names = ProjectNames::from_name("TestName");
+        let info = ProjectInfo::default();
+        let pom = generate_pom_xml_content(&names, &info, "1.21", "com.test", &[]).unwrap();
+        let plugin_yml =
+            generate_plugin_yml_content(&PluginDescriptor::new(&names, &info, "com.test")).unwrap();
+        assert!(pom.contains("<version>1.0.0</version>"));
+        assert!(!pom.contains("<description>"));
+        assert!(!pom.contains("<licenses>"));
+        assert!(!plugin_yml.contains("author"));
+        assert!(!plugin_yml.contains("Notch"));
+    }
+
+    #[test]
+    fn test_render_template_files_skips_unmet_conditions() {
+        let files = vec![
+            TemplateFile::new("pom.xml", "pom"),
+            TemplateFile::new("LICENSE", LICENSE_TEMPLATE).when("license"),
+        ];
+        let names = ProjectNames::from_name("TestName");
+        let context = project_context(&names, &ProjectInfo::default(), "1.21", "com.test");
+        let rendered = render_template_files(&files, &context).unwrap();
+        assert_eq!(
+            vec![(String::from("pom.xml"), String::from("pom"))],
+            rendered
+        );
+    }
 }
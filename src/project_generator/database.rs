@@ -0,0 +1,125 @@
+use serde_yaml::{Mapping, Value as YamlValue};
+
+use crate::errors::GeneratorError;
+use crate::project_generator::config_spec::insert_dotted_key;
+use crate::project_generator::dependencies::Dependency;
+use crate::project_generator::template_engine::Context;
+use crate::project_generator::validation::invalid_parameter;
+use crate::project_generator::{ParameterDescriptor, Parameters};
+
+/// The last HikariCP release running on Java 8, the Java version of servers before 1.17.
+pub const HIKARI_VERSION: &str = "4.0.3";
+
+/// The database the generated plugin connects to through a HikariCP pool.
+/// Both JDBC drivers are bundled with Spigot and Paper.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DatabaseType {
+    Sqlite,
+    Mysql,
+}
+
+impl DatabaseType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            DatabaseType::Sqlite => "sqlite",
+            DatabaseType::Mysql => "mysql",
+        }
+    }
+
+    pub fn parameters() -> Vec<ParameterDescriptor> {
+        vec![
+            ParameterDescriptor::new("database", "Database layer: none, sqlite or mysql", false)
+                .with_default("none"),
+        ]
+    }
+
+    pub fn validate_parameter(name: &str, value: &str) -> Result<(), GeneratorError> {
+        match name {
+            "database" => parse_database(value).map(|_| ()),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn from_parameters(parameters: &Parameters) -> Result<Option<Self>, GeneratorError> {
+        match parameters.get("database") {
+            Some(value) => parse_database(value),
+            None => Ok(None),
+        }
+    }
+
+    pub fn dependencies(&self) -> Vec<Dependency> {
+        vec![Dependency::shaded("com.zaxxer", "HikariCP", HIKARI_VERSION)]
+    }
+
+    /// The `database` section of the `config.yml`, every setting being read by the `Database` class.
+    pub fn config(&self, database_name: &str) -> Mapping {
+        let mut config = Mapping::new();
+        let settings: [(&str, YamlValue); 8] = [
+            ("database.type", YamlValue::from(self.name())),
+            ("database.file", YamlValue::from("database.db")),
+            ("database.host", YamlValue::from("localhost")),
+            ("database.port", YamlValue::from(3306)),
+            ("database.name", YamlValue::from(database_name)),
+            ("database.user", YamlValue::from("root")),
+            ("database.password", YamlValue::from("")),
+            ("database.pool-size", YamlValue::from(10)),
+        ];
+        for (key, value) in settings {
+            insert_dotted_key(&mut config, key, value);
+        }
+        config
+    }
+
+    pub fn extend_context(&self, context: &mut Context) {
+        context.insert("database", self.name());
+    }
+}
+
+fn parse_database(value: &str) -> Result<Option<DatabaseType>, GeneratorError> {
+    match value.to_lowercase().as_str() {
+        "" | "none" => Ok(None),
+        "sqlite" => Ok(Some(DatabaseType::Sqlite)),
+        "mysql" | "mariadb" => Ok(Some(DatabaseType::Mysql)),
+        _ => Err(invalid_parameter(
+            "database",
+            value,
+            "it must be none, sqlite or mysql",
+            String::from("sqlite"),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_parameters_should_read_database_type() {
+        // GIVEN database parameters
+        let parameters =
+            |value: &str| Parameters::from([(String::from("database"), value.to_string())]);
+
+        // WHEN we read them
+        // THEN none should disable the database layer
+        assert_eq!(
+            None,
+            DatabaseType::from_parameters(&parameters("none")).unwrap()
+        );
+        assert_eq!(
+            Some(DatabaseType::Mysql),
+            DatabaseType::from_parameters(&parameters("MySQL")).unwrap()
+        );
+        assert!(DatabaseType::from_parameters(&parameters("mongodb")).is_err());
+    }
+
+    #[test]
+    fn config_should_nest_database_settings() {
+        // GIVEN a sqlite database
+        // WHEN we build its configuration
+        let config = serde_yaml::to_string(&DatabaseType::Sqlite.config("homes")).unwrap();
+
+        // THEN every setting should be under the database section
+        assert!(config.starts_with("database:\n  type: sqlite\n  file: database.db\n"));
+        assert!(config.contains("  name: homes\n"));
+    }
+}
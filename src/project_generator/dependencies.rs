@@ -0,0 +1,262 @@
+use std::collections::BTreeMap;
+
+use crate::errors::GeneratorError;
+use crate::project_generator::content_generator::MAVEN_SHADE_PLUGIN_TEMPLATE;
+use crate::project_generator::template_engine::{render, Context, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DependencyScope {
+    /// Supplied by the server or loaded through the `plugin.yml` `libraries`.
+    Provided,
+    /// Shaded into the plugin jar.
+    Compile,
+    Runtime,
+    Test,
+}
+
+/// The Maven scopes and the Gradle configurations they map to.
+const SCOPES: &[(DependencyScope, &str, &str)] = &[
+    (DependencyScope::Provided, "provided", "compileOnly"),
+    (DependencyScope::Compile, "compile", "implementation"),
+    (DependencyScope::Runtime, "runtime", "runtimeOnly"),
+    (DependencyScope::Test, "test", "testImplementation"),
+];
+
+impl DependencyScope {
+    pub fn name(&self) -> &'static str {
+        SCOPES
+            .iter()
+            .find(|scope| scope.0 == *self)
+            .map_or("", |scope| scope.1)
+    }
+
+    pub fn gradle_configuration(&self) -> &'static str {
+        SCOPES
+            .iter()
+            .find(|scope| scope.0 == *self)
+            .map_or("", |scope| scope.2)
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        SCOPES
+            .iter()
+            .find(|scope| scope.1 == name)
+            .map(|scope| scope.0)
+    }
+
+    pub fn from_gradle_configuration(configuration: &str) -> Option<Self> {
+        SCOPES
+            .iter()
+            .find(|scope| scope.2 == configuration)
+            .map(|scope| scope.0)
+    }
+}
+
+/// The package of the libraries commonly shaded into plugins, by group id, and the name they are relocated under.
+pub const KNOWN_RELOCATIONS: &[(&str, &str, &str)] = &[
+    ("org.bstats", "org.bstats", "bstats"),
+    ("com.zaxxer", "com.zaxxer.hikari", "hikari"),
+    ("net.kyori", "net.kyori", "kyori"),
+    ("org.jetbrains.kotlin", "kotlin", "kotlin"),
+];
+
+/// A library of the generated build, relocated under `<group id>.libs.<name>` when shaded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dependency {
+    pub group_id: String,
+    pub artifact_id: String,
+    pub version: String,
+    pub scope: DependencyScope,
+    pub relocation: Option<Relocation>,
+    pub repository: Option<Repository>,
+}
+
+/// A Maven repository, Maven Central needing none.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Repository {
+    pub id: String,
+    pub url: String,
+}
+
+/// The package of a shaded library and the name it is relocated under.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Relocation {
+    pub package: String,
+    pub name: String,
+}
+
+impl Dependency {
+    pub fn provided(group_id: &str, artifact_id: &str, version: &str) -> Self {
+        Self {
+            group_id: group_id.to_string(),
+            artifact_id: artifact_id.to_string(),
+            version: version.to_string(),
+            scope: DependencyScope::Provided,
+            relocation: None,
+            repository: None,
+        }
+    }
+
+    pub fn with_repository(mut self, id: &str, url: &str) -> Self {
+        self.repository = Some(Repository {
+            id: id.to_string(),
+            url: url.to_string(),
+        });
+        self
+    }
+
+    /// A provided dependency from its `group:artifact:version` coordinates.
+    pub fn parse(coordinates: &str) -> Option<Self> {
+        match coordinates.split(':').collect::<Vec<_>>()[..] {
+            [group_id, artifact_id, version] => {
+                Some(Self::provided(group_id, artifact_id, version))
+            }
+            _ => None,
+        }
+    }
+
+    /// A dependency shaded into the jar, relocated with its known package or else its group id.
+    pub fn shaded(group_id: &str, artifact_id: &str, version: &str) -> Self {
+        let relocation = match KNOWN_RELOCATIONS
+            .iter()
+            .find(|(group, _, _)| *group == group_id)
+        {
+            Some((_, package, name)) => Relocation {
+                package: package.to_string(),
+                name: name.to_string(),
+            },
+            None => Relocation {
+                package: group_id.to_string(),
+                name: artifact_id
+                    .to_lowercase()
+                    .chars()
+                    .filter(char::is_ascii_alphanumeric)
+                    .collect(),
+            },
+        };
+        Self {
+            scope: DependencyScope::Compile,
+            relocation: Some(relocation),
+            ..Self::provided(group_id, artifact_id, version)
+        }
+    }
+
+    /// The `group:artifact:version` coordinates.
+    pub fn coordinates(&self) -> String {
+        format!("{}:{}:{}", self.group_id, self.artifact_id, self.version)
+    }
+}
+
+/// Inserts the `repositories`, `dependencies` and `relocations` lists the build templates use,
+/// and the `shade_plugin` relocating the shaded ones.
+pub fn extend_context(
+    context: &mut Context,
+    dependencies: &[Dependency],
+    group_id: &str,
+) -> Result<(), GeneratorError> {
+    let entry = |values: &[(&str, &str)]| {
+        Value::from(
+            values
+                .iter()
+                .map(|(name, value)| (name.to_string(), Value::from(*value)))
+                .collect::<BTreeMap<_, _>>(),
+        )
+    };
+    let mut repositories: Vec<&Repository> = Vec::new();
+    for repository in dependencies.iter().filter_map(|d| d.repository.as_ref()) {
+        if !repositories.iter().any(|r| r.id == repository.id) {
+            repositories.push(repository);
+        }
+    }
+    let mut relocations = Vec::new();
+    for relocation in dependencies.iter().filter_map(|d| d.relocation.as_ref()) {
+        let shaded_pattern = format!("{}.libs.{}", group_id, relocation.name);
+        let relocation = entry(&[
+            ("pattern", &relocation.package),
+            ("shaded_pattern", &shaded_pattern),
+        ]);
+        // Libraries of the same group, such as the Adventure ones, share their relocation
+        if !relocations.contains(&relocation) {
+            relocations.push(relocation);
+        }
+    }
+    context
+        .insert(
+            "dependencies",
+            dependencies
+                .iter()
+                .map(|dependency| {
+                    entry(&[
+                        ("group_id", &dependency.group_id),
+                        ("artifact_id", &dependency.artifact_id),
+                        ("version", &dependency.version),
+                        ("scope", dependency.scope.name()),
+                    ])
+                })
+                .collect::<Vec<_>>(),
+        )
+        .insert("relocations", relocations)
+        .insert(
+            "repositories",
+            repositories
+                .iter()
+                .map(|repository| entry(&[("id", &repository.id), ("url", &repository.url)]))
+                .collect::<Vec<_>>(),
+        );
+    let shade_plugin = render(MAVEN_SHADE_PLUGIN_TEMPLATE, context)?;
+    context.insert("shade_plugin", shade_plugin);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shaded_should_relocate_known_and_unknown_libraries() {
+        // GIVEN a known and an unknown shaded library
+        // WHEN we create them
+        let bstats = Dependency::shaded("org.bstats", "bstats-bukkit", "3.0.2");
+        let unknown = Dependency::shaded("com.example", "Cool-Lib", "1.0");
+
+        // THEN the known one should use its package and the other its group id
+        assert_eq!(
+            Some(Relocation {
+                package: String::from("org.bstats"),
+                name: String::from("bstats")
+            }),
+            bstats.relocation
+        );
+        assert_eq!(
+            Some(Relocation {
+                package: String::from("com.example"),
+                name: String::from("coollib")
+            }),
+            unknown.relocation
+        );
+    }
+
+    #[test]
+    fn extend_context_should_relocate_shaded_dependencies() {
+        // GIVEN a provided and a shaded dependency
+        let dependencies = vec![
+            Dependency::provided("org.spigotmc", "spigot-api", "1.21-R0.1-SNAPSHOT"),
+            Dependency::shaded("com.zaxxer", "HikariCP", "5.1.0"),
+        ];
+        let mut context = Context::new();
+
+        // WHEN we render them
+        extend_context(&mut context, &dependencies, "com.test").unwrap();
+        let rendered = render(
+            "{% for d in dependencies %}{{ d.artifact_id }}:{{ d.scope }} {% endfor %}{% for r in relocations %}{{ r.pattern }}>{{ r.shaded_pattern }}{% endfor %}",
+            &context,
+        )
+        .unwrap();
+
+        // THEN only the shaded one should be relocated under the group id
+        assert_eq!(
+            "spigot-api:provided HikariCP:compile com.zaxxer.hikari>com.test.libs.hikari",
+            rendered
+        );
+    }
+}
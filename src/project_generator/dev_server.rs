@@ -0,0 +1,325 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::errors::GeneratorError;
+use crate::project_generator::file_change::FileChange;
+use crate::project_generator::project_metadata::content_hash;
+use crate::project_generator::validation::invalid_parameter;
+
+pub const RUN_DIRECTORY: &str = "run";
+pub const EULA_URL: &str = "https://aka.ms/MinecraftEULA";
+const DEFAULT_SERVER_JAR: &str = "server.jar";
+
+/// The `server.properties` entries set for development, the others being left to the server.
+const DEVELOPMENT_PROPERTIES: [(&str, &str); 9] = [
+    ("online-mode", "false"),
+    ("difficulty", "peaceful"),
+    ("spawn-monsters", "false"),
+    ("view-distance", "4"),
+    ("simulation-distance", "4"),
+    ("spawn-protection", "0"),
+    ("max-players", "5"),
+    ("allow-flight", "true"),
+    ("enable-command-block", "true"),
+];
+
+/// How the start scripts run the server.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerSettings {
+    pub memory: String,
+    pub jvm_flags: String,
+    /// The file name of the server jar in the run directory.
+    pub jar: String,
+    pub motd: String,
+}
+
+impl Default for ServerSettings {
+    fn default() -> Self {
+        Self {
+            memory: String::from("2G"),
+            jvm_flags: String::from("-XX:+UseG1GC"),
+            jar: String::from(DEFAULT_SERVER_JAR),
+            motd: String::from("Development server"),
+        }
+    }
+}
+
+/// A server jar of the cache and its SHA-256.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachedJar {
+    pub path: PathBuf,
+    pub sha256: String,
+}
+
+/// A JVM memory size such as `512M` or `2G`.
+pub fn validate_memory(value: &str) -> Result<(), GeneratorError> {
+    let digits = value.trim_end_matches(['K', 'M', 'G', 'k', 'm', 'g']);
+    match digits.len() + 1 == value.len()
+        && !digits.is_empty()
+        && digits.chars().all(|character| character.is_ascii_digit())
+    {
+        true => Ok(()),
+        false => Err(invalid_parameter(
+            "memory",
+            value,
+            "it must be a number followed by K, M or G",
+            String::from("2G"),
+        )),
+    }
+}
+
+/// The run directory files, leaving out the ones already up to date.
+pub fn server_files(root: &Path, settings: &ServerSettings) -> Vec<FileChange> {
+    let path = |name: &str| format!("{}/{}", RUN_DIRECTORY, name);
+    let properties =
+        fs::read_to_string(root.join(path("server.properties"))).unwrap_or_else(|_| {
+            String::from("#Minecraft server properties, tuned for plugin development\n")
+        });
+    let files = [
+        (
+            path("server.properties"),
+            development_properties(&properties, &settings.motd),
+        ),
+        (path("start.sh"), start_script(settings)),
+        (path("start.bat"), start_batch(settings)),
+    ];
+    files
+        .into_iter()
+        .filter_map(
+            |(path, content)| match fs::read_to_string(root.join(&path)) {
+                Ok(before) if before == content => None,
+                Ok(before) => Some(FileChange::edited(&path, before, content)),
+                Err(_) => Some(FileChange::created(&path, content)),
+            },
+        )
+        .collect()
+}
+
+/// Sets the development entries and the motd, keeping every other line as the server wrote it.
+pub fn development_properties(before: &str, motd: &str) -> String {
+    let motd = ("motd", motd);
+    let mut missing: Vec<(&str, &str)> = DEVELOPMENT_PROPERTIES
+        .iter()
+        .copied()
+        .chain(std::iter::once(motd))
+        .collect();
+    let mut lines: Vec<String> = before
+        .lines()
+        .map(|line| {
+            let key = line.split_once('=').map(|(key, _)| key.trim());
+            match missing.iter().position(|(name, _)| Some(*name) == key) {
+                Some(index) => {
+                    let (name, value) = missing.remove(index);
+                    format!("{}={}", name, value)
+                }
+                None => line.to_string(),
+            }
+        })
+        .collect();
+    lines.extend(
+        missing
+            .into_iter()
+            .map(|(name, value)| format!("{}={}", name, value)),
+    );
+    lines.join("\n") + "\n"
+}
+
+/// The command running the server jar, given already quoted for the script.
+fn java_command(settings: &ServerSettings, jar: &str) -> String {
+    let flags = match settings.jvm_flags.trim() {
+        "" => String::new(),
+        flags => format!("{} ", flags),
+    };
+    format!(
+        "java -Xms{memory} -Xmx{memory} {}-jar {} nogui",
+        flags,
+        jar,
+        memory = settings.memory
+    )
+}
+
+fn is_plain_word(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | '+'))
+}
+
+fn start_script(settings: &ServerSettings) -> String {
+    let jar = match is_plain_word(&settings.jar) {
+        true => settings.jar.clone(),
+        false => format!("'{}'", settings.jar.replace('\'', "'\\''")),
+    };
+    format!(
+        "#!/bin/sh\ncd \"$(dirname \"$0\")\"\nexec {}\n",
+        java_command(settings, &jar)
+    )
+}
+
+/// Batch files cannot escape a double quote, so jar names containing one are refused when cached.
+fn start_batch(settings: &ServerSettings) -> String {
+    let jar = match is_plain_word(&settings.jar) {
+        true => settings.jar.clone(),
+        false => format!("\"{}\"", settings.jar.replace('%', "%%")),
+    };
+    format!(
+        "@echo off\r\ncd /d \"%~dp0\"\r\n{}\r\npause\r\n",
+        java_command(settings, &jar)
+    )
+}
+
+/// Copies the server jar into the cache, next to a `<jar>.sha256` checksum file.
+pub fn cache_server_jar(cache: &Path, jar: &Path) -> Result<CachedJar, GeneratorError> {
+    let invalid = |reason: String| GeneratorError::InvalidJar(jar.display().to_string(), reason);
+    let name = jar
+        .file_name()
+        .ok_or_else(|| invalid(String::from("it is not a file")))?;
+    if name
+        .to_string_lossy()
+        .chars()
+        .any(|c| c == '"' || c.is_control())
+    {
+        return Err(invalid(String::from(
+            "its name cannot be written in the start scripts, rename it",
+        )));
+    }
+    let bytes = fs::read(jar).map_err(|error| invalid(error.to_string()))?;
+    let cached = CachedJar {
+        path: cache.join(name),
+        sha256: content_hash(&bytes),
+    };
+    fs::create_dir_all(cache).map_err(GeneratorError::DirectoryCreationError)?;
+    fs::write(&cached.path, &bytes).map_err(GeneratorError::FileCreationError)?;
+    fs::write(
+        checksum_path(&cached.path),
+        format!("{}  {}\n", cached.sha256, name.to_string_lossy()),
+    )
+    .map_err(GeneratorError::FileCreationError)?;
+    Ok(cached)
+}
+
+/// Finds a server jar of the cache by file name, failing when it no longer matches its checksum.
+pub fn find_cached_jar(cache: &Path, name: &str) -> Result<Option<CachedJar>, GeneratorError> {
+    if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\', ':']) {
+        return Err(GeneratorError::InvalidJar(
+            name.to_string(),
+            String::from("only the file name of a cached jar can be looked up in the cache"),
+        ));
+    }
+    let path = cache.join(name);
+    let Ok(bytes) = fs::read(&path) else {
+        return Ok(None);
+    };
+    let invalid =
+        |reason: &str| GeneratorError::InvalidJar(path.display().to_string(), reason.to_string());
+    let recorded = fs::read_to_string(checksum_path(&path))
+        .map_err(|_| invalid("its checksum file is missing"))?;
+    let sha256 = content_hash(&bytes);
+    if recorded.split_whitespace().next() != Some(sha256.as_str()) {
+        return Err(invalid(
+            "it does not match its checksum, give the server jar again",
+        ));
+    }
+    Ok(Some(CachedJar { path, sha256 }))
+}
+
+fn checksum_path(jar: &Path) -> PathBuf {
+    let mut path = jar.as_os_str().to_owned();
+    path.push(".sha256");
+    PathBuf::from(path)
+}
+
+/// Copies a cached jar into the run directory, unless the same jar is already there.
+pub fn install_server_jar(root: &Path, jar: &CachedJar) -> Result<bool, GeneratorError> {
+    let name = jar.path.file_name().unwrap_or_default();
+    let target = root.join(RUN_DIRECTORY).join(name);
+    if fs::read(&target).is_ok_and(|bytes| content_hash(&bytes) == jar.sha256) {
+        return Ok(false);
+    }
+    fs::create_dir_all(root.join(RUN_DIRECTORY)).map_err(GeneratorError::DirectoryCreationError)?;
+    fs::copy(&jar.path, target).map_err(GeneratorError::FileCreationError)?;
+    Ok(true)
+}
+
+/// The `eula.txt` the server writes, agreed to.
+pub fn eula() -> String {
+    format!(
+        "#By changing the setting below to TRUE you are indicating your agreement to our EULA ({}).\neula=true\n",
+        EULA_URL
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn development_properties_should_keep_other_entries() {
+        // GIVEN properties written by the server
+        let before =
+            "#Minecraft server properties\nonline-mode=true\nlevel-seed=42\nview-distance=10\n";
+
+        // WHEN we tune them for development
+        let after = development_properties(before, "Homes development server");
+
+        // THEN the development entries should be set and the others kept
+        assert!(after.starts_with(
+            "#Minecraft server properties\nonline-mode=false\nlevel-seed=42\nview-distance=4\n"
+        ));
+        assert!(after.contains("\ndifficulty=peaceful\n"));
+        assert!(after.ends_with("\nmotd=Homes development server\n"));
+        assert_eq!(
+            after,
+            development_properties(&after, "Homes development server")
+        );
+    }
+
+    #[test]
+    fn find_cached_jar_should_verify_checksum() {
+        // GIVEN a server jar put in the cache
+        let directory = env::temp_dir().join("mcdev-server-cache");
+        let cache = directory.join("servers");
+        fs::create_dir_all(&directory).unwrap();
+        let jar = directory.join("paper-1.21.4.jar");
+        fs::write(&jar, "jar content").unwrap();
+        let cached = cache_server_jar(&cache, &jar).unwrap();
+
+        // WHEN we find it, then after it was altered
+        let found = find_cached_jar(&cache, "paper-1.21.4.jar").unwrap();
+        let unknown = find_cached_jar(&cache, "spigot.jar").unwrap();
+        fs::write(&cached.path, "altered").unwrap();
+        let altered = find_cached_jar(&cache, "paper-1.21.4.jar");
+
+        // THEN only the untouched jar should be found
+        assert_eq!(Some(cached), found);
+        assert_eq!(None, unknown);
+        assert!(altered.is_err());
+
+        // AND names reaching outside the cache should be refused
+        assert!(find_cached_jar(&cache, "../paper-1.21.4.jar").is_err());
+        assert!(find_cached_jar(&cache, "..").is_err());
+
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn start_scripts_should_quote_the_jar() {
+        // GIVEN a server jar whose name has a space, a quote and a percent sign
+        let settings = ServerSettings {
+            jar: String::from("paper 1.21.4 it's 100%.jar"),
+            ..ServerSettings::default()
+        };
+
+        // WHEN we write the start scripts
+        let script = start_script(&settings);
+        let batch = start_batch(&settings);
+
+        // THEN the jar should be one argument of each
+        assert!(script.contains("-jar 'paper 1.21.4 it'\\''s 100%.jar' nogui\n"));
+        assert!(batch.contains("-jar \"paper 1.21.4 it's 100%%.jar\" nogui\r\n"));
+
+        // AND a plain name should be left as is
+        assert!(start_script(&ServerSettings::default()).contains("-jar server.jar nogui"));
+    }
+}
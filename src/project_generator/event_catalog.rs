@@ -0,0 +1,271 @@
+use crate::errors::GeneratorError;
+use crate::project_generator::validation::invalid_parameter;
+
+/// An event class listeners can handle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EventClass {
+    pub name: &'static str,
+    pub package: &'static str,
+    pub cancellable: bool,
+}
+
+impl EventClass {
+    const fn new(package: &'static str, name: &'static str, cancellable: bool) -> Self {
+        Self {
+            name,
+            package,
+            cancellable,
+        }
+    }
+
+    pub fn qualified_name(&self) -> String {
+        format!("{}.{}", self.package, self.name)
+    }
+
+    /// `onPlayerJoin` for `PlayerJoinEvent`.
+    pub fn handler_name(&self) -> String {
+        format!("on{}", self.name.strip_suffix("Event").unwrap_or(self.name))
+    }
+}
+
+const PLAYER: &str = "org.bukkit.event.player";
+const BLOCK: &str = "org.bukkit.event.block";
+const ENTITY: &str = "org.bukkit.event.entity";
+const INVENTORY: &str = "org.bukkit.event.inventory";
+const SERVER: &str = "org.bukkit.event.server";
+const WORLD: &str = "org.bukkit.event.world";
+const WEATHER: &str = "org.bukkit.event.weather";
+const VEHICLE: &str = "org.bukkit.event.vehicle";
+const HANGING: &str = "org.bukkit.event.hanging";
+const ENCHANTMENT: &str = "org.bukkit.event.enchantment";
+const PAPER_PLAYER: &str = "io.papermc.paper.event.player";
+const DESTROYSTOKYO_PLAYER: &str = "com.destroystokyo.paper.event.player";
+const DESTROYSTOKYO_SERVER: &str = "com.destroystokyo.paper.event.server";
+
+pub const EVENTS: &[EventClass] = &[
+    EventClass::new(PLAYER, "AsyncPlayerChatEvent", true),
+    EventClass::new(PLAYER, "AsyncPlayerPreLoginEvent", false),
+    EventClass::new(PLAYER, "PlayerAdvancementDoneEvent", false),
+    EventClass::new(PLAYER, "PlayerAnimationEvent", true),
+    EventClass::new(PLAYER, "PlayerBedEnterEvent", true),
+    EventClass::new(PLAYER, "PlayerBedLeaveEvent", true),
+    EventClass::new(PLAYER, "PlayerBucketEmptyEvent", true),
+    EventClass::new(PLAYER, "PlayerBucketFillEvent", true),
+    EventClass::new(PLAYER, "PlayerChangedWorldEvent", false),
+    EventClass::new(PLAYER, "PlayerCommandPreprocessEvent", true),
+    EventClass::new(PLAYER, "PlayerDropItemEvent", true),
+    EventClass::new(PLAYER, "PlayerEditBookEvent", true),
+    EventClass::new(PLAYER, "PlayerExpChangeEvent", false),
+    EventClass::new(PLAYER, "PlayerFishEvent", true),
+    EventClass::new(PLAYER, "PlayerGameModeChangeEvent", true),
+    EventClass::new(PLAYER, "PlayerInteractAtEntityEvent", true),
+    EventClass::new(PLAYER, "PlayerInteractEntityEvent", true),
+    EventClass::new(PLAYER, "PlayerInteractEvent", true),
+    EventClass::new(PLAYER, "PlayerItemConsumeEvent", true),
+    EventClass::new(PLAYER, "PlayerItemDamageEvent", true),
+    EventClass::new(PLAYER, "PlayerItemHeldEvent", true),
+    EventClass::new(PLAYER, "PlayerJoinEvent", false),
+    EventClass::new(PLAYER, "PlayerKickEvent", true),
+    EventClass::new(PLAYER, "PlayerLevelChangeEvent", false),
+    EventClass::new(PLAYER, "PlayerLoginEvent", false),
+    EventClass::new(PLAYER, "PlayerMoveEvent", true),
+    EventClass::new(PLAYER, "PlayerPortalEvent", true),
+    EventClass::new(PLAYER, "PlayerQuitEvent", false),
+    EventClass::new(PLAYER, "PlayerRespawnEvent", false),
+    EventClass::new(PLAYER, "PlayerSwapHandItemsEvent", true),
+    EventClass::new(PLAYER, "PlayerTeleportEvent", true),
+    EventClass::new(PLAYER, "PlayerToggleFlightEvent", true),
+    EventClass::new(PLAYER, "PlayerToggleSneakEvent", true),
+    EventClass::new(PLAYER, "PlayerToggleSprintEvent", true),
+    EventClass::new(BLOCK, "BlockBreakEvent", true),
+    EventClass::new(BLOCK, "BlockBurnEvent", true),
+    EventClass::new(BLOCK, "BlockDamageEvent", true),
+    EventClass::new(BLOCK, "BlockDispenseEvent", true),
+    EventClass::new(BLOCK, "BlockExplodeEvent", true),
+    EventClass::new(BLOCK, "BlockFadeEvent", true),
+    EventClass::new(BLOCK, "BlockFormEvent", true),
+    EventClass::new(BLOCK, "BlockFromToEvent", true),
+    EventClass::new(BLOCK, "BlockGrowEvent", true),
+    EventClass::new(BLOCK, "BlockIgniteEvent", true),
+    EventClass::new(BLOCK, "BlockPistonExtendEvent", true),
+    EventClass::new(BLOCK, "BlockPistonRetractEvent", true),
+    EventClass::new(BLOCK, "BlockPlaceEvent", true),
+    EventClass::new(BLOCK, "BlockRedstoneEvent", false),
+    EventClass::new(BLOCK, "BlockSpreadEvent", true),
+    EventClass::new(BLOCK, "LeavesDecayEvent", true),
+    EventClass::new(BLOCK, "SignChangeEvent", true),
+    EventClass::new(ENTITY, "CreatureSpawnEvent", true),
+    EventClass::new(ENTITY, "EntityChangeBlockEvent", true),
+    EventClass::new(ENTITY, "EntityDamageByEntityEvent", true),
+    EventClass::new(ENTITY, "EntityDamageEvent", true),
+    EventClass::new(ENTITY, "EntityDeathEvent", false),
+    EventClass::new(ENTITY, "EntityExplodeEvent", true),
+    EventClass::new(ENTITY, "EntityPickupItemEvent", true),
+    EventClass::new(ENTITY, "EntityRegainHealthEvent", true),
+    EventClass::new(ENTITY, "EntityShootBowEvent", true),
+    EventClass::new(ENTITY, "EntitySpawnEvent", true),
+    EventClass::new(ENTITY, "EntityTargetEvent", true),
+    EventClass::new(ENTITY, "EntityTeleportEvent", true),
+    EventClass::new(ENTITY, "EntityToggleGlideEvent", true),
+    EventClass::new(ENTITY, "FoodLevelChangeEvent", true),
+    EventClass::new(ENTITY, "ItemSpawnEvent", true),
+    EventClass::new(ENTITY, "PlayerDeathEvent", false),
+    EventClass::new(ENTITY, "ProjectileHitEvent", true),
+    EventClass::new(ENTITY, "ProjectileLaunchEvent", true),
+    EventClass::new(INVENTORY, "CraftItemEvent", true),
+    EventClass::new(INVENTORY, "FurnaceSmeltEvent", true),
+    EventClass::new(INVENTORY, "InventoryClickEvent", true),
+    EventClass::new(INVENTORY, "InventoryCloseEvent", false),
+    EventClass::new(INVENTORY, "InventoryDragEvent", true),
+    EventClass::new(INVENTORY, "InventoryMoveItemEvent", true),
+    EventClass::new(INVENTORY, "InventoryOpenEvent", true),
+    EventClass::new(INVENTORY, "PrepareAnvilEvent", false),
+    EventClass::new(INVENTORY, "PrepareItemCraftEvent", false),
+    EventClass::new(SERVER, "PluginDisableEvent", false),
+    EventClass::new(SERVER, "PluginEnableEvent", false),
+    EventClass::new(SERVER, "ServerCommandEvent", true),
+    EventClass::new(SERVER, "ServerListPingEvent", false),
+    EventClass::new(SERVER, "ServerLoadEvent", false),
+    EventClass::new(SERVER, "TabCompleteEvent", true),
+    EventClass::new(WORLD, "ChunkLoadEvent", false),
+    EventClass::new(WORLD, "ChunkUnloadEvent", false),
+    EventClass::new(WORLD, "PortalCreateEvent", true),
+    EventClass::new(WORLD, "StructureGrowEvent", true),
+    EventClass::new(WORLD, "WorldLoadEvent", false),
+    EventClass::new(WORLD, "WorldSaveEvent", false),
+    EventClass::new(WORLD, "WorldUnloadEvent", true),
+    EventClass::new(WEATHER, "LightningStrikeEvent", true),
+    EventClass::new(WEATHER, "ThunderChangeEvent", true),
+    EventClass::new(WEATHER, "WeatherChangeEvent", true),
+    EventClass::new(VEHICLE, "VehicleDestroyEvent", true),
+    EventClass::new(VEHICLE, "VehicleEnterEvent", true),
+    EventClass::new(VEHICLE, "VehicleExitEvent", true),
+    EventClass::new(VEHICLE, "VehicleMoveEvent", false),
+    EventClass::new(HANGING, "HangingBreakEvent", true),
+    EventClass::new(HANGING, "HangingPlaceEvent", true),
+    EventClass::new(ENCHANTMENT, "EnchantItemEvent", true),
+    EventClass::new(ENCHANTMENT, "PrepareItemEnchantEvent", true),
+    EventClass::new(PAPER_PLAYER, "AsyncChatEvent", true),
+    EventClass::new(DESTROYSTOKYO_PLAYER, "PlayerJumpEvent", true),
+    EventClass::new(DESTROYSTOKYO_PLAYER, "PlayerArmorChangeEvent", false),
+    EventClass::new(DESTROYSTOKYO_SERVER, "PaperServerListPingEvent", true),
+    EventClass::new(DESTROYSTOKYO_SERVER, "ServerTickEndEvent", false),
+    EventClass::new(DESTROYSTOKYO_SERVER, "ServerTickStartEvent", false),
+];
+
+pub const EVENT_PRIORITIES: [&str; 6] = ["LOWEST", "LOW", "NORMAL", "HIGH", "HIGHEST", "MONITOR"];
+
+/// Finds an event by its simple or fully qualified class name.
+pub fn find_event(name: &str) -> Result<&'static EventClass, GeneratorError> {
+    EVENTS
+        .iter()
+        .find(|event| event.name == name || event.qualified_name() == name)
+        .ok_or_else(|| {
+            let simple_name = name.rsplit('.').next().unwrap_or(name);
+            invalid_parameter(
+                "event",
+                name,
+                "it is not a known Bukkit or Paper event",
+                closest_event(simple_name).to_string(),
+            )
+        })
+}
+
+pub fn parse_event_priority(value: &str) -> Result<&'static str, GeneratorError> {
+    let upper = value.to_uppercase();
+    EVENT_PRIORITIES
+        .iter()
+        .find(|priority| **priority == upper)
+        .copied()
+        .ok_or_else(|| {
+            invalid_parameter(
+                "priority",
+                value,
+                "it must be LOWEST, LOW, NORMAL, HIGH, HIGHEST or MONITOR",
+                String::from("NORMAL"),
+            )
+        })
+}
+
+fn closest_event(name: &str) -> &'static str {
+    let name = name.to_lowercase();
+    EVENTS
+        .iter()
+        .min_by_key(|event| edit_distance(&name, &event.name.to_lowercase()))
+        .map(|event| event.name)
+        .unwrap_or("PlayerJoinEvent")
+}
+
+/// The Levenshtein distance, used to suggest the closest known name.
+pub fn edit_distance(left: &str, right: &str) -> usize {
+    let right: Vec<char> = right.chars().collect();
+    let mut previous: Vec<usize> = (0..=right.len()).collect();
+    for (i, left_char) in left.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, right_char) in right.iter().enumerate() {
+            let substitution = previous[j] + usize::from(left_char != *right_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[right.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_event_should_accept_simple_and_qualified_names() {
+        // GIVEN an event name with and without its package
+        // WHEN we look for them
+        let simple = find_event("BlockBreakEvent").unwrap();
+        let qualified = find_event("io.papermc.paper.event.player.AsyncChatEvent").unwrap();
+
+        // THEN they should be found with their package
+        assert_eq!(
+            "org.bukkit.event.block.BlockBreakEvent",
+            simple.qualified_name()
+        );
+        assert!(simple.cancellable);
+        assert_eq!("onAsyncChat", qualified.handler_name());
+    }
+
+    #[test]
+    fn find_event_should_suggest_closest_event() {
+        // GIVEN a misspelled event
+        // WHEN we look for it
+        let error = find_event("PlayerJoinedEvent").unwrap_err();
+
+        // THEN the closest event should be suggested
+        assert_eq!(Some("PlayerJoinEvent"), error.suggestion());
+    }
+
+    #[test]
+    fn parse_event_priority_should_ignore_case() {
+        // GIVEN priorities in any case
+        // WHEN we parse them
+        // THEN only known priorities should be accepted
+        assert_eq!("HIGHEST", parse_event_priority("highest").unwrap());
+        assert!(parse_event_priority("URGENT").is_err());
+    }
+
+    #[test]
+    fn catalog_should_not_contain_duplicates() {
+        // GIVEN the bundled catalog
+        // WHEN we look for duplicated names
+        let duplicated = EVENTS
+            .iter()
+            .filter(|event| {
+                EVENTS
+                    .iter()
+                    .filter(|other| other.name == event.name)
+                    .count()
+                    > 1
+            })
+            .count();
+
+        // THEN there should be none
+        assert_eq!(0, duplicated);
+    }
+}
@@ -0,0 +1,707 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde_yaml::{Mapping, Value};
+
+use crate::errors::GeneratorError;
+use crate::project_generator::build_model::GRADLE_BUILD_PATH;
+use crate::project_generator::file_operations::create_file_with_parents;
+
+pub const PLUGIN_YML_PATH: &str = "src/main/resources/plugin.yml";
+pub const POM_PATH: &str = "pom.xml";
+const JAVA_SOURCES_PATH: &str = "src/main/java";
+const RESOURCES_PATH: &str = "src/main/resources";
+
+/// A previously generated project, located through the `main` entry of its `plugin.yml`.
+#[derive(Debug)]
+pub struct ExistingProject {
+    root: PathBuf,
+    plugin_yml: Mapping,
+    /// The `plugin.yml` as the user wrote it, edited line by line to keep comments and order.
+    plugin_yml_content: String,
+    package: String,
+    main_class: String,
+}
+
+impl ExistingProject {
+    pub fn open(root: &Path) -> Result<Self, GeneratorError> {
+        let invalid =
+            |reason: String| GeneratorError::InvalidProject(root.display().to_string(), reason);
+        let content = fs::read_to_string(root.join(PLUGIN_YML_PATH))
+            .map_err(|error| invalid(format!("unable to read {}: {}", PLUGIN_YML_PATH, error)))?;
+        let plugin_yml: Mapping = serde_yaml::from_str(&content)
+            .map_err(|error| invalid(format!("unable to parse {}: {}", PLUGIN_YML_PATH, error)))?;
+        let main = plugin_yml
+            .get("main")
+            .and_then(Value::as_str)
+            .ok_or_else(|| invalid(format!("{} has no main entry", PLUGIN_YML_PATH)))?;
+        let (package, main_class) = match main.rsplit_once('.') {
+            Some((package, class)) => (package.to_string(), class.to_string()),
+            None => (String::new(), main.to_string()),
+        };
+
+        let project = Self {
+            root: root.to_path_buf(),
+            plugin_yml,
+            plugin_yml_content: content,
+            package,
+            main_class,
+        };
+        if !project.main_class_path().is_file() {
+            return Err(invalid(format!(
+                "the main class {} does not exist",
+                project.main_class_path().display()
+            )));
+        }
+        Ok(project)
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn package(&self) -> &str {
+        &self.package
+    }
+
+    pub fn main_class(&self) -> &str {
+        &self.main_class
+    }
+
+    pub fn plugin_name(&self) -> &str {
+        self.plugin_yml
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or(&self.main_class)
+    }
+
+    pub fn plugin_yml(&self) -> &Mapping {
+        &self.plugin_yml
+    }
+
+    pub fn java_path(&self, package: &str, class: &str) -> PathBuf {
+        let mut path = self.root.join(JAVA_SOURCES_PATH);
+        for segment in package.split('.').filter(|segment| !segment.is_empty()) {
+            path.push(segment);
+        }
+        path.join(format!("{}.java", class))
+    }
+
+    pub fn resource_path(&self, name: &str) -> PathBuf {
+        self.root.join(RESOURCES_PATH).join(name)
+    }
+
+    pub fn write_resource(&self, name: &str, content: &str) -> Result<(), GeneratorError> {
+        create_file_with_parents(&self.resource_path(name).to_string_lossy(), content)
+    }
+
+    /// The locale and content of every `messages_<locale>.yml`, sorted by locale.
+    pub fn message_files(&self) -> Result<Vec<(String, String)>, GeneratorError> {
+        let invalid = |error: io::Error| {
+            GeneratorError::InvalidProject(self.root.display().to_string(), error.to_string())
+        };
+        let mut files = Vec::new();
+        for entry in fs::read_dir(self.root.join(RESOURCES_PATH)).map_err(invalid)? {
+            let path = entry.map_err(invalid)?.path();
+            let file_name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or("");
+            if let Some(locale) = file_name
+                .strip_prefix("messages_")
+                .and_then(|name| name.strip_suffix(".yml"))
+            {
+                let content = fs::read_to_string(&path).map_err(invalid)?;
+                files.push((locale.to_string(), content));
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
+
+    /// The path, relative to the root, and content of every Java source, sorted by path.
+    pub fn java_sources(&self) -> Result<Vec<(String, String)>, GeneratorError> {
+        let mut sources = Vec::new();
+        let mut directories = vec![self.root.join(JAVA_SOURCES_PATH)];
+        while let Some(directory) = directories.pop() {
+            let Ok(entries) = fs::read_dir(&directory) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    directories.push(path);
+                } else if path
+                    .extension()
+                    .is_some_and(|extension| extension == "java")
+                {
+                    let content = fs::read_to_string(&path).map_err(|error| {
+                        GeneratorError::InvalidProject(
+                            self.root.display().to_string(),
+                            error.to_string(),
+                        )
+                    })?;
+                    let relative = path.strip_prefix(&self.root).unwrap_or(&path);
+                    sources.push((relative.to_string_lossy().replace('\\', "/"), content));
+                }
+            }
+        }
+        sources.sort();
+        Ok(sources)
+    }
+
+    pub fn main_class_path(&self) -> PathBuf {
+        self.java_path(&self.package, &self.main_class)
+    }
+
+    /// Sets `key` of the top level `section` of the `plugin.yml`, failing if it is already declared.
+    pub fn add_plugin_yml_entry(
+        &mut self,
+        section: &str,
+        key: &str,
+        value: Value,
+    ) -> Result<(), GeneratorError> {
+        match self.plugin_yml.get(section) {
+            None | Some(Value::Null) => {}
+            Some(Value::Mapping(entries)) if entries.contains_key(key) => {
+                return Err(self.invalid(format!("{} {} is already declared", section, key)))
+            }
+            Some(Value::Mapping(_)) => {}
+            Some(_) => {
+                return Err(self.invalid(format!(
+                    "{} is not a mapping in {}",
+                    section, PLUGIN_YML_PATH
+                )))
+            }
+        }
+        let entry = serde_yaml::to_string(&Mapping::from_iter([(Value::from(key), value)]))
+            .map_err(|error| GeneratorError::TemplateError(error.to_string()))?;
+        let content = add_yaml_entry(&self.plugin_yml_content, section, &entry)
+            .ok_or_else(|| self.invalid(format!("{} must be a block mapping", section)))?;
+        self.set_plugin_yml_content(content)
+    }
+
+    /// Appends the value to the top level `list` of the `plugin.yml`, such as `softdepend`.
+    /// Returns false when it is already listed.
+    pub fn add_plugin_yml_list_entry(
+        &mut self,
+        list: &str,
+        value: &str,
+    ) -> Result<bool, GeneratorError> {
+        match self.plugin_yml.get(list) {
+            None | Some(Value::Null) => {}
+            Some(Value::Sequence(entries)) => {
+                if entries.iter().any(|entry| entry.as_str() == Some(value)) {
+                    return Ok(false);
+                }
+            }
+            Some(_) => {
+                return Err(self.invalid(format!("{} is not a list in {}", list, PLUGIN_YML_PATH)))
+            }
+        }
+        let item = serde_yaml::to_string(value)
+            .map_err(|error| GeneratorError::TemplateError(error.to_string()))?;
+        let content = add_yaml_list_item(&self.plugin_yml_content, list, item.trim_end())
+            .ok_or_else(|| self.invalid(format!("{} must be a list", list)))?;
+        self.set_plugin_yml_content(content)?;
+        Ok(true)
+    }
+
+    /// Keeps the parsed `plugin.yml` in sync with its edited content.
+    fn set_plugin_yml_content(&mut self, content: String) -> Result<(), GeneratorError> {
+        self.plugin_yml = serde_yaml::from_str(&content).map_err(|error| {
+            self.invalid(format!("unable to edit {}: {}", PLUGIN_YML_PATH, error))
+        })?;
+        self.plugin_yml_content = content;
+        Ok(())
+    }
+
+    fn invalid(&self, reason: String) -> GeneratorError {
+        GeneratorError::InvalidProject(self.root.display().to_string(), reason)
+    }
+
+    /// The path and content of the Maven or Gradle Kotlin DSL build, the ones that can be edited.
+    pub fn read_build(&self) -> Result<(&'static str, String), GeneratorError> {
+        [POM_PATH, GRADLE_BUILD_PATH]
+            .into_iter()
+            .find_map(|path| {
+                let content = fs::read_to_string(self.root.join(path)).ok()?;
+                Some((path, content))
+            })
+            .ok_or_else(|| {
+                GeneratorError::InvalidProject(
+                    self.root.display().to_string(),
+                    format!("unable to read {} or {}", POM_PATH, GRADLE_BUILD_PATH),
+                )
+            })
+    }
+
+    pub fn write_build(&self, path: &str, content: &str) -> Result<(), GeneratorError> {
+        create_file_with_parents(&self.root.join(path).to_string_lossy(), content)
+    }
+
+    pub fn save_plugin_yml(&self) -> Result<(), GeneratorError> {
+        create_file_with_parents(
+            &self.root.join(PLUGIN_YML_PATH).to_string_lossy(),
+            &self.plugin_yml_content,
+        )
+    }
+
+    /// Fails when the source file already exists, so it can be checked before writing anything.
+    pub fn check_new_source(&self, path: &Path) -> Result<(), GeneratorError> {
+        match path.exists() {
+            true => Err(GeneratorError::InvalidProject(
+                self.root.display().to_string(),
+                format!("{} already exists", path.display()),
+            )),
+            false => Ok(()),
+        }
+    }
+
+    /// Writes a new source file, refusing to overwrite an existing one.
+    pub fn create_source(&self, path: &Path, content: &str) -> Result<(), GeneratorError> {
+        self.check_new_source(path)?;
+        create_file_with_parents(&path.to_string_lossy(), content)
+    }
+
+    /// The main class importing the classes and appending the statements to `onEnable`, not written yet.
+    pub fn main_class_registering(
+        &self,
+        imports: &[String],
+        statements: &[String],
+    ) -> Result<String, GeneratorError> {
+        let path = self.main_class_path();
+        let invalid = |reason: String| {
+            GeneratorError::InvalidProject(self.root.display().to_string(), reason)
+        };
+        let mut source = fs::read_to_string(&path)
+            .map_err(|error| invalid(format!("unable to read {}: {}", path.display(), error)))?;
+        for import in imports {
+            source = add_import(&source, import);
+        }
+        append_to_method(&source, "void onEnable()", statements)
+            .ok_or_else(|| invalid(format!("no onEnable method found in {}", path.display())))
+    }
+
+    pub fn write_main_class(&self, source: &str) -> Result<(), GeneratorError> {
+        create_file_with_parents(&self.main_class_path().to_string_lossy(), source)
+    }
+}
+
+/// Adds `import <class>;` after the existing imports, unless it is already there.
+/// The index of the top level `key:` line and the value following it on that line.
+fn find_yaml_key<'a>(lines: &[&'a str], key: &str) -> Option<(usize, &'a str)> {
+    lines.iter().enumerate().find_map(|(index, line)| {
+        let value = line.strip_prefix(key)?.strip_prefix(':')?;
+        (value.is_empty() || value.starts_with(char::is_whitespace)).then_some((index, value))
+    })
+}
+
+/// Whether the value after `key:` leaves the key empty, comments aside, so a block can follow.
+fn is_empty_yaml_value(value: &str) -> bool {
+    let value = value.split(" #").next().unwrap_or_default().trim();
+    matches!(value, "" | "~" | "null" | "{}" | "[]")
+}
+
+/// The lines of the block under the top level key at `index`, up to the next top level key,
+/// leaving out the blank and comment lines ending it.
+fn yaml_block_end(lines: &[&str], index: usize) -> usize {
+    let mut end = index + 1;
+    for (offset, line) in lines[index + 1..].iter().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if !line.starts_with(char::is_whitespace) && !line.starts_with("- ") && *line != "-" {
+            break;
+        }
+        end = index + offset + 2;
+    }
+    end
+}
+
+fn with_trailing_newline(content: &str) -> String {
+    match content.is_empty() || content.ends_with('\n') {
+        true => content.to_string(),
+        false => format!("{}\n", content),
+    }
+}
+
+/// Appends the YAML `entry` at the end of the top level `section` mapping, creating it when
+/// missing, other lines being kept as written. Fails for flow mappings.
+fn add_yaml_entry(content: &str, section: &str, entry: &str) -> Option<String> {
+    let content = with_trailing_newline(content);
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let Some((index, value)) = find_yaml_key(&lines, section) else {
+        return Some(format!(
+            "{}{}:\n{}",
+            content,
+            section,
+            indent_yaml(entry, "  ")
+        ));
+    };
+    if !is_empty_yaml_value(value) {
+        return None;
+    }
+    let end = yaml_block_end(&lines, index);
+    let key_line = match value.trim().starts_with('#') || value.trim().is_empty() {
+        true => lines[index].to_string(),
+        false => format!("{}:\n", section),
+    };
+    let head = lines[..index].concat() + &key_line;
+    let entries = lines[index + 1..end].concat();
+    let indentation: String = entries
+        .lines()
+        .find(|line| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map_or("  ", |line| &line[..line.len() - line.trim_start().len()])
+        .to_string();
+    Some(format!(
+        "{}{}{}{}",
+        head,
+        entries,
+        indent_yaml(entry, &indentation),
+        lines[end..].concat()
+    ))
+}
+
+/// Appends the YAML scalar `item` to the top level `list`, in its block or flow style, creating
+/// the list when missing.
+fn add_yaml_list_item(content: &str, list: &str, item: &str) -> Option<String> {
+    let content = with_trailing_newline(content);
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let Some((index, value)) = find_yaml_key(&lines, list) else {
+        return Some(format!("{}{}:\n- {}\n", content, list, item));
+    };
+    let end = yaml_block_end(&lines, index);
+    let edited = if is_empty_yaml_value(value) && end > index + 1 {
+        let last = lines[end - 1];
+        let prefix = &last[..last.len() - last.trim_start().len()];
+        format!(
+            "{}{}- {}\n{}",
+            lines[..end].concat(),
+            prefix,
+            item,
+            lines[end..].concat()
+        )
+    } else if is_empty_yaml_value(value) {
+        format!(
+            "{}{}:\n- {}\n{}",
+            lines[..index].concat(),
+            list,
+            item,
+            lines[index + 1..].concat()
+        )
+    } else {
+        // A flow sequence on a single line, such as `depend: [Vault]`
+        let line = lines[index];
+        let closing = line.rfind(']')?;
+        let separator = match line[..closing].trim_end().ends_with('[') {
+            true => "",
+            false => ", ",
+        };
+        let insertion = line[..closing].trim_end().len();
+        format!(
+            "{}{}{}{}{}{}",
+            lines[..index].concat(),
+            &line[..insertion],
+            separator,
+            item,
+            &line[insertion..],
+            lines[index + 1..].concat()
+        )
+    };
+    Some(edited)
+}
+
+fn indent_yaml(yaml: &str, indentation: &str) -> String {
+    yaml.lines()
+        .map(|line| format!("{}{}\n", indentation, line))
+        .collect()
+}
+
+pub fn add_import(source: &str, class: &str) -> String {
+    let import = format!("import {};", class);
+    if source.lines().any(|line| line.trim() == import) {
+        return source.to_string();
+    }
+
+    let lines: Vec<&str> = source.lines().collect();
+    let anchor = lines
+        .iter()
+        .rposition(|line| line.trim_start().starts_with("import "))
+        .or_else(|| {
+            lines
+                .iter()
+                .position(|line| line.trim_start().starts_with("package "))
+        });
+    let mut result: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+    match anchor {
+        Some(index) if lines[index].trim_start().starts_with("import ") => {
+            result.insert(index + 1, import)
+        }
+        Some(index) => {
+            result.insert(index + 1, String::new());
+            result.insert(index + 2, import);
+        }
+        None => {
+            result.insert(0, import);
+            result.insert(1, String::new());
+        }
+    }
+    let mut result = result.join("\n");
+    if source.ends_with('\n') {
+        result.push('\n');
+    }
+    result
+}
+
+/// Appends the statements at the end of the body of the first method matching the signature.
+/// Statements already present in the body are skipped.
+pub fn append_to_method(source: &str, signature: &str, statements: &[String]) -> Option<String> {
+    let start = source.find(signature)?;
+    let open = start + source[start..].find('{')?;
+    let close = matching_brace(source, open)?;
+    let body = &source[open + 1..close];
+    let statements: Vec<&String> = statements
+        .iter()
+        .filter(|statement| !body.contains(statement.as_str()))
+        .collect();
+    if statements.is_empty() {
+        return Some(source.to_string());
+    }
+
+    let line_start = source[..close].rfind('\n').map_or(0, |index| index + 1);
+    let before_brace = &source[line_start..close];
+    let (insert_at, indent, prefix) = if before_brace.trim().is_empty() {
+        (line_start, before_brace.to_string(), "")
+    } else {
+        let indent: String = before_brace
+            .chars()
+            .take_while(|c| c.is_whitespace())
+            .collect();
+        (close, indent, "\n")
+    };
+
+    let mut inserted = String::from(prefix);
+    for statement in statements {
+        inserted.push_str(&format!("{}    {}\n", indent, statement));
+    }
+    if !prefix.is_empty() {
+        inserted.push_str(&indent);
+    }
+    let mut result = source.to_string();
+    result.insert_str(insert_at, &inserted);
+    Some(result)
+}
+
+/// The index of the brace closing the one at `open`, skipping strings, chars and comments.
+fn matching_brace(source: &str, open: usize) -> Option<usize> {
+    let bytes = source.as_bytes();
+    let mut depth = 0;
+    let mut index = open;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'{' => depth += 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(index);
+                }
+            }
+            quote @ (b'"' | b'\'') => {
+                index += 1;
+                while index < bytes.len() && bytes[index] != quote {
+                    if bytes[index] == b'\\' {
+                        index += 1;
+                    }
+                    index += 1;
+                }
+            }
+            b'/' if bytes.get(index + 1) == Some(&b'/') => {
+                while index < bytes.len() && bytes[index] != b'\n' {
+                    index += 1;
+                }
+            }
+            b'/' if bytes.get(index + 1) == Some(&b'*') => {
+                index += 2;
+                while index + 1 < bytes.len() && !(bytes[index] == b'*' && bytes[index + 1] == b'/')
+                {
+                    index += 1;
+                }
+                index += 1;
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAIN: &str = r#"package com.test;
+
+import org.bukkit.plugin.java.JavaPlugin;
+
+public class Homes extends JavaPlugin {
+
+    @Override
+    public void onEnable() {
+        getLogger().info("Hello, {SpigotMC}!"); // }
+    }
+}
+"#;
+
+    #[test]
+    fn add_import_should_append_after_imports_once() {
+        // GIVEN a main class with an import
+        // WHEN we add an import twice
+        let source = add_import(
+            &add_import(MAIN, "com.test.commands.HomeCommand"),
+            "com.test.commands.HomeCommand",
+        );
+
+        // THEN it should be added once after the existing one
+        assert!(source.contains(
+            "import org.bukkit.plugin.java.JavaPlugin;\nimport com.test.commands.HomeCommand;\n\npublic"
+        ));
+        assert_eq!(1, source.matches("HomeCommand").count());
+    }
+
+    #[test]
+    fn append_to_method_should_insert_before_closing_brace() {
+        // GIVEN a main class whose onEnable contains braces in strings and comments
+        let statements = vec![String::from(
+            "getCommand(\"home\").setExecutor(new HomeCommand());",
+        )];
+
+        // WHEN we append a statement twice
+        let source = append_to_method(MAIN, "void onEnable()", &statements).unwrap();
+        let source = append_to_method(&source, "void onEnable()", &statements).unwrap();
+
+        // THEN it should be the last statement of the method, only once
+        assert!(source.contains(
+            "// }\n        getCommand(\"home\").setExecutor(new HomeCommand());\n    }\n}\n"
+        ));
+        assert_eq!(1, source.matches("setExecutor").count());
+    }
+
+    #[test]
+    fn append_to_method_should_handle_single_line_bodies() {
+        // GIVEN a method declared on one line
+        let source = "class A {\n    public void onEnable() { }\n}\n";
+
+        // WHEN we append a statement
+        let result = append_to_method(source, "void onEnable()", &[String::from("run();")]);
+
+        // THEN it should be placed on its own line
+        assert_eq!(
+            Some(String::from(
+                "class A {\n    public void onEnable() { \n        run();\n    }\n}\n"
+            )),
+            result
+        );
+    }
+
+    #[test]
+    fn open_should_read_main_class_from_plugin_yml() {
+        // GIVEN a generated project
+        let root = std::env::temp_dir().join("mcdev-existing-project");
+        let _ = fs::remove_dir_all(&root);
+        create_file_with_parents(
+            &root.join(PLUGIN_YML_PATH).to_string_lossy(),
+            "name: Homes\nversion: 1.0.0\nmain: com.test.Homes\n",
+        )
+        .unwrap();
+        create_file_with_parents(
+            &root
+                .join("src/main/java/com/test/Homes.java")
+                .to_string_lossy(),
+            MAIN,
+        )
+        .unwrap();
+
+        // WHEN we open it
+        let project = ExistingProject::open(&root).unwrap();
+
+        // THEN the package and main class should come from the main entry
+        assert_eq!("com.test", project.package());
+        assert_eq!("Homes", project.main_class());
+        assert_eq!("Homes", project.plugin_name());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn add_plugin_yml_entries_should_keep_comments_and_order() {
+        // GIVEN a project whose plugin.yml has comments, quoted values and a flow list
+        let root = std::env::temp_dir().join("mcdev-existing-project-comments");
+        let _ = fs::remove_dir_all(&root);
+        let plugin_yml = "# Homes plugin\nname: Homes\nversion: '1.10'\nmain: com.test.Homes\ndepend: [Vault] # economy\ncommands:\n    # Teleports home\n    home:\n        usage: /<command>\n\n# Permissions below\npermissions: {}\n";
+        create_file_with_parents(&root.join(PLUGIN_YML_PATH).to_string_lossy(), plugin_yml)
+            .unwrap();
+        create_file_with_parents(
+            &root
+                .join("src/main/java/com/test/Homes.java")
+                .to_string_lossy(),
+            MAIN,
+        )
+        .unwrap();
+        let mut project = ExistingProject::open(&root).unwrap();
+
+        // WHEN we add a command, its permission and dependencies
+        let mut command = Mapping::new();
+        command.insert(Value::from("usage"), Value::from("/<command>"));
+        project
+            .add_plugin_yml_entry("commands", "warp", Value::Mapping(command))
+            .unwrap();
+        let mut permission = Mapping::new();
+        permission.insert(Value::from("default"), Value::from("op"));
+        project
+            .add_plugin_yml_entry(
+                "permissions",
+                "homes.command.warp",
+                Value::Mapping(permission),
+            )
+            .unwrap();
+        assert!(project
+            .add_plugin_yml_list_entry("depend", "Essentials")
+            .unwrap());
+        assert!(!project
+            .add_plugin_yml_list_entry("depend", "Vault")
+            .unwrap());
+        assert!(project
+            .add_plugin_yml_list_entry("softdepend", "World Guard")
+            .unwrap());
+        project.save_plugin_yml().unwrap();
+
+        // THEN only the new lines should be added, every other line kept as written
+        assert_eq!(
+            "# Homes plugin\nname: Homes\nversion: '1.10'\nmain: com.test.Homes\ndepend: [Vault, Essentials] # economy\ncommands:\n    # Teleports home\n    home:\n        usage: /<command>\n    warp:\n      usage: /<command>\n\n# Permissions below\npermissions:\n  homes.command.warp:\n    default: op\nsoftdepend:\n- World Guard\n",
+            fs::read_to_string(root.join(PLUGIN_YML_PATH)).unwrap()
+        );
+        assert!(project
+            .plugin_yml()
+            .get("commands")
+            .unwrap()
+            .get("warp")
+            .is_some());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn open_should_fail_without_plugin_yml() {
+        // GIVEN a directory that is not a project
+        let root = std::env::temp_dir().join("mcdev-existing-project-missing");
+
+        // WHEN we open it
+        let result = ExistingProject::open(&root);
+
+        // THEN it should be rejected
+        assert!(matches!(result, Err(GeneratorError::InvalidProject(..))));
+    }
+}
@@ -0,0 +1,56 @@
+use std::fs;
+use std::path::Path;
+
+use crate::errors::GeneratorError;
+use crate::project_generator::file_operations::create_file_with_parents;
+use crate::project_generator::merge::diff;
+
+/// A file of an existing project rewritten in memory, written once the user has seen its diff.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileChange {
+    pub path: String,
+    /// Empty for created files.
+    pub before: String,
+    /// `None` for deleted files.
+    pub after: Option<String>,
+}
+
+impl FileChange {
+    pub fn edited(path: &str, before: String, after: String) -> Self {
+        Self {
+            path: path.to_string(),
+            before,
+            after: Some(after),
+        }
+    }
+
+    pub fn created(path: &str, content: String) -> Self {
+        Self::edited(path, String::new(), content)
+    }
+
+    pub fn deleted(path: &str, before: String) -> Self {
+        Self {
+            path: path.to_string(),
+            before,
+            after: None,
+        }
+    }
+
+    pub fn diff(&self) -> String {
+        let header = match (&self.after, self.before.is_empty()) {
+            (None, _) => format!("--- {} (deleted)", self.path),
+            (Some(_), true) => format!("--- {} (new)", self.path),
+            (Some(_), false) => format!("--- {}", self.path),
+        };
+        let after = self.after.as_deref().unwrap_or_default();
+        format!("{}\n{}", header, diff(&self.before, after))
+    }
+
+    pub fn write(&self, root: &Path) -> Result<(), GeneratorError> {
+        let path = root.join(&self.path);
+        match &self.after {
+            Some(content) => create_file_with_parents(&path.to_string_lossy(), content),
+            None => fs::remove_file(path).map_err(GeneratorError::FileCreationError),
+        }
+    }
+}
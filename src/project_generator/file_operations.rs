@@ -1,4 +1,5 @@
 use std::fs;
+use std::path::{Component, Path};
 
 use crate::errors::GeneratorError;
 
@@ -10,11 +11,43 @@ pub fn create_directory(path: &str) -> Result<(), GeneratorError> {
     fs::create_dir_all(path).map_err(GeneratorError::DirectoryCreationError)
 }
 
+pub fn create_file_with_parents(path: &str, content: &str) -> Result<(), GeneratorError> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent).map_err(GeneratorError::DirectoryCreationError)?;
+    }
+    create_file(path, content)
+}
+
+/// Tells whether a relative path stays inside the directory it is joined to.
+pub fn is_contained_path(path: &str) -> bool {
+    !path.trim().is_empty()
+        && Path::new(path)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+}
+
+pub fn copy_directory(source: &Path, destination: &Path) -> Result<(), GeneratorError> {
+    fs::create_dir_all(destination).map_err(GeneratorError::DirectoryCreationError)?;
+    for entry in fs::read_dir(source).map_err(GeneratorError::DirectoryCreationError)? {
+        let entry = entry.map_err(GeneratorError::DirectoryCreationError)?;
+        let path = entry.path();
+        if entry.file_name() == ".git" {
+            continue;
+        }
+        if path.is_dir() {
+            copy_directory(&path, &destination.join(entry.file_name()))?;
+        } else {
+            fs::copy(&path, destination.join(entry.file_name()))
+                .map_err(GeneratorError::FileCreationError)?;
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::fs;
-    use std::path::Path;
 
     #[test]
     fn test_create_file() {
@@ -37,4 +70,46 @@ mod tests {
 
         fs::remove_dir(path).expect("Unable to delete test directory");
     }
+
+    #[test]
+    fn test_create_file_with_parents() {
+        let path = "test_parents_dir/nested/test_file.txt";
+        let content = "Hello, nested world!";
+        create_file_with_parents(path, content).expect("Unable to create file");
+
+        let file_content = fs::read_to_string(path).expect("Unable to read file");
+        assert_eq!(file_content, content);
+
+        fs::remove_dir_all("test_parents_dir").expect("Unable to delete test directory");
+    }
+
+    #[test]
+    fn test_is_contained_path() {
+        assert!(is_contained_path("src/main/java/Main.java"));
+        assert!(is_contained_path("./README.md"));
+        assert!(!is_contained_path("../../escaped.txt"));
+        assert!(!is_contained_path("src/../../escaped.txt"));
+        assert!(!is_contained_path("/etc/passwd"));
+        assert!(!is_contained_path(""));
+    }
+
+    #[test]
+    fn test_copy_directory() {
+        let source = "test_copy_source";
+        create_file_with_parents(&format!("{}/nested/file.txt", source), "copied")
+            .expect("Unable to create file");
+        create_file_with_parents(&format!("{}/.git/HEAD", source), "ignored")
+            .expect("Unable to create file");
+
+        copy_directory(Path::new(source), Path::new("test_copy_destination"))
+            .expect("Unable to copy directory");
+
+        let file_content = fs::read_to_string("test_copy_destination/nested/file.txt")
+            .expect("Unable to read file");
+        assert_eq!(file_content, "copied");
+        assert!(!Path::new("test_copy_destination/.git").exists());
+
+        fs::remove_dir_all(source).expect("Unable to delete test directory");
+        fs::remove_dir_all("test_copy_destination").expect("Unable to delete test directory");
+    }
 }
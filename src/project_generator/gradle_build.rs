@@ -0,0 +1,647 @@
+use crate::errors::GeneratorError;
+use crate::project_generator::build_model::{
+    BuildModel, ResourceFiltering, Shade, GRADLE_BUILD_PATH,
+};
+use crate::project_generator::dependencies::{Dependency, DependencyScope, Repository};
+
+pub const SHADOW_PLUGIN_ID: &str = "com.gradleup.shadow";
+const SHADOW_PLUGIN_VERSION: &str = "8.3.5";
+/// The plugin id Shadow used before moving to GradleUp.
+const LEGACY_SHADOW_PLUGIN_ID: &str = "com.github.johnrengelman.shadow";
+
+/// Writes a Kotlin DSL `build.gradle.kts`.
+pub fn write_build(model: &BuildModel) -> String {
+    let mut build = String::from("plugins {\n    java\n");
+    if model.shade.is_some() {
+        build.push_str(&format!(
+            "    id(\"{}\") version \"{}\"\n",
+            SHADOW_PLUGIN_ID, SHADOW_PLUGIN_VERSION
+        ));
+    }
+    build.push_str("}\n\n");
+
+    build.push_str(&format!("group = {}\n", string(&model.group_id)));
+    build.push_str(&format!("version = {}\n", string(&model.version)));
+    if let Some(description) = &model.description {
+        build.push_str(&format!("description = {}\n", string(description)));
+    }
+    if !model.properties.is_empty() {
+        build.push('\n');
+        for (name, value) in &model.properties {
+            build.push_str(&format!("extra[\"{}\"] = {}\n", name, string(value)));
+        }
+    }
+
+    if let Some(java_version) = &model.java_version {
+        build.push_str(&format!(
+            "\njava {{\n    toolchain.languageVersion.set(JavaLanguageVersion.of({}))\n}}\n",
+            java_version
+        ));
+    }
+
+    build.push_str("\nrepositories {\n    mavenCentral()\n");
+    for repository in &model.repositories {
+        build.push_str(&format!(
+            "    maven({}) {{\n        name = {}\n    }}\n",
+            string(&repository.url),
+            string(&repository.id)
+        ));
+    }
+    build.push_str("}\n");
+
+    build.push_str("\ndependencies {\n");
+    for dependency in &model.dependencies {
+        let mut coordinates = format!("{}:{}", dependency.group_id, dependency.artifact_id);
+        if !dependency.version.is_empty() {
+            coordinates = format!("{}:{}", coordinates, dependency.version);
+        }
+        build.push_str(&format!(
+            "    {}({})\n",
+            dependency.scope.gradle_configuration(),
+            string(&coordinates)
+        ));
+    }
+    build.push_str("}\n");
+
+    if let Some(encoding) = &model.encoding {
+        build.push_str(&format!(
+            "\ntasks.withType<JavaCompile> {{\n    options.encoding = {}\n}}\n",
+            string(encoding)
+        ));
+    }
+
+    if let Some(filtering) = &model.resource_filtering {
+        build.push_str("\ntasks.processResources {\n");
+        if let Some(encoding) = &model.encoding {
+            build.push_str(&format!("    filteringCharset = {}\n", string(encoding)));
+        }
+        match filtering.includes.is_empty() {
+            true => build.push_str("    expand(project.properties)\n"),
+            false => {
+                let includes: Vec<String> = filtering.includes.iter().map(|i| string(i)).collect();
+                build.push_str(&format!(
+                    "    filesMatching(listOf({})) {{\n        expand(project.properties)\n    }}\n",
+                    includes.join(", ")
+                ));
+            }
+        }
+        build.push_str("}\n");
+    }
+
+    if let Some(shade) = &model.shade {
+        build.push_str("\ntasks.shadowJar {\n    archiveClassifier.set(\"\")\n");
+        for (pattern, shaded_pattern) in &shade.relocations {
+            build.push_str(&format!(
+                "    relocate({}, {})\n",
+                string(pattern),
+                string(shaded_pattern)
+            ));
+        }
+        for exclude in &shade.excludes {
+            build.push_str(&format!("    exclude({})\n", string(exclude)));
+        }
+        if shade.minimize {
+            build.push_str("    minimize()\n");
+        }
+        build.push_str("}\n\ntasks.build {\n    dependsOn(tasks.shadowJar)\n}\n");
+    }
+    build
+}
+
+/// Declares the dependency in a `build.gradle.kts`, with its repository and, when shaded, its Shadow
+/// relocation, in the forms `write_build` emits.
+pub fn add_dependency(
+    build: &str,
+    dependency: &Dependency,
+    group_id: &str,
+) -> Result<String, GeneratorError> {
+    let model = read_build_model(build, None, &mut Vec::new())?;
+    if model.dependencies.iter().any(|declared| {
+        declared.group_id == dependency.group_id && declared.artifact_id == dependency.artifact_id
+    }) {
+        return Err(GeneratorError::InvalidConfiguration(
+            String::from(GRADLE_BUILD_PATH),
+            format!(
+                "{}:{} is already declared",
+                dependency.group_id, dependency.artifact_id
+            ),
+        ));
+    }
+
+    let mut build = build.to_string();
+    if let Some(repository) = &dependency.repository {
+        if !model
+            .repositories
+            .iter()
+            .any(|declared| declared.url == repository.url)
+        {
+            let text = format!(
+                "    maven({}) {{\n        name = {}\n    }}\n",
+                string(&repository.url),
+                string(&repository.id)
+            );
+            build = insert_in_block(&build, "repositories", &text);
+        }
+    }
+    let coordinates = format!(
+        "{}:{}:{}",
+        dependency.group_id, dependency.artifact_id, dependency.version
+    );
+    let text = format!(
+        "    {}({})\n",
+        dependency.scope.gradle_configuration(),
+        string(&coordinates)
+    );
+    build = insert_in_block(&build, "dependencies", &text);
+
+    if let Some(relocation) = &dependency.relocation {
+        let relocated = model.shade.as_ref().is_some_and(|shade| {
+            shade
+                .relocations
+                .iter()
+                .any(|(pattern, _)| *pattern == relocation.package)
+        });
+        if !relocated {
+            if model.shade.is_none() {
+                let plugin = format!(
+                    "    id(\"{}\") version \"{}\"\n",
+                    SHADOW_PLUGIN_ID, SHADOW_PLUGIN_VERSION
+                );
+                build = insert_in_block(&build, "plugins", &plugin);
+            }
+            let relocate = format!(
+                "    relocate({}, {})\n",
+                string(&relocation.package),
+                string(&format!("{}.libs.{}", group_id, relocation.name))
+            );
+            if !has_block(&build, "tasks.shadowJar") {
+                build = insert_in_block(
+                    &build,
+                    "tasks.shadowJar",
+                    "    archiveClassifier.set(\"\")\n",
+                );
+            }
+            build = insert_in_block(&build, "tasks.shadowJar", &relocate);
+            if !build.contains("dependsOn(tasks.shadowJar)") {
+                build = insert_in_block(&build, "tasks.build", "    dependsOn(tasks.shadowJar)\n");
+            }
+        }
+    }
+    Ok(build)
+}
+
+fn has_block(build: &str, block: &str) -> bool {
+    let header = format!("{} {{", block);
+    build.lines().any(|line| line.trim_end() == header)
+}
+
+/// Inserts the text at the end of the top level block, appending the block when missing.
+/// `plugins` goes first, as Gradle requires.
+fn insert_in_block(build: &str, block: &str, text: &str) -> String {
+    let header = format!("{} {{", block);
+    let mut offset = 0;
+    let mut opened = false;
+    for line in build.split_inclusive('\n') {
+        if !opened {
+            opened = line.trim_end() == header;
+        } else if line.trim_end() == "}" {
+            return format!("{}{}{}", &build[..offset], text, &build[offset..]);
+        }
+        offset += line.len();
+    }
+    let created = format!("{}\n{}}}\n", header, text);
+    match block {
+        "plugins" => format!("{}\n{}", created, build),
+        _ => format!("{}\n\n{}", build.trim_end_matches('\n'), created),
+    }
+}
+
+pub fn write_settings(model: &BuildModel) -> String {
+    format!("rootProject.name = {}\n", string(&model.artifact_id))
+}
+
+/// Reads the subset of the Kotlin DSL `write_build` emits, plus common variants, noting the lines it skips.
+pub fn read_build_model(
+    build: &str,
+    settings: Option<&str>,
+    notes: &mut Vec<String>,
+) -> Result<BuildModel, GeneratorError> {
+    let mut model = BuildModel::default();
+    if let Some(name) = settings.and_then(|settings| {
+        settings
+            .lines()
+            .find_map(|line| assignment(line, "rootProject.name"))
+    }) {
+        model.artifact_id = name;
+    }
+
+    let mut blocks: Vec<String> = Vec::new();
+    let mut repository: Option<Repository> = None;
+    for line in build.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with("//") {
+            continue;
+        }
+        let block = blocks.last().map_or("", String::as_str);
+        match block {
+            _ if line == "}" => {}
+            "" => {
+                if let Some(group) = assignment(line, "group") {
+                    model.group_id = group;
+                } else if let Some(version) = assignment(line, "version") {
+                    model.version = version;
+                } else if let Some(description) = assignment(line, "description") {
+                    model.description = Some(description);
+                } else if let Some(property) = line.strip_prefix("extra[\"") {
+                    if let Some((name, value)) = property.split_once("\"]") {
+                        if let Some(value) = assignment(value, "") {
+                            model.properties.push((name.to_string(), value));
+                        }
+                    }
+                }
+            }
+            "plugins" => {
+                if line.contains(SHADOW_PLUGIN_ID) || line.contains(LEGACY_SHADOW_PLUGIN_ID) {
+                    model.shade.get_or_insert_with(Shade::default);
+                } else if !["java", "`java-library`", "id(\"java\")"].contains(&line) {
+                    notes.push(format!("the plugin {} is not converted", line));
+                }
+            }
+            "repositories" => {
+                if let Some(url) = line.strip_prefix("maven(").and_then(first_string) {
+                    let declared = Repository {
+                        id: repository_id(&url),
+                        url,
+                    };
+                    match line.ends_with('{') {
+                        true => repository = Some(declared),
+                        false => model.repositories.push(declared),
+                    }
+                } else if line == "maven {" {
+                    repository = Some(Repository {
+                        id: String::new(),
+                        url: String::new(),
+                    });
+                } else if line != "mavenCentral()" {
+                    notes.push(format!("the repository {} is not converted", line));
+                }
+            }
+            "maven" => {
+                if let Some(repository) = repository.as_mut() {
+                    if let Some(name) = assignment(line, "name") {
+                        repository.id = name;
+                    } else if let Some(url) = line.strip_prefix("url = uri(").and_then(first_string)
+                    {
+                        repository.url = url;
+                    } else if let Some(url) = assignment(line, "url") {
+                        repository.url = url;
+                    }
+                }
+            }
+            "dependencies" => match dependency(line) {
+                Some(dependency) => model.dependencies.push(dependency),
+                None => notes.push(format!("the dependency {} is not converted", line)),
+            },
+            "java" | "toolchain" => {
+                if let Some(version) = line
+                    .split_once("JavaLanguageVersion.of(")
+                    .and_then(|(_, rest)| rest.split_once(')'))
+                {
+                    model.java_version = Some(version.0.trim().to_string());
+                }
+            }
+            "tasks.withType<JavaCompile>" | "tasks.compileJava" => {
+                if let Some(encoding) = assignment(line, "options.encoding") {
+                    model.encoding = Some(encoding);
+                }
+            }
+            "tasks.processResources" => {
+                if let Some(patterns) = line.strip_prefix("filesMatching(") {
+                    let includes = strings(patterns);
+                    model.resource_filtering = Some(ResourceFiltering {
+                        includes: match includes.as_slice() {
+                            [all] if all == "**" || all == "**/*" => Vec::new(),
+                            _ => includes,
+                        },
+                    });
+                } else if line.starts_with("expand(") {
+                    model
+                        .resource_filtering
+                        .get_or_insert_with(ResourceFiltering::default);
+                }
+            }
+            "tasks.shadowJar" => {
+                let shade = model.shade.get_or_insert_with(Shade::default);
+                if let Some(arguments) = line.strip_prefix("relocate(") {
+                    if let [pattern, shaded_pattern] = strings(arguments).as_slice() {
+                        shade
+                            .relocations
+                            .push((pattern.clone(), shaded_pattern.clone()));
+                    }
+                } else if let Some(arguments) = line.strip_prefix("exclude(") {
+                    shade.excludes.extend(strings(arguments));
+                } else if line == "minimize()" {
+                    shade.minimize = true;
+                }
+            }
+            _ => {}
+        }
+
+        let opened = line.matches('{').count();
+        let closed = line.matches('}').count();
+        if opened > closed {
+            blocks.push(block_name(line));
+        } else {
+            for _ in opened..closed {
+                if blocks.pop().as_deref() == Some("maven") {
+                    if let Some(mut repository) = repository.take() {
+                        if repository.id.is_empty() {
+                            repository.id = repository_id(&repository.url);
+                        }
+                        model.repositories.push(repository);
+                    }
+                }
+            }
+        }
+    }
+    if let Some(java_version) = &model.java_version {
+        model.java_version = Some(java_version.trim_start_matches("1.").to_string());
+    }
+    Ok(model)
+}
+
+/// `maven("url") {` is `maven` and `tasks.shadowJar {` is `tasks.shadowJar`.
+fn block_name(line: &str) -> String {
+    let header = line.split('{').next().unwrap_or("").trim();
+    header
+        .split('(')
+        .next()
+        .unwrap_or(header)
+        .trim()
+        .to_string()
+}
+
+/// The string assigned to `name` in `name = "value"`, any name when empty.
+fn assignment(line: &str, name: &str) -> Option<String> {
+    let (key, value) = line.split_once('=')?;
+    if !name.is_empty() && key.trim() != name {
+        return None;
+    }
+    let value = value.trim();
+    let value = value.strip_prefix('"')?.strip_suffix('"')?;
+    Some(from_kotlin(value))
+}
+
+/// `compileOnly("group:artifact:version")`.
+fn dependency(line: &str) -> Option<Dependency> {
+    let (configuration, rest) = line.split_once("(\"")?;
+    let scope = DependencyScope::from_gradle_configuration(configuration.trim())?;
+    let coordinates = from_kotlin(rest.strip_suffix("\")")?);
+    let mut parts = coordinates.splitn(3, ':');
+    let (group_id, artifact_id) = (parts.next()?, parts.next()?);
+    Some(Dependency {
+        scope,
+        ..Dependency::provided(group_id, artifact_id, parts.next().unwrap_or(""))
+    })
+}
+
+/// Every `"string"` argument of a call.
+fn strings(arguments: &str) -> Vec<String> {
+    arguments
+        .split('"')
+        .skip(1)
+        .step_by(2)
+        .map(from_kotlin)
+        .collect()
+}
+
+fn first_string(arguments: &str) -> Option<String> {
+    strings(arguments).into_iter().next()
+}
+
+/// Repositories declared without a name are named after their host.
+fn repository_id(url: &str) -> String {
+    url.split("://")
+        .nth(1)
+        .and_then(|rest| rest.split(['/', ':']).next())
+        .unwrap_or(url)
+        .to_string()
+}
+
+/// A Kotlin string literal, Maven `${property}` references becoming `property()` calls.
+fn string(value: &str) -> String {
+    let mut result = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        result.push_str(&rest[..start].replace('"', "\\\""));
+        match &rest[start + 2..start + end] {
+            "project.version" => result.push_str("$version"),
+            "project.groupId" => result.push_str("$group"),
+            name => result.push_str(&format!("${{property(\"{}\")}}", name)),
+        }
+        rest = &rest[start + end + 1..];
+    }
+    result.push_str(&rest.replace('"', "\\\""));
+    format!("\"{}\"", result)
+}
+
+/// The reverse of `string`, without the quotes.
+fn from_kotlin(value: &str) -> String {
+    let mut result = String::new();
+    let mut rest = value;
+    while let Some(start) = rest.find("${property(\"") {
+        let Some(end) = rest[start..].find("\")}") else {
+            break;
+        };
+        result.push_str(&rest[..start]);
+        result.push_str(&format!("${{{}}}", &rest[start + 12..start + end]));
+        rest = &rest[start + end + 3..];
+    }
+    result.push_str(rest);
+    result
+        .replace("$version", "${project.version}")
+        .replace("$group", "${project.groupId}")
+        .replace("\\\"", "\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project_generator::dependencies::Relocation;
+
+    #[test]
+    fn read_build_model_should_read_handwritten_builds() {
+        // GIVEN a handwritten Kotlin DSL build
+        let build = r#"plugins {
+    java
+    id("com.github.johnrengelman.shadow") version "8.1.1"
+}
+
+group = "com.example"
+version = "2.0.0"
+
+repositories {
+    mavenCentral()
+    maven("https://repo.papermc.io/repository/maven-public/")
+    maven {
+        name = "jitpack"
+        url = uri("https://jitpack.io")
+    }
+}
+
+dependencies {
+    compileOnly("io.papermc.paper:paper-api:1.21.4-R0.1-SNAPSHOT")
+    implementation("org.bstats:bstats-bukkit:3.0.2")
+    implementation(libs.adventure)
+}
+
+java {
+    toolchain {
+        languageVersion.set(JavaLanguageVersion.of(21))
+    }
+}
+
+tasks.processResources {
+    filesMatching("plugin.yml") {
+        expand("version" to version)
+    }
+}
+
+tasks.shadowJar {
+    relocate("org.bstats", "com.example.libs.bstats")
+}
+"#;
+
+        // WHEN we read it
+        let mut notes = Vec::new();
+        let model =
+            read_build_model(build, Some("rootProject.name = \"homes\"\n"), &mut notes).unwrap();
+
+        // THEN every supported part should be read and the others noted
+        assert_eq!("homes", model.artifact_id);
+        assert_eq!("2.0.0", model.version);
+        assert_eq!(Some("21"), model.java_version.as_deref());
+        assert_eq!(
+            vec![
+                (
+                    "repo.papermc.io",
+                    "https://repo.papermc.io/repository/maven-public/"
+                ),
+                ("jitpack", "https://jitpack.io")
+            ],
+            model
+                .repositories
+                .iter()
+                .map(|r| (r.id.as_str(), r.url.as_str()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(2, model.dependencies.len());
+        assert_eq!(DependencyScope::Compile, model.dependencies[1].scope);
+        assert_eq!(
+            Some(vec![String::from("plugin.yml")]),
+            model.resource_filtering.map(|filtering| filtering.includes)
+        );
+        assert_eq!(
+            vec![(
+                String::from("org.bstats"),
+                String::from("com.example.libs.bstats")
+            )],
+            model.shade.unwrap().relocations
+        );
+        assert_eq!(
+            vec![String::from(
+                "the dependency implementation(libs.adventure) is not converted"
+            )],
+            notes
+        );
+    }
+
+    #[test]
+    fn add_dependency_should_insert_repository_dependency_and_relocation() {
+        // GIVEN a build without Shadow
+        let model = BuildModel {
+            group_id: String::from("com.test"),
+            artifact_id: String::from("homes"),
+            version: String::from("1.0.0"),
+            dependencies: vec![Dependency::provided(
+                "org.spigotmc",
+                "spigot-api",
+                "1.21-R0.1-SNAPSHOT",
+            )],
+            ..BuildModel::default()
+        };
+        let build = write_build(&model);
+        let vault = Dependency {
+            repository: Some(Repository {
+                id: String::from("jitpack"),
+                url: String::from("https://jitpack.io"),
+            }),
+            ..Dependency::provided("com.github.MilkBowl", "VaultAPI", "1.7.1")
+        };
+        let bstats = Dependency {
+            scope: DependencyScope::Compile,
+            relocation: Some(Relocation {
+                package: String::from("org.bstats"),
+                name: String::from("bstats"),
+            }),
+            ..Dependency::provided("org.bstats", "bstats-bukkit", "3.0.2")
+        };
+
+        // WHEN we add a provided dependency from jitpack and a shaded one
+        let build = add_dependency(&build, &vault, "com.test").unwrap();
+        let build = add_dependency(&build, &bstats, "com.test").unwrap();
+
+        // THEN they should be written as the converter writes them
+        let mut notes = Vec::new();
+        let read = read_build_model(&build, None, &mut notes).unwrap();
+        assert!(notes.is_empty(), "{:?}", notes);
+        assert_eq!(
+            vec![String::from("https://jitpack.io")],
+            read.repositories
+                .iter()
+                .map(|repository| repository.url.clone())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![
+                ("spigot-api", DependencyScope::Provided),
+                ("VaultAPI", DependencyScope::Provided),
+                ("bstats-bukkit", DependencyScope::Compile),
+            ],
+            read.dependencies
+                .iter()
+                .map(|dependency| (dependency.artifact_id.as_str(), dependency.scope))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(
+                String::from("org.bstats"),
+                String::from("com.test.libs.bstats")
+            )],
+            read.shade.unwrap().relocations
+        );
+        assert!(build.starts_with("plugins {\n    java\n    id(\"com.gradleup.shadow\") version"));
+        assert!(build.ends_with(
+            "tasks.shadowJar {\n    archiveClassifier.set(\"\")\n    relocate(\"org.bstats\", \"com.test.libs.bstats\")\n}\n\ntasks.build {\n    dependsOn(tasks.shadowJar)\n}\n"
+        ));
+
+        // AND adding one again should be refused
+        assert!(add_dependency(&build, &vault, "com.test").is_err());
+    }
+
+    #[test]
+    fn string_should_map_maven_properties() {
+        // GIVEN a version referencing Maven properties
+        let value = "${spigot.version} for ${project.version}";
+
+        // WHEN we write it as Kotlin and read it back
+        let kotlin = string(value);
+
+        // THEN properties should become property() calls
+        assert_eq!("\"${property(\"spigot.version\")} for $version\"", kotlin);
+        assert_eq!(value, from_kotlin(&kotlin[1..kotlin.len() - 1]));
+    }
+}
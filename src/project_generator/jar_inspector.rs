@@ -0,0 +1,497 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use serde_json::Value as JsonValue;
+use serde_yaml::Value as YamlValue;
+use zip::ZipArchive;
+
+use crate::errors::GeneratorError;
+use crate::project_generator::dependencies::KNOWN_RELOCATIONS;
+
+/// The descriptors a jar may carry at its root, and the platform loading it.
+const DESCRIPTORS: [(&str, &str); 7] = [
+    ("plugin.yml", "Bukkit"),
+    ("paper-plugin.yml", "Paper"),
+    ("bungee.yml", "BungeeCord"),
+    ("velocity-plugin.json", "Velocity"),
+    ("fabric.mod.json", "Fabric"),
+    ("META-INF/mods.toml", "Forge"),
+    ("META-INF/neoforge.mods.toml", "NeoForge"),
+];
+
+/// Libraries commonly shaded into plugins, besides the ones the generated builds relocate.
+const SHADED_LIBRARIES: [&str; 7] = [
+    "co.aikar.commands",
+    "com.github.benmanes.caffeine",
+    "com.google.gson",
+    "de.tr7zw.changeme.nbtapi",
+    "org.apache.commons",
+    "org.slf4j",
+    "org.yaml.snakeyaml",
+];
+
+/// APIs the server provides, which must never be shaded.
+const SERVER_APIS: [&str; 8] = [
+    "com.velocitypowered",
+    "io.papermc",
+    "net.fabricmc",
+    "net.md_5.bungee",
+    "net.minecraft",
+    "net.minecraftforge",
+    "org.bukkit",
+    "org.spigotmc",
+];
+
+/// The class file major version of Java 1.0, Java N using `N + 44`.
+const CLASS_VERSION_OFFSET: u16 = 44;
+
+/// A descriptor found in the jar, its metadata in declaration order and the classes it names.
+#[derive(Debug, Default, PartialEq)]
+pub struct JarDescriptor {
+    pub path: &'static str,
+    pub platform: &'static str,
+    pub metadata: Vec<(String, String)>,
+    /// Every class the platform instantiates, and whether the jar contains it.
+    pub classes: Vec<(String, bool)>,
+    pub content: String,
+}
+
+#[derive(Debug, Default)]
+pub struct JarInspection {
+    pub descriptors: Vec<JarDescriptor>,
+    /// The number of classes by class file major version.
+    pub class_versions: BTreeMap<u16, usize>,
+    /// Shaded packages left under their own name, and the reason it matters.
+    pub unrelocated: Vec<(String, &'static str)>,
+    pub notes: Vec<String>,
+}
+
+impl JarInspection {
+    /// The Java version able to load every class of the jar.
+    pub fn minimum_java_version(&self) -> Option<u16> {
+        self.class_versions
+            .keys()
+            .last()
+            .map(|major| java_version(*major))
+    }
+}
+
+pub fn java_version(major: u16) -> u16 {
+    major.saturating_sub(CLASS_VERSION_OFFSET)
+}
+
+/// Opens a built plugin or mod jar and reports what a server would find in it.
+pub fn inspect_jar(path: &Path) -> Result<JarInspection, GeneratorError> {
+    let invalid = |reason: String| GeneratorError::InvalidJar(path.display().to_string(), reason);
+    let file = File::open(path).map_err(|error| invalid(error.to_string()))?;
+    let mut archive = ZipArchive::new(file).map_err(|error| invalid(error.to_string()))?;
+
+    let mut inspection = JarInspection::default();
+    let mut entries = BTreeSet::new();
+    let mut descriptors = BTreeMap::new();
+    for index in 0..archive.len() {
+        let mut entry = archive
+            .by_index(index)
+            .map_err(|error| invalid(error.to_string()))?;
+        let name = entry.name().to_string();
+        if DESCRIPTORS
+            .iter()
+            .any(|(descriptor, _)| *descriptor == name)
+        {
+            let mut content = String::new();
+            match entry.read_to_string(&mut content) {
+                Ok(_) => {
+                    descriptors.insert(name.clone(), content);
+                }
+                Err(error) => inspection
+                    .notes
+                    .push(format!("{} is unreadable: {}", name, error)),
+            }
+        } else if is_counted_class(&name) {
+            let mut header = [0; 8];
+            match entry.read_exact(&mut header) {
+                Ok(()) if header[..4] == [0xCA, 0xFE, 0xBA, 0xBE] => {
+                    let major = u16::from_be_bytes([header[6], header[7]]);
+                    *inspection.class_versions.entry(major).or_default() += 1;
+                }
+                _ => inspection
+                    .notes
+                    .push(format!("{} is not a class file", name)),
+            }
+        }
+        entries.insert(name);
+    }
+
+    for (path, platform) in DESCRIPTORS {
+        if let Some(content) = descriptors.remove(path) {
+            let mut descriptor = JarDescriptor {
+                path,
+                platform,
+                ..JarDescriptor::default()
+            };
+            let parsed = match path.rsplit('.').next() {
+                Some("yml") => read_yaml(&content, &mut descriptor),
+                Some("json") => read_json(&content, &mut descriptor),
+                _ => read_toml(&content, &mut descriptor),
+            };
+            descriptor.content = content;
+            if let Err(error) = parsed {
+                inspection
+                    .notes
+                    .push(format!("{} is invalid: {}", path, error));
+            }
+            for (class, found) in descriptor.classes.iter_mut() {
+                *found = entries.contains(&format!("{}.class", class.replace('.', "/")));
+            }
+            inspection.descriptors.push(descriptor);
+        }
+    }
+    if inspection.descriptors.is_empty() {
+        inspection
+            .notes
+            .push(String::from("No plugin or mod descriptor found"));
+    }
+    inspection.unrelocated = unrelocated_packages(&entries);
+    Ok(inspection)
+}
+
+/// Multi-release and module classes target a given Java version on purpose.
+fn is_counted_class(name: &str) -> bool {
+    name.ends_with(".class")
+        && !name.starts_with("META-INF/versions/")
+        && !name.ends_with("module-info.class")
+}
+
+fn unrelocated_packages(entries: &BTreeSet<String>) -> Vec<(String, &'static str)> {
+    let libraries = KNOWN_RELOCATIONS
+        .iter()
+        .map(|(_, package, _)| *package)
+        .chain(SHADED_LIBRARIES);
+    let packages = libraries
+        .map(|package| (package, "shaded without relocation"))
+        .chain(
+            SERVER_APIS
+                .into_iter()
+                .map(|package| (package, "server API shaded into the jar")),
+        );
+    let mut unrelocated: Vec<(String, &'static str)> = packages
+        .filter(|(package, _)| {
+            let prefix = format!("{}/", package.replace('.', "/"));
+            entries
+                .iter()
+                .any(|entry| entry.starts_with(&prefix) && entry.ends_with(".class"))
+        })
+        .map(|(package, reason)| (package.to_string(), reason))
+        .collect();
+    unrelocated.sort();
+    unrelocated
+}
+
+/// `plugin.yml`, `paper-plugin.yml` and `bungee.yml`.
+fn read_yaml(content: &str, descriptor: &mut JarDescriptor) -> Result<(), String> {
+    let yaml: serde_yaml::Mapping =
+        serde_yaml::from_str(content).map_err(|error| error.to_string())?;
+    for (key, value) in &yaml {
+        let Some(key) = key.as_str() else {
+            continue;
+        };
+        match (key, value) {
+            ("commands" | "permissions", YamlValue::Mapping(entries)) => {
+                let names = entries.keys().filter_map(YamlValue::as_str);
+                descriptor
+                    .metadata
+                    .push((key.to_string(), names.collect::<Vec<_>>().join(", ")));
+            }
+            ("dependencies", YamlValue::Mapping(sections)) => {
+                for (section, dependencies) in sections {
+                    let names = dependencies
+                        .as_mapping()
+                        .into_iter()
+                        .flat_map(|dependencies| dependencies.keys())
+                        .filter_map(YamlValue::as_str);
+                    descriptor.metadata.push((
+                        format!("dependencies.{}", section.as_str().unwrap_or_default()),
+                        names.collect::<Vec<_>>().join(", "),
+                    ));
+                }
+            }
+            _ => {
+                if let Some(value) = yaml_text(value) {
+                    if matches!(key, "main" | "bootstrapper" | "loader") {
+                        descriptor.classes.push((value.clone(), false));
+                    }
+                    descriptor.metadata.push((key.to_string(), value));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn yaml_text(value: &YamlValue) -> Option<String> {
+    match value {
+        YamlValue::String(text) => Some(text.clone()),
+        YamlValue::Number(number) => Some(number.to_string()),
+        YamlValue::Bool(boolean) => Some(boolean.to_string()),
+        YamlValue::Sequence(values) => Some(
+            values
+                .iter()
+                .filter_map(yaml_text)
+                .collect::<Vec<_>>()
+                .join(", "),
+        ),
+        _ => None,
+    }
+}
+
+/// `velocity-plugin.json` and `fabric.mod.json`.
+fn read_json(content: &str, descriptor: &mut JarDescriptor) -> Result<(), String> {
+    let json: serde_json::Map<String, JsonValue> =
+        serde_json::from_str(content).map_err(|error| error.to_string())?;
+    for (key, value) in &json {
+        match (key.as_str(), value) {
+            ("entrypoints", JsonValue::Object(entrypoints)) => {
+                for (entrypoint, classes) in entrypoints {
+                    let classes = classes
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .filter_map(json_text)
+                        .map(|class| match class.split_once("::") {
+                            Some((class, _)) => class.to_string(),
+                            None => class,
+                        })
+                        .collect::<Vec<_>>();
+                    descriptor
+                        .metadata
+                        .push((format!("entrypoints.{}", entrypoint), classes.join(", ")));
+                    descriptor
+                        .classes
+                        .extend(classes.into_iter().map(|class| (class, false)));
+                }
+            }
+            ("depends" | "recommends" | "breaks", JsonValue::Object(dependencies)) => {
+                let dependencies = dependencies
+                    .iter()
+                    .map(|(id, version)| match json_text(version) {
+                        Some(version) => format!("{} {}", id, version),
+                        None => id.clone(),
+                    })
+                    .collect::<Vec<_>>();
+                descriptor
+                    .metadata
+                    .push((key.clone(), dependencies.join(", ")));
+            }
+            _ => {
+                if let Some(text) = json_text(value) {
+                    if key == "main" {
+                        descriptor.classes.push((text.clone(), false));
+                    }
+                    descriptor.metadata.push((key.clone(), text));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Strings, numbers and lists of them, objects such as authors and dependencies by their identifier.
+fn json_text(value: &JsonValue) -> Option<String> {
+    match value {
+        JsonValue::String(text) => Some(text.clone()),
+        JsonValue::Number(number) => Some(number.to_string()),
+        JsonValue::Bool(boolean) => Some(boolean.to_string()),
+        JsonValue::Array(values) => Some(
+            values
+                .iter()
+                .filter_map(json_text)
+                .collect::<Vec<_>>()
+                .join(", "),
+        ),
+        JsonValue::Object(object) => ["id", "name", "value"]
+            .iter()
+            .find_map(|key| object.get(*key))
+            .and_then(json_text),
+        JsonValue::Null => None,
+    }
+}
+
+/// `mods.toml` and `neoforge.mods.toml`, whose mods are found through their `@Mod` annotation.
+fn read_toml(content: &str, descriptor: &mut JarDescriptor) -> Result<(), String> {
+    let toml: toml::Table = toml::from_str(content).map_err(|error| error.to_string())?;
+    for key in ["modLoader", "loaderVersion", "license"] {
+        if let Some(value) = toml.get(key).and_then(toml::Value::as_str) {
+            descriptor
+                .metadata
+                .push((key.to_string(), value.to_string()));
+        }
+    }
+    let mods = toml
+        .get("mods")
+        .and_then(toml::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(toml::Value::as_table);
+    for table in mods {
+        let mod_id = table
+            .get("modId")
+            .and_then(toml::Value::as_str)
+            .unwrap_or_default();
+        for key in ["version", "displayName", "description"] {
+            if let Some(value) = table.get(key).and_then(toml::Value::as_str) {
+                descriptor
+                    .metadata
+                    .push((format!("mods.{}.{}", mod_id, key), value.trim().to_string()));
+            }
+        }
+    }
+    let dependencies = toml
+        .get("dependencies")
+        .and_then(toml::Value::as_table)
+        .into_iter()
+        .flatten();
+    for (mod_id, dependencies) in dependencies {
+        let dependencies = dependencies
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(toml::Value::as_table)
+            .map(|dependency| {
+                let text = |key: &str| {
+                    dependency
+                        .get(key)
+                        .and_then(toml::Value::as_str)
+                        .unwrap_or_default()
+                };
+                format!("{} {}", text("modId"), text("versionRange"))
+            })
+            .collect::<Vec<_>>();
+        descriptor
+            .metadata
+            .push((format!("dependencies.{}", mod_id), dependencies.join(", ")));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::fs;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+    use zip::ZipWriter;
+
+    fn class(major: u16) -> Vec<u8> {
+        let mut bytes = vec![0xCA, 0xFE, 0xBA, 0xBE, 0, 0];
+        bytes.extend(major.to_be_bytes());
+        bytes
+    }
+
+    fn write_jar(path: &Path, entries: &[(&str, Vec<u8>)]) {
+        let mut jar = ZipWriter::new(File::create(path).unwrap());
+        for (name, content) in entries {
+            jar.start_file(*name, SimpleFileOptions::default()).unwrap();
+            jar.write_all(content).unwrap();
+        }
+        jar.finish().unwrap();
+    }
+
+    #[test]
+    fn inspect_jar_should_report_plugin_metadata() {
+        // GIVEN a Bukkit plugin jar built for Java 21 with a Java 8 library and unrelocated bStats
+        let path = env::temp_dir().join("mcdev-inspect-plugin.jar");
+        write_jar(
+            &path,
+            &[
+                (
+                    "plugin.yml",
+                    b"name: Homes\nversion: 1.0.0\nmain: com.test.Homes\napi-version: '1.21'\ndepend: [Vault]\ncommands:\n  home:\n  sethome:\n".to_vec(),
+                ),
+                ("com/test/Homes.class", class(65)),
+                ("com/test/Home.class", class(65)),
+                ("org/bstats/bukkit/Metrics.class", class(52)),
+                ("META-INF/versions/9/module-info.class", class(53)),
+            ],
+        );
+
+        // WHEN we inspect it
+        let inspection = inspect_jar(&path).unwrap();
+
+        // THEN the descriptor, Java versions and shaded packages should be reported
+        let descriptor = &inspection.descriptors[0];
+        assert_eq!("Bukkit", descriptor.platform);
+        assert_eq!(
+            vec![
+                ("name", "Homes"),
+                ("version", "1.0.0"),
+                ("main", "com.test.Homes"),
+                ("api-version", "1.21"),
+                ("depend", "Vault"),
+                ("commands", "home, sethome"),
+            ],
+            descriptor
+                .metadata
+                .iter()
+                .map(|(key, value)| (key.as_str(), value.as_str()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(String::from("com.test.Homes"), true)],
+            descriptor.classes
+        );
+        assert_eq!(
+            BTreeMap::from([(52, 1), (65, 2)]),
+            inspection.class_versions
+        );
+        assert_eq!(Some(21), inspection.minimum_java_version());
+        assert_eq!(
+            vec![(String::from("org.bstats"), "shaded without relocation")],
+            inspection.unrelocated
+        );
+        assert!(inspection.notes.is_empty());
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn inspect_jar_should_report_missing_fabric_entrypoints() {
+        // GIVEN a Fabric mod jar whose client entrypoint class is missing
+        let path = env::temp_dir().join("mcdev-inspect-mod.jar");
+        write_jar(
+            &path,
+            &[
+                (
+                    "fabric.mod.json",
+                    br#"{"schemaVersion": 1, "id": "homes", "version": "1.0.0", "entrypoints": {"main": ["com.test.Homes"], "client": [{"value": "com.test.HomesClient::init"}]}, "depends": {"minecraft": "~1.21"}}"#.to_vec(),
+                ),
+                ("com/test/Homes.class", class(65)),
+                ("org/bukkit/Bukkit.class", class(65)),
+            ],
+        );
+
+        // WHEN we inspect it
+        let inspection = inspect_jar(&path).unwrap();
+
+        // THEN every entrypoint should be checked
+        let descriptor = &inspection.descriptors[0];
+        assert_eq!(
+            vec![
+                (String::from("com.test.Homes"), true),
+                (String::from("com.test.HomesClient"), false),
+            ],
+            descriptor.classes
+        );
+        assert!(descriptor
+            .metadata
+            .contains(&(String::from("depends"), String::from("minecraft ~1.21"))));
+        assert_eq!(
+            vec![(String::from("org.bukkit"), "server API shaded into the jar")],
+            inspection.unrelocated
+        );
+
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,253 @@
+use crate::errors::GeneratorError;
+use crate::project_generator::database::HIKARI_VERSION;
+use crate::project_generator::dependencies::Dependency;
+use crate::project_generator::event_catalog::edit_distance;
+use crate::project_generator::messages::ADVENTURE_PLATFORM_VERSION;
+use crate::project_generator::validation::invalid_parameter;
+use crate::project_generator::{ParameterDescriptor, Parameters};
+
+/// How the plugin providing a library must be declared in the `plugin.yml`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PluginRequirement {
+    /// A library shipped inside the plugin jar.
+    None,
+    Depend(&'static str),
+    SoftDepend(&'static str),
+}
+
+/// A common plugin dependency, usable without network access.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Library {
+    pub id: &'static str,
+    pub name: &'static str,
+    /// The repository id and url, Maven Central needing none.
+    pub repository: Option<(&'static str, &'static str)>,
+    pub group_id: &'static str,
+    pub artifact_id: &'static str,
+    pub version: &'static str,
+    pub shaded: bool,
+    pub requirement: PluginRequirement,
+}
+
+impl Library {
+    pub fn dependency(&self) -> Dependency {
+        let dependency = match self.shaded {
+            true => Dependency::shaded(self.group_id, self.artifact_id, self.version),
+            false => Dependency::provided(self.group_id, self.artifact_id, self.version),
+        };
+        match self.repository {
+            Some((id, url)) => dependency.with_repository(id, url),
+            None => dependency,
+        }
+    }
+
+    /// The name of the plugin providing the library at runtime.
+    pub fn plugin(&self) -> Option<&'static str> {
+        match self.requirement {
+            PluginRequirement::None => None,
+            PluginRequirement::Depend(plugin) | PluginRequirement::SoftDepend(plugin) => {
+                Some(plugin)
+            }
+        }
+    }
+}
+
+const JITPACK: Option<(&str, &str)> = Some(("jitpack", "https://jitpack.io"));
+const ENGINEHUB: Option<(&str, &str)> = Some(("enginehub", "https://maven.enginehub.org/repo/"));
+
+pub const LIBRARIES: &[Library] = &[
+    Library {
+        id: "vault",
+        name: "Vault",
+        repository: JITPACK,
+        group_id: "com.github.MilkBowl",
+        artifact_id: "VaultAPI",
+        version: "1.7.1",
+        shaded: false,
+        requirement: PluginRequirement::SoftDepend("Vault"),
+    },
+    Library {
+        id: "placeholderapi",
+        name: "PlaceholderAPI",
+        repository: Some((
+            "placeholderapi",
+            "https://repo.extendedclip.com/content/repositories/placeholderapi/",
+        )),
+        group_id: "me.clip",
+        artifact_id: "placeholderapi",
+        version: "2.11.6",
+        shaded: false,
+        requirement: PluginRequirement::SoftDepend("PlaceholderAPI"),
+    },
+    Library {
+        id: "protocollib",
+        name: "ProtocolLib",
+        repository: Some(("dmulloy2", "https://repo.dmulloy2.net/repository/public/")),
+        group_id: "com.comphenix.protocol",
+        artifact_id: "ProtocolLib",
+        version: "5.3.0",
+        shaded: false,
+        requirement: PluginRequirement::Depend("ProtocolLib"),
+    },
+    Library {
+        id: "luckperms",
+        name: "LuckPerms API",
+        repository: None,
+        group_id: "net.luckperms",
+        artifact_id: "api",
+        version: "5.4",
+        shaded: false,
+        requirement: PluginRequirement::SoftDepend("LuckPerms"),
+    },
+    Library {
+        id: "worldguard",
+        name: "WorldGuard",
+        repository: ENGINEHUB,
+        group_id: "com.sk89q.worldguard",
+        artifact_id: "worldguard-bukkit",
+        version: "7.0.12",
+        shaded: false,
+        requirement: PluginRequirement::SoftDepend("WorldGuard"),
+    },
+    Library {
+        id: "worldedit",
+        name: "WorldEdit",
+        repository: ENGINEHUB,
+        group_id: "com.sk89q.worldedit",
+        artifact_id: "worldedit-bukkit",
+        version: "7.3.9",
+        shaded: false,
+        requirement: PluginRequirement::SoftDepend("WorldEdit"),
+    },
+    Library {
+        id: "essentialsx",
+        name: "EssentialsX",
+        repository: Some(("essentialsx", "https://repo.essentialsx.net/releases/")),
+        group_id: "net.essentialsx",
+        artifact_id: "EssentialsX",
+        version: "2.20.1",
+        shaded: false,
+        requirement: PluginRequirement::SoftDepend("Essentials"),
+    },
+    Library {
+        id: "citizens",
+        name: "Citizens",
+        repository: Some(("citizens", "https://maven.citizensnpcs.co/repo")),
+        group_id: "net.citizensnpcs",
+        artifact_id: "citizens-main",
+        version: "2.0.35-SNAPSHOT",
+        shaded: false,
+        requirement: PluginRequirement::SoftDepend("Citizens"),
+    },
+    Library {
+        id: "bstats",
+        name: "bStats",
+        repository: None,
+        group_id: "org.bstats",
+        artifact_id: "bstats-bukkit",
+        version: "3.0.2",
+        shaded: true,
+        requirement: PluginRequirement::None,
+    },
+    Library {
+        id: "adventure",
+        name: "Adventure platform",
+        repository: None,
+        group_id: "net.kyori",
+        artifact_id: "adventure-platform-bukkit",
+        version: ADVENTURE_PLATFORM_VERSION,
+        shaded: true,
+        requirement: PluginRequirement::None,
+    },
+    Library {
+        id: "hikaricp",
+        name: "HikariCP",
+        repository: None,
+        group_id: "com.zaxxer",
+        artifact_id: "HikariCP",
+        version: HIKARI_VERSION,
+        shaded: true,
+        requirement: PluginRequirement::None,
+    },
+];
+
+/// The `dependencies` generator parameter, a comma separated list of catalog ids.
+pub fn parameters() -> Vec<ParameterDescriptor> {
+    vec![ParameterDescriptor::new(
+        "dependencies",
+        "Comma separated libraries from the catalog, e.g. vault,placeholderapi",
+        false,
+    )]
+}
+
+pub fn validate_parameter(name: &str, value: &str) -> Result<(), GeneratorError> {
+    match name {
+        "dependencies" => find_libraries(value).map(|_| ()),
+        _ => Ok(()),
+    }
+}
+
+pub fn from_parameters(parameters: &Parameters) -> Result<Vec<&'static Library>, GeneratorError> {
+    find_libraries(parameters.get("dependencies").map_or("", String::as_str))
+}
+
+fn find_libraries(value: &str) -> Result<Vec<&'static Library>, GeneratorError> {
+    let mut libraries: Vec<&'static Library> = Vec::new();
+    for id in value.split(',').map(str::trim).filter(|id| !id.is_empty()) {
+        let library = find_library(id)?;
+        if !libraries.contains(&library) {
+            libraries.push(library);
+        }
+    }
+    Ok(libraries)
+}
+
+pub fn find_library(id: &str) -> Result<&'static Library, GeneratorError> {
+    let id = id.to_lowercase();
+    LIBRARIES
+        .iter()
+        .find(|library| library.id == id)
+        .ok_or_else(|| {
+            let closest = LIBRARIES
+                .iter()
+                .min_by_key(|library| edit_distance(&id, library.id))
+                .map_or("vault", |library| library.id);
+            invalid_parameter(
+                "library",
+                &id,
+                "it is not in the library catalog",
+                closest.to_string(),
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_library_should_suggest_closest_library() {
+        // GIVEN a known and a misspelled library
+        // WHEN we look for them
+        let vault = find_library("Vault").unwrap();
+        let error = find_library("placeholder-api").unwrap_err();
+
+        // THEN the known one should be found and the closest suggested for the other
+        assert_eq!(Some("Vault"), vault.plugin());
+        assert_eq!(Some("https://jitpack.io"), vault.repository.map(|r| r.1));
+        assert_eq!(Some("placeholderapi"), error.suggestion());
+    }
+
+    #[test]
+    fn dependency_should_shade_bundled_libraries() {
+        // GIVEN a library provided by a plugin and one shipped in the jar
+        // WHEN we build their dependencies
+        let protocollib = find_library("protocollib").unwrap().dependency();
+        let bstats = find_library("bstats").unwrap().dependency();
+
+        // THEN only the bundled one should be relocated
+        assert_eq!(None, protocollib.relocation);
+        assert_eq!("dmulloy2", protocollib.repository.unwrap().id);
+        assert_eq!("bstats", bstats.relocation.unwrap().name);
+    }
+}
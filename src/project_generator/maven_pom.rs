@@ -0,0 +1,667 @@
+use std::collections::BTreeMap;
+
+use roxmltree::{Document, Node};
+
+use crate::errors::GeneratorError;
+use crate::project_generator::build_model::{BuildModel, ResourceFiltering, Shade};
+use crate::project_generator::content_generator::MAVEN_SHADE_PLUGIN_TEMPLATE;
+use crate::project_generator::dependencies::{Dependency, DependencyScope, Repository};
+use crate::project_generator::template_engine::{render, Context, Value};
+
+const RESOURCES_DIRECTORY: &str = "src/main/resources";
+
+/// Adds the dependency, its repository and its relocation to a `pom.xml`, editing the top-level
+/// `<repositories>`, `<dependencies>` and the maven-shade-plugin of the `<build>` in place.
+/// Fails when the dependency is already declared or the pom is not a Maven project.
+pub fn add_dependency(
+    pom: &str,
+    dependency: &Dependency,
+    group_id: &str,
+) -> Result<String, GeneratorError> {
+    let invalid = |reason: &str| {
+        GeneratorError::InvalidConfiguration(String::from("pom.xml"), reason.to_string())
+    };
+    let document = Document::parse(pom).map_err(|error| invalid(&error.to_string()))?;
+    let project = document.root_element();
+    if !project.has_tag_name("project") {
+        return Err(invalid("no <project> element found"));
+    }
+    if children(project, "dependencies").iter().any(|declared| {
+        child_text(*declared, "groupId").as_deref() == Some(dependency.group_id.as_str())
+            && child_text(*declared, "artifactId").as_deref()
+                == Some(dependency.artifact_id.as_str())
+    }) {
+        return Err(invalid(&format!(
+            "{}:{} is already declared",
+            dependency.group_id, dependency.artifact_id
+        )));
+    }
+
+    let mut insertions = Insertions::new(pom);
+    let dependencies = child(project, "dependencies");
+    if let Some(repository) = &dependency.repository {
+        let declared = children(project, "repositories")
+            .iter()
+            .any(|declared| child_text(*declared, "url").as_deref() == Some(&repository.url));
+        if !declared {
+            let xml = format!(
+                "<repository>\n    <id>{}</id>\n    <url>{}</url>\n</repository>\n",
+                repository.id, repository.url
+            );
+            match (child(project, "repositories"), dependencies) {
+                (Some(repositories), _) => insertions.at_end_of(repositories, &xml)?,
+                (None, Some(dependencies)) => {
+                    insertions.before(dependencies, &section("repositories", &xml))
+                }
+                (None, None) => insertions.at_end_of(project, &section("repositories", &xml))?,
+            }
+        }
+    }
+    let xml = format!(
+        "<dependency>\n    <groupId>{}</groupId>\n    <artifactId>{}</artifactId>\n    <version>{}</version>\n    <scope>{}</scope>\n</dependency>\n",
+        dependency.group_id,
+        dependency.artifact_id,
+        dependency.version,
+        dependency.scope.name()
+    );
+    match dependencies {
+        Some(dependencies) => insertions.at_end_of(dependencies, &xml)?,
+        None => insertions.at_end_of(project, &section("dependencies", &xml))?,
+    }
+
+    if let Some(relocation) = &dependency.relocation {
+        let shaded_pattern = format!("{}.libs.{}", group_id, relocation.name);
+        let xml = format!(
+            "<relocation>\n    <pattern>{}</pattern>\n    <shadedPattern>{}</shadedPattern>\n</relocation>\n",
+            relocation.package, shaded_pattern
+        );
+        let build = child(project, "build");
+        let plugins = build.and_then(|build| child(build, "plugins"));
+        let shade_plugin = plugins.and_then(|plugins| {
+            plugins.children().find(|plugin| {
+                child_text(*plugin, "artifactId").as_deref() == Some("maven-shade-plugin")
+            })
+        });
+        match shade_plugin {
+            Some(plugin) => add_relocation(&mut insertions, plugin, &relocation.package, &xml)?,
+            None => {
+                let mut context = Context::new();
+                context.insert(
+                    "relocations",
+                    vec![Value::from(BTreeMap::from([
+                        (
+                            String::from("pattern"),
+                            Value::from(relocation.package.as_str()),
+                        ),
+                        (String::from("shaded_pattern"), Value::from(shaded_pattern)),
+                    ]))],
+                );
+                // The template is indented for `<build><plugins>`, 12 spaces deep
+                let plugin = render(MAVEN_SHADE_PLUGIN_TEMPLATE, &context)?
+                    .lines()
+                    .map(|line| format!("{}\n", line.strip_prefix("            ").unwrap_or(line)))
+                    .collect::<String>();
+                match (build, plugins) {
+                    (_, Some(plugins)) => insertions.at_end_of(plugins, &plugin)?,
+                    (Some(build), None) => {
+                        insertions.at_end_of(build, &section("plugins", &plugin))?
+                    }
+                    (None, None) => insertions
+                        .at_end_of(project, &section("build", &section("plugins", &plugin)))?,
+                }
+            }
+        }
+    }
+    Ok(insertions.apply())
+}
+
+/// Adds the relocation to the `<relocations>` of an existing maven-shade-plugin, creating them in
+/// its first `<configuration>`, unless the package is already relocated.
+fn add_relocation(
+    insertions: &mut Insertions,
+    plugin: Node,
+    package: &str,
+    xml: &str,
+) -> Result<(), GeneratorError> {
+    let relocations = plugin
+        .descendants()
+        .find(|node| node.has_tag_name("relocations"));
+    match relocations {
+        Some(relocations) => {
+            let relocated = relocations
+                .children()
+                .any(|relocation| child_text(relocation, "pattern").as_deref() == Some(package));
+            if relocated {
+                Ok(())
+            } else {
+                insertions.at_end_of(relocations, xml)
+            }
+        }
+        None => match plugin
+            .descendants()
+            .find(|node| node.has_tag_name("configuration"))
+        {
+            Some(configuration) => {
+                insertions.at_end_of(configuration, &section("relocations", xml))
+            }
+            None => insertions.at_end_of(
+                plugin,
+                &section("configuration", &section("relocations", xml)),
+            ),
+        },
+    }
+}
+
+/// `<name>` around the lines, indented one level.
+fn section(name: &str, xml: &str) -> String {
+    format!("<{}>\n{}</{}>\n", name, indent(xml, "    "), name)
+}
+
+fn indent(xml: &str, indentation: &str) -> String {
+    xml.lines()
+        .map(|line| format!("{}{}\n", indentation, line))
+        .collect()
+}
+
+/// Lines inserted into the original pom, by offset, applied together so the offsets stay valid.
+struct Insertions<'a> {
+    pom: &'a str,
+    texts: BTreeMap<usize, String>,
+}
+
+impl<'a> Insertions<'a> {
+    fn new(pom: &'a str) -> Self {
+        Self {
+            pom,
+            texts: BTreeMap::new(),
+        }
+    }
+
+    /// Inserts the lines before the closing tag of the element, one level deeper.
+    fn at_end_of(&mut self, element: Node, xml: &str) -> Result<(), GeneratorError> {
+        let range = element.range();
+        let closing = self.pom[range.clone()]
+            .rfind("</")
+            .filter(|_| !self.pom[range.clone()].ends_with("/>"))
+            .ok_or_else(|| {
+                GeneratorError::InvalidConfiguration(
+                    String::from("pom.xml"),
+                    format!(
+                        "<{}/> has no closing tag to add to",
+                        element.tag_name().name()
+                    ),
+                )
+            })?;
+        self.insert(range.start + closing, xml, "    ");
+        Ok(())
+    }
+
+    /// Inserts the lines before the element, at its level.
+    fn before(&mut self, element: Node, xml: &str) {
+        self.insert(element.range().start, xml, "");
+    }
+
+    /// Inserts at the start of the line when only whitespace precedes the offset on it, and
+    /// otherwise on a new line at the offset.
+    fn insert(&mut self, offset: usize, xml: &str, extra_indentation: &str) {
+        let line_start = self.pom[..offset].rfind('\n').map_or(0, |index| index + 1);
+        let prefix = &self.pom[line_start..offset];
+        let (offset, text) = if prefix.trim().is_empty() {
+            (
+                line_start,
+                indent(xml, &format!("{}{}", prefix, extra_indentation)),
+            )
+        } else {
+            let indentation: String = prefix
+                .chars()
+                .take_while(|character| character.is_whitespace())
+                .collect();
+            (
+                offset,
+                format!(
+                    "\n{}{}",
+                    indent(xml, &format!("{}{}", indentation, extra_indentation)),
+                    indentation
+                ),
+            )
+        };
+        self.texts.entry(offset).or_default().push_str(&text);
+    }
+
+    fn apply(self) -> String {
+        let mut pom = self.pom.to_string();
+        for (offset, text) in self.texts.iter().rev() {
+            pom.insert_str(*offset, text);
+        }
+        pom
+    }
+}
+
+/// Reads the parts of a `pom.xml` a Gradle build can express, noting the others.
+pub fn read_build_model(pom: &str, notes: &mut Vec<String>) -> Result<BuildModel, GeneratorError> {
+    let document = Document::parse(pom).map_err(|error| {
+        GeneratorError::InvalidConfiguration(String::from("pom.xml"), error.to_string())
+    })?;
+    let project = document.root_element();
+    let mut model = BuildModel {
+        group_id: child_text(project, "groupId").unwrap_or_default(),
+        artifact_id: child_text(project, "artifactId").unwrap_or_default(),
+        version: child_text(project, "version").unwrap_or_default(),
+        description: child_text(project, "description"),
+        ..BuildModel::default()
+    };
+    for element in [
+        "parent",
+        "name",
+        "url",
+        "licenses",
+        "developers",
+        "modules",
+        "profiles",
+        "dependencyManagement",
+        "pluginRepositories",
+    ] {
+        if child(project, element).is_some() {
+            notes.push(format!("<{}> is not converted", element));
+        }
+    }
+
+    for property in children(project, "properties") {
+        let (name, value) = (property.tag_name().name(), text(property));
+        match name {
+            "maven.compiler.source" | "maven.compiler.target" | "maven.compiler.release" => {
+                model.java_version = Some(java_version(&value));
+            }
+            "project.build.sourceEncoding" => model.encoding = Some(value),
+            _ => model.properties.push((name.to_string(), value)),
+        }
+    }
+    for repository in children(project, "repositories") {
+        model.repositories.push(Repository {
+            id: child_text(repository, "id").unwrap_or_default(),
+            url: child_text(repository, "url").unwrap_or_default(),
+        });
+    }
+    for dependency in children(project, "dependencies") {
+        let scope = child_text(dependency, "scope").unwrap_or_else(|| String::from("compile"));
+        let Some(scope) = DependencyScope::from_name(&scope) else {
+            notes.push(format!(
+                "{}:{} has the unsupported scope {}",
+                child_text(dependency, "groupId").unwrap_or_default(),
+                child_text(dependency, "artifactId").unwrap_or_default(),
+                scope
+            ));
+            continue;
+        };
+        model.dependencies.push(Dependency {
+            scope,
+            ..Dependency::provided(
+                &child_text(dependency, "groupId").unwrap_or_default(),
+                &child_text(dependency, "artifactId").unwrap_or_default(),
+                &child_text(dependency, "version").unwrap_or_default(),
+            )
+        });
+    }
+
+    let build = child(project, "build");
+    for resource in build.map_or(Vec::new(), |build| children(build, "resources")) {
+        if child_text(resource, "filtering").as_deref() != Some("true") {
+            continue;
+        }
+        let directory = child_text(resource, "directory");
+        if directory
+            .as_deref()
+            .is_some_and(|directory| directory != RESOURCES_DIRECTORY)
+        {
+            notes.push(format!(
+                "filtering of {} is not converted",
+                directory.unwrap_or_default()
+            ));
+            continue;
+        }
+        model.resource_filtering = Some(ResourceFiltering {
+            includes: children(resource, "includes")
+                .iter()
+                .map(|node| text(*node))
+                .collect(),
+        });
+    }
+    for plugin in build.map_or(Vec::new(), |build| children(build, "plugins")) {
+        let artifact_id = child_text(plugin, "artifactId").unwrap_or_default();
+        let configurations: Vec<Node> = plugin
+            .descendants()
+            .filter(|node| node.has_tag_name("configuration"))
+            .collect();
+        match artifact_id.as_str() {
+            "maven-shade-plugin" => {
+                let mut shade = Shade::default();
+                for configuration in configurations {
+                    shade.minimize |=
+                        child_text(configuration, "minimizeJar").as_deref() == Some("true");
+                    for relocation in children(configuration, "relocations") {
+                        shade.relocations.push((
+                            child_text(relocation, "pattern").unwrap_or_default(),
+                            child_text(relocation, "shadedPattern").unwrap_or_default(),
+                        ));
+                    }
+                    for filter in children(configuration, "filters") {
+                        shade
+                            .excludes
+                            .extend(children(filter, "excludes").iter().map(|node| text(*node)));
+                    }
+                }
+                model.shade = Some(shade);
+            }
+            "maven-compiler-plugin" => {
+                for configuration in configurations {
+                    if let Some(version) = ["release", "target", "source"]
+                        .iter()
+                        .find_map(|name| child_text(configuration, name))
+                    {
+                        model.java_version = Some(java_version(&version));
+                    }
+                }
+            }
+            "maven-resources-plugin" | "maven-jar-plugin" => {}
+            _ => notes.push(format!("the {} build plugin is not converted", artifact_id)),
+        }
+    }
+    Ok(model)
+}
+
+/// Writes a `pom.xml` laid out like the generated ones.
+pub fn write_pom(model: &BuildModel) -> String {
+    let mut pom = String::from(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<project xsi:schemaLocation="http://maven.apache.org/POM/4.0.0 https://maven.apache.org/xsd/maven-4.0.0.xsd"
+    xmlns="http://maven.apache.org/POM/4.0.0"
+    xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">
+    <modelVersion>4.0.0</modelVersion>
+"#,
+    );
+    pom.push_str(&element(1, "groupId", &model.group_id));
+    pom.push_str(&element(1, "artifactId", &model.artifact_id));
+    pom.push_str(&element(1, "version", &model.version));
+    pom.push_str("    <packaging>jar</packaging>\n");
+    if let Some(description) = &model.description {
+        pom.push_str(&element(1, "description", description));
+    }
+
+    pom.push_str("    <properties>\n");
+    if let Some(java_version) = &model.java_version {
+        pom.push_str(&element(2, "maven.compiler.target", java_version));
+        pom.push_str(&element(2, "maven.compiler.source", java_version));
+    }
+    if let Some(encoding) = &model.encoding {
+        pom.push_str(&element(2, "project.build.sourceEncoding", encoding));
+    }
+    for (name, value) in &model.properties {
+        pom.push_str(&element(2, name, value));
+    }
+    pom.push_str("    </properties>\n");
+
+    pom.push_str("    <repositories>\n");
+    for repository in &model.repositories {
+        pom.push_str("        <repository>\n");
+        pom.push_str(&element(3, "id", &repository.id));
+        pom.push_str(&element(3, "url", &repository.url));
+        pom.push_str("        </repository>\n");
+    }
+    pom.push_str("    </repositories>\n");
+
+    pom.push_str("    <dependencies>\n");
+    for dependency in &model.dependencies {
+        pom.push_str("        <dependency>\n");
+        pom.push_str(&element(3, "groupId", &dependency.group_id));
+        pom.push_str(&element(3, "artifactId", &dependency.artifact_id));
+        if !dependency.version.is_empty() {
+            pom.push_str(&element(3, "version", &dependency.version));
+        }
+        pom.push_str(&element(3, "scope", dependency.scope.name()));
+        pom.push_str("        </dependency>\n");
+    }
+    pom.push_str("    </dependencies>\n");
+
+    if model.shade.is_some() || model.resource_filtering.is_some() {
+        pom.push_str("    <build>\n");
+        if let Some(filtering) = &model.resource_filtering {
+            pom.push_str("        <resources>\n            <resource>\n");
+            pom.push_str(&element(4, "directory", RESOURCES_DIRECTORY));
+            pom.push_str(&element(4, "filtering", "true"));
+            if !filtering.includes.is_empty() {
+                pom.push_str("                <includes>\n");
+                for include in &filtering.includes {
+                    pom.push_str(&element(5, "include", include));
+                }
+                pom.push_str("                </includes>\n");
+            }
+            pom.push_str("            </resource>\n        </resources>\n");
+        }
+        if let Some(shade) = &model.shade {
+            pom.push_str("        <plugins>\n");
+            pom.push_str(&shade_plugin(shade));
+            pom.push_str("        </plugins>\n");
+        }
+        pom.push_str("    </build>\n");
+    }
+    pom.push_str("</project>\n");
+    pom
+}
+
+fn shade_plugin(shade: &Shade) -> String {
+    let mut configuration = String::new();
+    if shade.minimize {
+        configuration.push_str(&element(7, "minimizeJar", "true"));
+    }
+    configuration.push_str(&element(7, "createDependencyReducedPom", "false"));
+    if !shade.excludes.is_empty() {
+        configuration.push_str(
+            "                            <filters>\n                                <filter>\n",
+        );
+        configuration.push_str(&element(9, "artifact", "*:*"));
+        configuration.push_str("                                    <excludes>\n");
+        for exclude in &shade.excludes {
+            configuration.push_str(&element(10, "exclude", exclude));
+        }
+        configuration.push_str("                                    </excludes>\n                                </filter>\n                            </filters>\n");
+    }
+    if !shade.relocations.is_empty() {
+        configuration.push_str("                            <relocations>\n");
+        for (pattern, shaded_pattern) in &shade.relocations {
+            configuration.push_str("                                <relocation>\n");
+            configuration.push_str(&element(9, "pattern", pattern));
+            configuration.push_str(&element(9, "shadedPattern", shaded_pattern));
+            configuration.push_str("                                </relocation>\n");
+        }
+        configuration.push_str("                            </relocations>\n");
+    }
+    format!(
+        r#"            <plugin>
+                <groupId>org.apache.maven.plugins</groupId>
+                <artifactId>maven-shade-plugin</artifactId>
+                <version>3.6.0</version>
+                <executions>
+                    <execution>
+                        <phase>package</phase>
+                        <goals>
+                            <goal>shade</goal>
+                        </goals>
+                        <configuration>
+{}                        </configuration>
+                    </execution>
+                </executions>
+            </plugin>
+"#,
+        configuration
+    )
+}
+
+/// `<name>value</name>` on its own line, indented by `depth` levels of four spaces.
+fn element(depth: usize, name: &str, value: &str) -> String {
+    let value = value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;");
+    format!("{}<{}>{}</{}>\n", "    ".repeat(depth), name, value, name)
+}
+
+/// `1.8` is Java 8.
+fn java_version(version: &str) -> String {
+    version.strip_prefix("1.").unwrap_or(version).to_string()
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|child| child.has_tag_name(name))
+}
+
+fn child_text(node: Node, name: &str) -> Option<String> {
+    child(node, name).map(text)
+}
+
+/// The elements inside the `name` child, such as the `<dependency>` of `<dependencies>`.
+fn children<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Vec<Node<'a, 'input>> {
+    child(node, name).map_or(Vec::new(), |parent| {
+        parent.children().filter(Node::is_element).collect()
+    })
+}
+
+fn text(node: Node) -> String {
+    node.text().unwrap_or_default().trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project_generator::content_generator::generate_pom_xml_content;
+    use crate::project_generator::naming::ProjectNames;
+    use crate::project_generator::project_info::ProjectInfo;
+
+    fn pom(dependencies: &[Dependency]) -> String {
+        generate_pom_xml_content(
+            &ProjectNames::from_name("Homes"),
+            &ProjectInfo::default(),
+            "1.21",
+            "com.test",
+            dependencies,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn add_dependency_should_match_generated_pom() {
+        // GIVEN a generated pom with the spigot api
+        let spigot = Dependency::provided("org.spigotmc", "spigot-api", "${spigot.version}")
+            .with_repository(
+                "spigot-repo",
+                "https://hub.spigotmc.org/nexus/content/repositories/snapshots/",
+            );
+        let vault = Dependency::provided("com.github.MilkBowl", "VaultAPI", "1.7.1")
+            .with_repository("jitpack", "https://jitpack.io");
+        let bstats = Dependency::shaded("org.bstats", "bstats-bukkit", "3.0.2");
+        let hikari = Dependency::shaded("com.zaxxer", "HikariCP", "5.1.0");
+
+        // WHEN we add dependencies one by one
+        let mut edited = pom(std::slice::from_ref(&spigot));
+        for dependency in [&vault, &bstats, &hikari] {
+            edited = add_dependency(&edited, dependency, "com.test").unwrap();
+        }
+
+        // THEN the pom should be the one generated with all of them
+        assert_eq!(pom(&[spigot, vault, bstats, hikari]), edited);
+    }
+
+    #[test]
+    fn add_dependency_should_edit_top_level_sections_and_existing_shade_plugin() {
+        // GIVEN a pom with managed dependencies, plugin management and a shade plugin without
+        // relocations, all before the sections to edit
+        let pom = r#"<project xmlns="http://maven.apache.org/POM/4.0.0">
+    <groupId>com.test</groupId>
+    <artifactId>homes</artifactId>
+    <dependencyManagement>
+        <dependencies>
+            <dependency>
+                <groupId>org.junit</groupId>
+                <artifactId>junit-bom</artifactId>
+                <version>5.11.0</version>
+            </dependency>
+        </dependencies>
+    </dependencyManagement>
+    <build>
+        <pluginManagement>
+            <plugins>
+                <plugin>
+                    <artifactId>maven-compiler-plugin</artifactId>
+                </plugin>
+            </plugins>
+        </pluginManagement>
+        <plugins>
+            <plugin>
+                <groupId>org.apache.maven.plugins</groupId>
+                <artifactId>maven-shade-plugin</artifactId>
+                <dependencies>
+                    <dependency>
+                        <groupId>org.ow2.asm</groupId>
+                        <artifactId>asm</artifactId>
+                        <version>9.7</version>
+                    </dependency>
+                </dependencies>
+                <configuration>
+                    <minimizeJar>true</minimizeJar>
+                </configuration>
+            </plugin>
+        </plugins>
+    </build>
+    <dependencies>
+        <dependency>
+            <groupId>org.spigotmc</groupId>
+            <artifactId>spigot-api</artifactId>
+            <version>1.21-R0.1-SNAPSHOT</version>
+            <scope>provided</scope>
+        </dependency>
+    </dependencies>
+</project>
+"#;
+        let bstats = Dependency::shaded("org.bstats", "bstats-bukkit", "3.0.2");
+
+        // WHEN we add a shaded dependency
+        let edited = add_dependency(pom, &bstats, "com.test").unwrap();
+
+        // THEN it should be added to the top-level dependencies
+        assert!(edited.contains("            <scope>provided</scope>\n        </dependency>\n        <dependency>\n            <groupId>org.bstats</groupId>\n            <artifactId>bstats-bukkit</artifactId>\n            <version>3.0.2</version>\n            <scope>compile</scope>\n        </dependency>\n    </dependencies>\n</project>"));
+        assert_eq!(1, edited.matches("<groupId>org.bstats</groupId>").count());
+
+        // AND relocated by the existing shade plugin, without a second one
+        assert_eq!(
+            1,
+            edited
+                .matches("<artifactId>maven-shade-plugin</artifactId>")
+                .count()
+        );
+        assert!(edited.contains("                    <minimizeJar>true</minimizeJar>\n                    <relocations>\n                        <relocation>\n                            <pattern>org.bstats</pattern>\n                            <shadedPattern>com.test.libs.bstats</shadedPattern>\n                        </relocation>\n                    </relocations>\n                </configuration>"));
+
+        // AND a second library of the same package should not be relocated twice
+        let edited = add_dependency(
+            &edited,
+            &Dependency::shaded("org.bstats", "bstats-base", "3.0.2"),
+            "com.test",
+        )
+        .unwrap();
+        assert_eq!(1, edited.matches("<pattern>org.bstats</pattern>").count());
+    }
+
+    #[test]
+    fn add_dependency_should_refuse_declared_dependency() {
+        // GIVEN a pom declaring bStats
+        let bstats = Dependency::shaded("org.bstats", "bstats-bukkit", "3.0.2");
+        let existing = pom(std::slice::from_ref(&bstats));
+
+        // WHEN we add it again
+        let result = add_dependency(&existing, &bstats, "com.test");
+
+        // THEN it should be refused
+        assert!(matches!(
+            result,
+            Err(GeneratorError::InvalidConfiguration(file, _)) if file == "pom.xml"
+        ));
+    }
+}
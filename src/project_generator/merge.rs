@@ -0,0 +1,266 @@
+/// A line based three-way merge of the user's version and the template's version of a generated file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Merge {
+    chunks: Vec<Chunk>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Chunk {
+    Resolved(Vec<String>),
+    Conflict(Conflict),
+}
+
+/// Lines both sides changed differently since generation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conflict {
+    pub base: Vec<String>,
+    pub ours: Vec<String>,
+    pub theirs: Vec<String>,
+}
+
+impl Merge {
+    /// Merges `ours`, the user's file, and `theirs`, the new template output, from their common `base`.
+    pub fn new(base: &str, ours: &str, theirs: &str) -> Self {
+        let base = lines(base);
+        let ours = lines(ours);
+        let theirs = lines(theirs);
+        let to_ours = matching_lines(&base, &ours);
+        let to_theirs = matching_lines(&base, &theirs);
+
+        let mut chunks = Vec::new();
+        let (mut i, mut j, mut k) = (0, 0, 0);
+        loop {
+            let mut stable = Vec::new();
+            while i < base.len() && to_ours[i] == Some(j) && to_theirs[i] == Some(k) {
+                stable.push(base[i].clone());
+                i += 1;
+                j += 1;
+                k += 1;
+            }
+            if !stable.is_empty() {
+                chunks.push(Chunk::Resolved(stable));
+            }
+            let next = (i..base.len()).find(
+                |&o| matches!((to_ours[o], to_theirs[o]), (Some(a), Some(b)) if a >= j && b >= k),
+            );
+            let (o, a, b) = match next {
+                Some(o) => (o, to_ours[o].unwrap_or(j), to_theirs[o].unwrap_or(k)),
+                None => (base.len(), ours.len(), theirs.len()),
+            };
+            if (o, a, b) == (i, j, k) {
+                break;
+            }
+            chunks.push(resolve(&base[i..o], &ours[j..a], &theirs[k..b]));
+            (i, j, k) = (o, a, b);
+        }
+        Self { chunks }
+    }
+
+    pub fn conflicts(&self) -> Vec<&Conflict> {
+        self.chunks
+            .iter()
+            .filter_map(|chunk| match chunk {
+                Chunk::Conflict(conflict) => Some(conflict),
+                Chunk::Resolved(_) => None,
+            })
+            .collect()
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.conflicts().is_empty()
+    }
+
+    /// The merged file, conflicts being surrounded by git style markers.
+    pub fn with_markers(&self) -> String {
+        self.render(|conflict, output| {
+            output.push(String::from("<<<<<<< yours"));
+            output.extend(conflict.ours.iter().cloned());
+            output.push(String::from("======="));
+            output.extend(conflict.theirs.iter().cloned());
+            output.push(String::from(">>>>>>> template"));
+        })
+    }
+
+    /// The merged file, keeping the user's lines where they conflict.
+    pub fn keeping_ours(&self) -> String {
+        self.render(|conflict, output| output.extend(conflict.ours.iter().cloned()))
+    }
+
+    /// The template changes left out by `keeping_ours`, as `-` base and `+` template lines.
+    pub fn rejects(&self) -> String {
+        let mut output = Vec::new();
+        let mut line = 1;
+        for chunk in &self.chunks {
+            match chunk {
+                Chunk::Resolved(lines) => line += lines.len(),
+                Chunk::Conflict(conflict) => {
+                    output.push(format!("@@ line {} @@", line));
+                    output.extend(conflict.base.iter().map(|l| format!("-{}", l)));
+                    output.extend(conflict.theirs.iter().map(|l| format!("+{}", l)));
+                    line += conflict.ours.len();
+                }
+            }
+        }
+        join(output)
+    }
+
+    fn render(&self, conflict: impl Fn(&Conflict, &mut Vec<String>)) -> String {
+        let mut output = Vec::new();
+        for chunk in &self.chunks {
+            match chunk {
+                Chunk::Resolved(lines) => output.extend(lines.iter().cloned()),
+                Chunk::Conflict(chunk) => conflict(chunk, &mut output),
+            }
+        }
+        join(output)
+    }
+}
+
+/// The changed lines between two versions of a file, as `-` before and `+` after lines.
+pub fn diff(before: &str, after: &str) -> String {
+    let before = lines(before);
+    let after = lines(after);
+    let matches = matching_lines(&before, &after);
+    let mut output = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < before.len() || j < after.len() {
+        if i < before.len() && matches[i] == Some(j) {
+            i += 1;
+            j += 1;
+            continue;
+        }
+        let removed_end = (i..before.len())
+            .find(|&o| matches[o].is_some())
+            .unwrap_or(before.len());
+        let added_end = matches
+            .get(removed_end)
+            .copied()
+            .flatten()
+            .unwrap_or(after.len());
+        output.push(format!("@@ line {} @@", j + 1));
+        output.extend(before[i..removed_end].iter().map(|l| format!("-{}", l)));
+        output.extend(after[j..added_end].iter().map(|l| format!("+{}", l)));
+        (i, j) = (removed_end, added_end);
+    }
+    join(output)
+}
+
+fn resolve(base: &[String], ours: &[String], theirs: &[String]) -> Chunk {
+    if ours == base || ours == theirs {
+        Chunk::Resolved(theirs.to_vec())
+    } else if theirs == base {
+        Chunk::Resolved(ours.to_vec())
+    } else {
+        Chunk::Conflict(Conflict {
+            base: base.to_vec(),
+            ours: ours.to_vec(),
+            theirs: theirs.to_vec(),
+        })
+    }
+}
+
+/// Lines keep their line ending so a missing final newline survives the merge.
+fn lines(content: &str) -> Vec<String> {
+    content.split_inclusive('\n').map(str::to_string).collect()
+}
+
+/// Only the last line may lack its line ending.
+fn join(lines: Vec<String>) -> String {
+    let count = lines.len();
+    lines
+        .into_iter()
+        .enumerate()
+        .map(
+            |(index, line)| match line.ends_with('\n') || index + 1 == count {
+                true => line,
+                false => format!("{}\n", line),
+            },
+        )
+        .collect::<String>()
+}
+
+/// For every line of `left`, the index of the line of `right` it is kept as in their longest common subsequence.
+fn matching_lines(left: &[String], right: &[String]) -> Vec<Option<usize>> {
+    let mut lengths = vec![vec![0usize; right.len() + 1]; left.len() + 1];
+    for i in (0..left.len()).rev() {
+        for j in (0..right.len()).rev() {
+            lengths[i][j] = if left[i] == right[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+    let mut matches = vec![None; left.len()];
+    let (mut i, mut j) = (0, 0);
+    while i < left.len() && j < right.len() {
+        if left[i] == right[j] {
+            matches[i] = Some(j);
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_should_keep_both_sides_changes() {
+        // GIVEN a template change and a user change on different lines
+        let base = "a\nb\nc\nd\n";
+        let ours = "a\nb\nc\nd\ne\n";
+        let theirs = "a\nB\nc\nd\n";
+
+        // WHEN we merge them
+        let merge = Merge::new(base, ours, theirs);
+
+        // THEN both should be kept
+        assert!(merge.is_clean());
+        assert_eq!("a\nB\nc\nd\ne\n", merge.with_markers());
+    }
+
+    #[test]
+    fn diff_should_list_changed_lines() {
+        // GIVEN a file with a changed and an added line
+        let before = "a\nb\nc\n";
+        let after = "a\nB\nc\nd\n";
+
+        // WHEN we diff them
+        // THEN only the changes should be listed
+        assert_eq!(
+            "@@ line 2 @@\n-b\n+B\n@@ line 4 @@\n+d\n",
+            diff(before, after)
+        );
+        assert_eq!("", diff(before, before));
+    }
+
+    #[test]
+    fn merge_should_mark_conflicting_changes() {
+        // GIVEN different changes of the same line
+        let base = "<version>1.0.0</version>\n<name>Homes</name>\n";
+        let ours = "<version>2.0.0</version>\n<name>Homes</name>\n";
+        let theirs = "<version>${revision}</version>\n<name>Homes</name>\n";
+
+        // WHEN we merge them
+        let merge = Merge::new(base, ours, theirs);
+
+        // THEN the conflict should be marked, or kept aside as a reject
+        assert_eq!(1, merge.conflicts().len());
+        assert_eq!(
+            "<<<<<<< yours\n<version>2.0.0</version>\n=======\n<version>${revision}</version>\n>>>>>>> template\n<name>Homes</name>\n",
+            merge.with_markers()
+        );
+        assert_eq!(ours, merge.keeping_ours());
+        assert_eq!(
+            "@@ line 1 @@\n-<version>1.0.0</version>\n+<version>${revision}</version>\n",
+            merge.rejects()
+        );
+    }
+}
@@ -0,0 +1,317 @@
+use std::collections::BTreeMap;
+
+use serde_yaml::{Mapping, Value as YamlValue};
+
+use crate::errors::GeneratorError;
+use crate::project_generator::config_spec::insert_dotted_key;
+use crate::project_generator::dependencies::Dependency;
+use crate::project_generator::template_engine::{Context, Value};
+use crate::project_generator::validation::invalid_parameter;
+use crate::project_generator::{ParameterDescriptor, Parameters};
+
+pub const DEFAULT_LOCALE: &str = "en";
+pub const ADVENTURE_PLATFORM_VERSION: &str = "4.3.4";
+pub const MINIMESSAGE_VERSION: &str = "4.17.0";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MessageFormat {
+    /// `&a` colour codes and `{name}` placeholders.
+    Legacy,
+    /// Adventure MiniMessage tags and `<name>` placeholders.
+    MiniMessage,
+}
+
+/// The localized messages of a generated plugin, one `messages_<locale>.yml` per locale.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MessagesOptions {
+    pub locales: Vec<String>,
+    pub format: MessageFormat,
+}
+
+impl MessagesOptions {
+    pub fn parameters() -> Vec<ParameterDescriptor> {
+        vec![
+            ParameterDescriptor::new(
+                "locales",
+                "Comma separated message locales, e.g. en,fr, leave empty for no messages",
+                false,
+            ),
+            ParameterDescriptor::new(
+                "message_format",
+                "Message colours: legacy or minimessage",
+                false,
+            )
+            .with_default("legacy"),
+        ]
+    }
+
+    pub fn validate_parameter(name: &str, value: &str) -> Result<(), GeneratorError> {
+        match name {
+            "locales" => split_locales(value)
+                .iter()
+                .try_for_each(|locale| validate_locale(locale)),
+            "message_format" => parse_message_format(value).map(|_| ()),
+            _ => Ok(()),
+        }
+    }
+
+    /// The messages requested by the `locales` parameter, the default locale always included.
+    pub fn from_parameters(parameters: &Parameters) -> Result<Option<Self>, GeneratorError> {
+        let mut locales = split_locales(parameters.get("locales").map_or("", String::as_str));
+        if locales.is_empty() {
+            return Ok(None);
+        }
+        locales
+            .iter()
+            .try_for_each(|locale| validate_locale(locale))?;
+        if !locales.iter().any(|locale| locale == DEFAULT_LOCALE) {
+            locales.insert(0, DEFAULT_LOCALE.to_string());
+        }
+        let format = match parameters.get("message_format") {
+            Some(format) => parse_message_format(format)?,
+            None => MessageFormat::Legacy,
+        };
+        Ok(Some(Self { locales, format }))
+    }
+
+    /// The Adventure libraries, shaded and relocated so they run on every server version.
+    /// MiniMessage comes first so its newer `adventure-api` wins over the platform's one.
+    pub fn dependencies(&self) -> Vec<Dependency> {
+        match self.format {
+            MessageFormat::Legacy => Vec::new(),
+            MessageFormat::MiniMessage => vec![
+                Dependency::shaded(
+                    "net.kyori",
+                    "adventure-text-minimessage",
+                    MINIMESSAGE_VERSION,
+                ),
+                Dependency::shaded(
+                    "net.kyori",
+                    "adventure-platform-bukkit",
+                    ADVENTURE_PLATFORM_VERSION,
+                ),
+            ],
+        }
+    }
+
+    /// The content of every locale file, to be translated.
+    pub fn messages_yml(&self, plugin_name: &str) -> Result<String, GeneratorError> {
+        let messages: &[(&str, String)] = match self.format {
+            MessageFormat::Legacy => &[
+                ("prefix", format!("&7[&b{}&7] ", plugin_name)),
+                (
+                    "no-permission",
+                    String::from("&cYou do not have permission to do that."),
+                ),
+                ("player-only", String::from("&cOnly players can do that.")),
+                ("welcome", String::from("&aWelcome, {player}!")),
+            ],
+            MessageFormat::MiniMessage => &[
+                (
+                    "prefix",
+                    format!("<gray>[<aqua>{}</aqua>]</gray> ", plugin_name),
+                ),
+                (
+                    "no-permission",
+                    String::from("<red>You do not have permission to do that."),
+                ),
+                (
+                    "player-only",
+                    String::from("<red>Only players can do that."),
+                ),
+                ("welcome", String::from("<green>Welcome, <player>!")),
+            ],
+        };
+        let mut root = Mapping::new();
+        for (key, message) in messages {
+            root.insert(YamlValue::from(*key), YamlValue::from(message.as_str()));
+        }
+        serde_yaml::to_string(&root)
+            .map_err(|error| GeneratorError::TemplateError(error.to_string()))
+    }
+
+    /// Inserts the `messages` map the main and `Messages` class templates use.
+    pub fn extend_context(
+        &self,
+        context: &mut Context,
+        plugin_name: &str,
+    ) -> Result<(), GeneratorError> {
+        let locales = self
+            .locales
+            .iter()
+            .map(|locale| format!("\"{}\"", locale))
+            .collect::<Vec<_>>()
+            .join(", ");
+        context
+            .insert(
+                "messages",
+                BTreeMap::from([
+                    (String::from("locales"), Value::from(locales)),
+                    (String::from("default_locale"), Value::from(DEFAULT_LOCALE)),
+                    (
+                        String::from("legacy"),
+                        Value::from(self.format == MessageFormat::Legacy),
+                    ),
+                    (
+                        String::from("minimessage"),
+                        Value::from(self.format == MessageFormat::MiniMessage),
+                    ),
+                ]),
+            )
+            .insert("messages_yml", self.messages_yml(plugin_name)?);
+        Ok(())
+    }
+}
+
+/// Adds the message to every locale file, `translations` overriding the text for some locales.
+/// Returns the updated files, failing if the key is already declared in one of them.
+pub fn add_message_to_files(
+    files: &[(String, String)],
+    key: &str,
+    text: &str,
+    translations: &BTreeMap<String, String>,
+) -> Result<Vec<(String, String)>, GeneratorError> {
+    let mut updated = Vec::new();
+    for (locale, content) in files {
+        let mut messages: Mapping = serde_yaml::from_str(content).map_err(|error| {
+            GeneratorError::InvalidConfiguration(
+                format!("messages_{}.yml", locale),
+                error.to_string(),
+            )
+        })?;
+        let text = translations.get(locale).map_or(text, String::as_str);
+        if !insert_dotted_key(&mut messages, key, YamlValue::from(text)) {
+            return Err(invalid_parameter(
+                "message key",
+                key,
+                &format!("it is already declared in messages_{}.yml", locale),
+                format!("{}-2", key),
+            ));
+        }
+        let content = serde_yaml::to_string(&messages)
+            .map_err(|error| GeneratorError::TemplateError(error.to_string()))?;
+        updated.push((locale.clone(), content));
+    }
+    Ok(updated)
+}
+
+fn split_locales(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|locale| locale.trim().to_string())
+        .filter(|locale| !locale.is_empty())
+        .collect()
+}
+
+/// Locales are file name friendly language tags such as `en`, `fr` or `pt_BR`.
+pub fn validate_locale(value: &str) -> Result<(), GeneratorError> {
+    let valid = (2..=10).contains(&value.len())
+        && value.starts_with(|c: char| c.is_ascii_lowercase())
+        && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(invalid_parameter(
+            "locale",
+            value,
+            "it must be a language tag such as en, fr or pt_BR",
+            value.replace('-', "_").to_lowercase(),
+        ))
+    }
+}
+
+fn parse_message_format(value: &str) -> Result<MessageFormat, GeneratorError> {
+    match value.to_lowercase().as_str() {
+        "legacy" => Ok(MessageFormat::Legacy),
+        "minimessage" => Ok(MessageFormat::MiniMessage),
+        _ => Err(invalid_parameter(
+            "message_format",
+            value,
+            "it must be legacy or minimessage",
+            String::from("legacy"),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project_generator::dependencies::DependencyScope;
+
+    #[test]
+    fn from_parameters_should_always_include_default_locale() {
+        // GIVEN parameters requesting french messages with MiniMessage
+        let parameters = Parameters::from([
+            (String::from("locales"), String::from("fr, de")),
+            (String::from("message_format"), String::from("MiniMessage")),
+        ]);
+
+        // WHEN we read the options
+        let options = MessagesOptions::from_parameters(&parameters)
+            .unwrap()
+            .unwrap();
+
+        // THEN english should be added as the fallback
+        assert_eq!(vec!["en", "fr", "de"], options.locales);
+        assert_eq!(MessageFormat::MiniMessage, options.format);
+        let dependencies = options.dependencies();
+        assert_eq!(2, dependencies.len());
+        assert!(dependencies
+            .iter()
+            .all(|dependency| dependency.scope == DependencyScope::Compile
+                && dependency.relocation.is_some()));
+    }
+
+    #[test]
+    fn from_parameters_should_disable_messages_without_locales() {
+        // GIVEN parameters without locales
+        // WHEN we read the options
+        let options = MessagesOptions::from_parameters(&Parameters::new()).unwrap();
+
+        // THEN there should be no messages
+        assert_eq!(None, options);
+    }
+
+    #[test]
+    fn add_message_to_files_should_update_every_locale() {
+        // GIVEN two locale files
+        let files = vec![
+            (String::from("en"), String::from("prefix: '[Homes] '\n")),
+            (String::from("fr"), String::from("prefix: '[Homes] '\n")),
+        ];
+        let translations =
+            BTreeMap::from([(String::from("fr"), String::from("Maison {name} définie"))]);
+
+        // WHEN we add a nested message
+        let updated =
+            add_message_to_files(&files, "home.set", "Home {name} set", &translations).unwrap();
+
+        // THEN every file should contain it, translated when possible
+        assert_eq!(
+            vec![
+                (
+                    String::from("en"),
+                    String::from("prefix: '[Homes] '\nhome:\n  set: Home {name} set\n")
+                ),
+                (
+                    String::from("fr"),
+                    String::from("prefix: '[Homes] '\nhome:\n  set: Maison {name} définie\n")
+                ),
+            ],
+            updated
+        );
+
+        // AND adding it again should be refused
+        assert!(add_message_to_files(&updated, "home.set", "again", &BTreeMap::new()).is_err());
+    }
+
+    #[test]
+    fn validate_parameter_should_reject_invalid_locales() {
+        // GIVEN malformed locales and formats
+        // WHEN we validate them
+        // THEN they should be rejected
+        assert!(MessagesOptions::validate_parameter("locales", "en,pt_BR").is_ok());
+        assert!(MessagesOptions::validate_parameter("locales", "en,pt-BR").is_err());
+        assert!(MessagesOptions::validate_parameter("message_format", "html").is_err());
+    }
+}
@@ -0,0 +1,88 @@
+use crate::errors::GeneratorError;
+use crate::project_generator::dependencies::Dependency;
+use crate::project_generator::template_engine::Context;
+use crate::project_generator::validation::invalid_parameter;
+use crate::project_generator::{ParameterDescriptor, Parameters};
+
+pub const BSTATS_VERSION: &str = "3.0.2";
+
+/// bStats metrics, started by the main class with the plugin id registered on bstats.org.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Metrics {
+    pub service_id: u32,
+}
+
+impl Metrics {
+    pub fn parameters() -> Vec<ParameterDescriptor> {
+        vec![ParameterDescriptor::new(
+            "bstats_id",
+            "bStats plugin id, leave empty for no metrics",
+            false,
+        )]
+    }
+
+    pub fn validate_parameter(name: &str, value: &str) -> Result<(), GeneratorError> {
+        match name {
+            "bstats_id" => parse_service_id(value).map(|_| ()),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn from_parameters(parameters: &Parameters) -> Result<Option<Self>, GeneratorError> {
+        match parameters.get("bstats_id") {
+            Some(value) => Ok(parse_service_id(value)?.map(|service_id| Self { service_id })),
+            None => Ok(None),
+        }
+    }
+
+    /// bStats refuses to start unless it is relocated.
+    pub fn dependencies(&self) -> Vec<Dependency> {
+        vec![Dependency::shaded(
+            "org.bstats",
+            "bstats-bukkit",
+            BSTATS_VERSION,
+        )]
+    }
+
+    pub fn extend_context(&self, context: &mut Context) {
+        context.insert("bstats_id", self.service_id.to_string());
+    }
+}
+
+fn parse_service_id(value: &str) -> Result<Option<u32>, GeneratorError> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    match value.parse::<u32>() {
+        Ok(service_id) if service_id > 0 => Ok(Some(service_id)),
+        _ => Err(invalid_parameter(
+            "bstats_id",
+            value,
+            "it must be the positive plugin id shown on bstats.org",
+            value.chars().filter(char::is_ascii_digit).collect(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_parameters_should_read_plugin_id() {
+        // GIVEN bStats ids
+        let parameters =
+            |value: &str| Parameters::from([(String::from("bstats_id"), value.to_string())]);
+
+        // WHEN we read them
+        // THEN only positive numbers should enable metrics
+        assert_eq!(
+            Some(Metrics { service_id: 1234 }),
+            Metrics::from_parameters(&parameters("1234")).unwrap()
+        );
+        assert_eq!(None, Metrics::from_parameters(&parameters("")).unwrap());
+        assert!(Metrics::from_parameters(&parameters("0")).is_err());
+        assert!(Metrics::from_parameters(&parameters("#12")).is_err());
+    }
+}
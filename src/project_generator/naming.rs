@@ -0,0 +1,315 @@
+use crate::errors::GeneratorError;
+use crate::project_generator::validation::{
+    validate_artifact_id, validate_java_identifier, validate_plugin_name,
+};
+use crate::project_generator::Parameters;
+
+/// Every name derived from the one given by the user, each of them can be overridden.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectNames {
+    pub display_name: String,
+    pub artifact_id: String,
+    pub main_class: String,
+    pub plugin_name: String,
+    pub mod_id: String,
+}
+
+impl ProjectNames {
+    pub fn from_name(name: &str) -> Self {
+        let main_class = derived_class_name(name);
+        Self {
+            display_name: name.trim().to_string(),
+            artifact_id: derived_artifact_id(name),
+            plugin_name: main_class.clone(),
+            main_class,
+            mod_id: derived_mod_id(name),
+        }
+    }
+
+    pub fn from_parameters(parameters: &Parameters) -> Result<Self, GeneratorError> {
+        let name = parameters
+            .get("name")
+            .ok_or_else(|| GeneratorError::MissingParameter(String::from("name")))?;
+        let mut names = Self::from_name(name);
+        let overrides = [
+            ("display_name", &mut names.display_name),
+            ("artifact_id", &mut names.artifact_id),
+            ("main_class", &mut names.main_class),
+            ("plugin_name", &mut names.plugin_name),
+            ("mod_id", &mut names.mod_id),
+        ];
+        for (parameter, field) in overrides {
+            if let Some(value) = parameters.get(parameter).filter(|v| !v.trim().is_empty()) {
+                *field = value.trim().to_string();
+            }
+        }
+        Ok(names)
+    }
+
+    pub fn validate(&self) -> Result<(), GeneratorError> {
+        validate_artifact_id(&self.artifact_id)?;
+        validate_java_identifier("main_class", &self.main_class)?;
+        validate_plugin_name(&self.plugin_name)
+    }
+
+    /// The folder the project is generated in.
+    pub fn folder(&self) -> String {
+        self.main_class.to_lowercase()
+    }
+}
+
+/// The name used when nothing of the given one is left in ASCII.
+const FALLBACK_NAME: &str = "plugin";
+
+/// A Java class name for the value, never empty nor starting with a digit.
+pub fn derived_class_name(value: &str) -> String {
+    let mut class_name = pascal_case(value);
+    if class_name.is_empty() {
+        class_name = pascal_case(FALLBACK_NAME);
+    } else if class_name.starts_with(|c: char| c.is_ascii_digit()) {
+        class_name.insert(0, '_');
+    }
+    class_name
+}
+
+pub fn derived_artifact_id(value: &str) -> String {
+    match kebab_case(value) {
+        artifact_id if artifact_id.is_empty() => FALLBACK_NAME.to_string(),
+        artifact_id => artifact_id,
+    }
+}
+
+/// A mod id of 2 to 64 characters starting with a lowercase letter.
+pub fn derived_mod_id(value: &str) -> String {
+    let mut mod_id = snake_case(value);
+    if mod_id.len() < 2 || !mod_id.starts_with(|c: char| c.is_ascii_lowercase()) {
+        mod_id.insert_str(0, "mod_");
+    }
+    mod_id.truncate(64);
+    mod_id.trim_end_matches('_').to_string()
+}
+
+/// Words of ASCII letters and digits, accented Latin letters losing their accent and other
+/// characters being dropped.
+pub fn split_words(value: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    for part in ascii_fold(value).split(|c: char| !c.is_ascii_alphanumeric()) {
+        let mut word = String::new();
+        let mut previous: Option<char> = None;
+        for c in part.chars() {
+            if c.is_uppercase() && previous.is_some_and(|p| p.is_lowercase() || p.is_numeric()) {
+                words.push(std::mem::take(&mut word));
+            }
+            word.push(c);
+            previous = Some(c);
+        }
+        if !word.is_empty() {
+            words.push(word);
+        }
+    }
+    words
+}
+
+fn ascii_fold(value: &str) -> String {
+    let mut folded = String::with_capacity(value.len());
+    for c in value.chars() {
+        let replacement = match c {
+            'À'..='Å' | 'Ā' | 'Ă' | 'Ą' => "A",
+            'à'..='å' | 'ā' | 'ă' | 'ą' => "a",
+            'Æ' => "Ae",
+            'æ' => "ae",
+            'Ç' | 'Ć' | 'Č' => "C",
+            'ç' | 'ć' | 'č' => "c",
+            'Ð' | 'Ď' | 'Đ' => "D",
+            'ð' | 'ď' | 'đ' => "d",
+            'È'..='Ë' | 'Ē' | 'Ė' | 'Ę' | 'Ě' => "E",
+            'è'..='ë' | 'ē' | 'ė' | 'ę' | 'ě' => "e",
+            'Ğ' => "G",
+            'ğ' => "g",
+            'Ì'..='Ï' | 'Ī' | 'İ' => "I",
+            'ì'..='ï' | 'ī' | 'ı' => "i",
+            'Ł' | 'Ľ' => "L",
+            'ł' | 'ľ' => "l",
+            'Ñ' | 'Ń' | 'Ň' => "N",
+            'ñ' | 'ń' | 'ň' => "n",
+            'Ò'..='Ö' | 'Ø' | 'Ő' => "O",
+            'ò'..='ö' | 'ø' | 'ő' => "o",
+            'Œ' => "Oe",
+            'œ' => "oe",
+            'Ř' => "R",
+            'ř' => "r",
+            'Ś' | 'Ş' | 'Š' => "S",
+            'ś' | 'ş' | 'š' => "s",
+            'ß' => "ss",
+            'Ť' => "T",
+            'ť' => "t",
+            'Þ' => "Th",
+            'þ' => "th",
+            'Ù'..='Ü' | 'Ū' | 'Ů' | 'Ű' => "U",
+            'ù'..='ü' | 'ū' | 'ů' | 'ű' => "u",
+            'Ý' | 'Ÿ' => "Y",
+            'ý' | 'ÿ' => "y",
+            'Ź' | 'Ż' | 'Ž' => "Z",
+            'ź' | 'ż' | 'ž' => "z",
+            c if c.is_ascii() => {
+                folded.push(c);
+                continue;
+            }
+            _ => " ",
+        };
+        folded.push_str(replacement);
+    }
+    folded
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+fn join_lowercase(value: &str, separator: &str) -> String {
+    split_words(value)
+        .iter()
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+pub fn pascal_case(value: &str) -> String {
+    split_words(value)
+        .iter()
+        .map(|word| capitalize(word))
+        .collect()
+}
+
+pub fn camel_case(value: &str) -> String {
+    let pascal = pascal_case(value);
+    let mut chars = pascal.chars();
+    chars
+        .next()
+        .map(|c| c.to_lowercase().collect::<String>() + chars.as_str())
+        .unwrap_or_default()
+}
+
+pub fn snake_case(value: &str) -> String {
+    join_lowercase(value, "_")
+}
+
+pub fn kebab_case(value: &str) -> String {
+    join_lowercase(value, "-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project_generator::validation::validate_mod_id;
+
+    #[test]
+    fn pascal_case_should_parse_into_pascal_case() {
+        // GIVEN a name
+        let name = "ezezz-ezfze_zefze=ff:pofkj";
+
+        // WHEN we parsed the name
+        let parsed_name = pascal_case(name);
+
+        // THEN the name should look like so
+        assert_eq!("EzezzEzfzeZefzeFfPofkj".to_string(), parsed_name)
+    }
+
+    #[test]
+    fn from_name_should_derive_every_name() {
+        // GIVEN a human name
+        let name = " My Cool Plugin ";
+
+        // WHEN we derive the project names
+        let names = ProjectNames::from_name(name);
+
+        // THEN each name should follow its convention
+        assert_eq!(
+            ProjectNames {
+                display_name: String::from("My Cool Plugin"),
+                artifact_id: String::from("my-cool-plugin"),
+                main_class: String::from("MyCoolPlugin"),
+                plugin_name: String::from("MyCoolPlugin"),
+                mod_id: String::from("my_cool_plugin"),
+            },
+            names
+        );
+        assert_eq!("mycoolplugin", names.folder());
+    }
+
+    #[test]
+    fn from_name_should_keep_main_class_a_valid_identifier() {
+        // GIVEN a name starting with a digit
+        let name = "1st plugin";
+
+        // WHEN we derive the project names
+        let names = ProjectNames::from_name(name);
+
+        // THEN the derived names should still be valid
+        assert_eq!("_1stPlugin", names.main_class);
+        assert_eq!("mod_1st_plugin", names.mod_id);
+        assert!(names.validate().is_ok());
+    }
+
+    #[test]
+    fn from_parameters_should_apply_overrides() {
+        // GIVEN parameters overriding the artifact id and the main class
+        let parameters = Parameters::from([
+            (String::from("name"), String::from("My Cool Plugin")),
+            (String::from("artifact_id"), String::from("cool-plugin")),
+            (String::from("main_class"), String::from("CoolPluginMain")),
+            (String::from("mod_id"), String::from("")),
+        ]);
+
+        // WHEN we derive the project names
+        let names = ProjectNames::from_parameters(&parameters).unwrap();
+
+        // THEN the overrides should be used and the other names derived
+        assert_eq!("cool-plugin", names.artifact_id);
+        assert_eq!("CoolPluginMain", names.main_class);
+        assert_eq!("MyCoolPlugin", names.plugin_name);
+        assert_eq!("my_cool_plugin", names.mod_id);
+    }
+
+    #[test]
+    fn from_name_should_derive_valid_ascii_names() {
+        // GIVEN a one letter name, a name with accents and a name without any Latin letter
+        // WHEN we derive the project names
+        let short = ProjectNames::from_name("X");
+        let accented = ProjectNames::from_name("Café Plugin");
+        let foreign = ProjectNames::from_name("ホーム");
+
+        // THEN every derived name should be valid ASCII
+        assert_eq!("x", short.artifact_id);
+        assert_eq!("mod_x", short.mod_id);
+        assert_eq!("cafe-plugin", accented.artifact_id);
+        assert_eq!("CafePlugin", accented.main_class);
+        assert_eq!("cafe_plugin", accented.mod_id);
+        assert_eq!("plugin", foreign.artifact_id);
+        assert_eq!("Plugin", foreign.main_class);
+        for names in [short, accented, foreign] {
+            assert!(names.validate().is_ok());
+            assert!(validate_mod_id(&names.mod_id).is_ok());
+        }
+    }
+
+    #[test]
+    fn validate_should_reject_invalid_override() {
+        // GIVEN names with a plugin name containing spaces
+        let mut names = ProjectNames::from_name("My Cool Plugin");
+        names.plugin_name = String::from("My Cool Plugin");
+
+        // WHEN we validate them
+        let result = names.validate();
+
+        // THEN the plugin name should be rejected
+        assert!(matches!(
+            result,
+            Err(GeneratorError::InvalidParameter { name, .. }) if name == "plugin_name"
+        ));
+    }
+}
@@ -0,0 +1,484 @@
+use std::collections::BTreeMap;
+use std::fs;
+
+use serde::Serialize;
+
+use crate::errors::GeneratorError;
+use crate::project_generator::content_generator::{
+    PAPER_BOOTSTRAP_JAVA_TEMPLATE, PAPER_LOADER_JAVA_TEMPLATE,
+};
+use crate::project_generator::existing_project::{ExistingProject, PLUGIN_YML_PATH, POM_PATH};
+use crate::project_generator::file_change::FileChange;
+use crate::project_generator::plugin_descriptor::{LoadOrder, PermissionSpec, PluginDescriptor};
+use crate::project_generator::spigot::SPIGOT_REPOSITORY;
+use crate::project_generator::template_engine::{render, Context, Value};
+
+pub const PAPER_REPOSITORY: &str = "https://repo.papermc.io/repository/maven-public/";
+pub const PAPER_PLUGIN_YML_PATH: &str = "src/main/resources/paper-plugin.yml";
+
+/// A Bukkit API Paper deprecates in favour of Adventure components.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeprecatedApi {
+    /// The text identifying a usage in a source line.
+    pub pattern: &'static str,
+    pub api: &'static str,
+    pub replacement: &'static str,
+}
+
+impl DeprecatedApi {
+    const fn new(pattern: &'static str, api: &'static str, replacement: &'static str) -> Self {
+        Self {
+            pattern,
+            api,
+            replacement,
+        }
+    }
+}
+
+/// Checked in order, a line only reporting its first match.
+pub const DEPRECATED_APIS: &[DeprecatedApi] = &[
+    DeprecatedApi::new(
+        "ChatColor.translateAlternateColorCodes",
+        "ChatColor.translateAlternateColorCodes",
+        "LegacyComponentSerializer.legacyAmpersand().deserialize(text), or MiniMessage",
+    ),
+    DeprecatedApi::new(
+        "ChatColor.",
+        "ChatColor",
+        "Component.text(text, NamedTextColor.RED), or MiniMessage tags",
+    ),
+    DeprecatedApi::new(
+        "Bukkit.broadcastMessage(",
+        "Bukkit.broadcastMessage(String)",
+        "Bukkit.broadcast(Component)",
+    ),
+    DeprecatedApi::new(
+        ".setDisplayName(",
+        "setDisplayName(String)",
+        "displayName(Component)",
+    ),
+    DeprecatedApi::new(".getDisplayName()", "getDisplayName()", "displayName()"),
+    DeprecatedApi::new(
+        ".setCustomName(",
+        "setCustomName(String)",
+        "customName(Component)",
+    ),
+    DeprecatedApi::new(
+        ".setPlayerListName(",
+        "setPlayerListName(String)",
+        "playerListName(Component)",
+    ),
+    DeprecatedApi::new(
+        ".setLore(",
+        "setLore(List<String>)",
+        "lore(List<Component>)",
+    ),
+    DeprecatedApi::new(".getLore()", "getLore()", "lore()"),
+    DeprecatedApi::new(".kickPlayer(", "kickPlayer(String)", "kick(Component)"),
+    DeprecatedApi::new(
+        ".setJoinMessage(",
+        "setJoinMessage(String)",
+        "joinMessage(Component)",
+    ),
+    DeprecatedApi::new(
+        ".setQuitMessage(",
+        "setQuitMessage(String)",
+        "quitMessage(Component)",
+    ),
+    DeprecatedApi::new(
+        ".setDeathMessage(",
+        "setDeathMessage(String)",
+        "deathMessage(Component)",
+    ),
+    DeprecatedApi::new(
+        ".sendTitle(",
+        "sendTitle(String, String)",
+        "showTitle(Title.title(Component, Component))",
+    ),
+    DeprecatedApi::new(
+        "AsyncPlayerChatEvent",
+        "AsyncPlayerChatEvent",
+        "io.papermc.paper.event.player.AsyncChatEvent",
+    ),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeprecatedUsage {
+    pub path: String,
+    pub line: usize,
+    pub api: &'static DeprecatedApi,
+}
+
+/// The rewritten build files and descriptor, plus the deprecated APIs left to the user.
+#[derive(Debug, Default)]
+pub struct PaperMigration {
+    pub changes: Vec<FileChange>,
+    pub usages: Vec<DeprecatedUsage>,
+}
+
+/// Moves a Spigot project to the Paper API, `paper_plugin` converting its `plugin.yml` to a `paper-plugin.yml`.
+pub fn migrate_to_paper(
+    project: &ExistingProject,
+    paper_plugin: bool,
+) -> Result<PaperMigration, GeneratorError> {
+    let mut migration = PaperMigration::default();
+    for (path, edit) in [
+        (POM_PATH, edit_pom as fn(&str) -> String),
+        ("build.gradle.kts", edit_gradle_build),
+        ("build.gradle", edit_gradle_build),
+    ] {
+        let Ok(before) = fs::read_to_string(project.root().join(path)) else {
+            continue;
+        };
+        let after = edit(&before);
+        if after != before {
+            migration
+                .changes
+                .push(FileChange::edited(path, before, after));
+        }
+    }
+    if migration.changes.is_empty() {
+        return Err(GeneratorError::InvalidProject(
+            project.root().display().to_string(),
+            String::from("no spigot-api dependency found in the pom.xml or Gradle build"),
+        ));
+    }
+    if paper_plugin {
+        convert_plugin_yml(project, &mut migration)?;
+    }
+    migration.usages = scan_sources(project)?;
+    Ok(migration)
+}
+
+fn edit_pom(content: &str) -> String {
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let mut output = String::new();
+    for (index, line) in lines.iter().enumerate() {
+        let spigot_api = line.contains("<groupId>org.spigotmc</groupId>")
+            && lines
+                .get(index + 1)
+                .is_some_and(|next| next.contains("<artifactId>spigot-api</artifactId>"));
+        let line = match spigot_api {
+            true => line.replace("org.spigotmc", "io.papermc.paper"),
+            false => line
+                .replace(
+                    "<artifactId>spigot-api</artifactId>",
+                    "<artifactId>paper-api</artifactId>",
+                )
+                .replace("<id>spigot-repo</id>", "<id>papermc</id>")
+                .replace(SPIGOT_REPOSITORY, PAPER_REPOSITORY)
+                .replace("spigot.version", "paper.version"),
+        };
+        output.push_str(&line);
+    }
+    output
+}
+
+fn edit_gradle_build(content: &str) -> String {
+    content
+        .replace("org.spigotmc:spigot-api:", "io.papermc.paper:paper-api:")
+        .replace(SPIGOT_REPOSITORY, PAPER_REPOSITORY)
+}
+
+/// The `paper-plugin.yml` keys, dependencies declaring their load order instead of `depend` lists.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct PaperPluginDescriptor {
+    name: String,
+    version: String,
+    main: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    authors: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    website: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    api_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    load: Option<LoadOrder>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prefix: Option<String>,
+    bootstrapper: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    loader: Option<String>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    dependencies: BTreeMap<String, BTreeMap<String, ServerDependency>>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    permissions: BTreeMap<String, PermissionSpec>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct ServerDependency {
+    load: &'static str,
+    required: bool,
+    join_classpath: bool,
+}
+
+fn convert_plugin_yml(
+    project: &ExistingProject,
+    migration: &mut PaperMigration,
+) -> Result<(), GeneratorError> {
+    let content = fs::read_to_string(project.root().join(PLUGIN_YML_PATH)).map_err(|error| {
+        GeneratorError::InvalidProject(project.root().display().to_string(), error.to_string())
+    })?;
+    let plugin = PluginDescriptor::from_yaml(&content)?;
+    refuse_commands(project, &plugin)?;
+
+    let mut server = BTreeMap::new();
+    for (plugins, load, required) in [
+        (&plugin.depend, "BEFORE", true),
+        (&plugin.softdepend, "BEFORE", false),
+        (&plugin.loadbefore, "AFTER", false),
+    ] {
+        for name in plugins {
+            server.insert(
+                name.clone(),
+                ServerDependency {
+                    load,
+                    required,
+                    join_classpath: load == "BEFORE",
+                },
+            );
+        }
+    }
+
+    let main_class = project.main_class();
+    let bootstrapper = format!("{}Bootstrap", main_class);
+    let loader = (!plugin.libraries.is_empty()).then(|| format!("{}Loader", main_class));
+    let qualified = |class: &str| match project.package() {
+        "" => class.to_string(),
+        package => format!("{}.{}", package, class),
+    };
+    let descriptor = PaperPluginDescriptor {
+        name: plugin.name,
+        version: plugin.version,
+        main: plugin.main,
+        description: plugin.description,
+        authors: plugin.authors,
+        website: plugin.website,
+        api_version: plugin.api_version,
+        load: plugin.load,
+        prefix: plugin.prefix,
+        bootstrapper: qualified(&bootstrapper),
+        loader: loader.as_deref().map(qualified),
+        dependencies: match server.is_empty() {
+            true => BTreeMap::new(),
+            false => BTreeMap::from([(String::from("server"), server)]),
+        },
+        permissions: plugin.permissions,
+    };
+    let yaml = serde_yaml::to_string(&descriptor)
+        .map_err(|error| GeneratorError::TemplateError(error.to_string()))?;
+
+    let mut context = Context::new();
+    context
+        .insert("package", project.package())
+        .insert("class_name", bootstrapper.as_str());
+    migration.changes.push(FileChange::created(
+        &source_path(project, &bootstrapper),
+        render(PAPER_BOOTSTRAP_JAVA_TEMPLATE, &context)?,
+    ));
+    if let Some(loader) = &loader {
+        let libraries: Vec<Value> = plugin
+            .libraries
+            .iter()
+            .map(|library| Value::from(library.as_str()))
+            .collect();
+        context
+            .insert("class_name", loader.as_str())
+            .insert("libraries", libraries);
+        migration.changes.push(FileChange::created(
+            &source_path(project, loader),
+            render(PAPER_LOADER_JAVA_TEMPLATE, &context)?,
+        ));
+    }
+    migration
+        .changes
+        .push(FileChange::created(PAPER_PLUGIN_YML_PATH, yaml));
+    migration
+        .changes
+        .push(FileChange::deleted(PLUGIN_YML_PATH, content));
+
+    Ok(())
+}
+
+/// Commands cannot be declared in a `paper-plugin.yml`, so `getCommand` would return null in `onEnable`.
+fn refuse_commands(
+    project: &ExistingProject,
+    plugin: &PluginDescriptor,
+) -> Result<(), GeneratorError> {
+    let mut problems: Vec<String> = plugin
+        .commands
+        .keys()
+        .map(|command| format!("{} declares /{}", PLUGIN_YML_PATH, command))
+        .collect();
+    for (path, content) in project.java_sources()? {
+        for (index, line) in content.lines().enumerate() {
+            if line.contains("getCommand(") && !line.trim_start().starts_with("//") {
+                problems.push(format!("{}:{} calls getCommand", path, index + 1));
+            }
+        }
+    }
+    if problems.is_empty() {
+        return Ok(());
+    }
+    Err(GeneratorError::InvalidProject(
+        project.root().display().to_string(),
+        format!(
+            "a paper-plugin.yml cannot declare commands, register them with the Brigadier API first ({})",
+            problems.join(", ")
+        ),
+    ))
+}
+
+fn source_path(project: &ExistingProject, class: &str) -> String {
+    let path = project.java_path(project.package(), class);
+    path.strip_prefix(project.root())
+        .unwrap_or(&path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// Every line of the Java sources using an API Paper deprecates.
+pub fn scan_sources(project: &ExistingProject) -> Result<Vec<DeprecatedUsage>, GeneratorError> {
+    let mut usages = Vec::new();
+    for (path, content) in project.java_sources()? {
+        for (index, line) in content.lines().enumerate() {
+            let code = line.trim_start();
+            if code.starts_with("import ") || code.starts_with("//") || code.starts_with('*') {
+                continue;
+            }
+            if let Some(api) = DEPRECATED_APIS
+                .iter()
+                .find(|api| line.contains(api.pattern))
+            {
+                usages.push(DeprecatedUsage {
+                    path: path.clone(),
+                    line: index + 1,
+                    api,
+                });
+            }
+        }
+    }
+    Ok(usages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project_generator::spigot::SpigotGenerator;
+    use crate::project_generator::{Parameters, ProjectGenerator};
+    use std::path::Path;
+
+    #[test]
+    fn migrate_to_paper_should_swap_api_and_convert_plugin_yml() {
+        // GIVEN a generated Spigot project with a dependency and a library
+        let parameters = Parameters::from([
+            (String::from("name"), String::from("PaperMigration")),
+            (String::from("version"), String::from("1.21")),
+            (String::from("group_id"), String::from("com.test")),
+            (String::from("depend"), String::from("Vault")),
+            (
+                String::from("libraries"),
+                String::from("com.google.code.gson:gson:2.11.0"),
+            ),
+        ]);
+        SpigotGenerator.generate(&parameters).unwrap();
+        let root = Path::new("papermigration");
+        let project = ExistingProject::open(root).unwrap();
+
+        // WHEN we migrate it to a Paper plugin
+        let migration = migrate_to_paper(&project, true).unwrap();
+
+        // THEN the pom should depend on the Paper API
+        let after = |path: &str| {
+            migration
+                .changes
+                .iter()
+                .find(|change| change.path == path)
+                .and_then(|change| change.after.clone())
+        };
+        let pom = after(POM_PATH).unwrap();
+        assert!(pom.contains("<id>papermc</id>\n            <url>https://repo.papermc.io/repository/maven-public/</url>"));
+        assert!(pom.contains("<groupId>io.papermc.paper</groupId>\n            <artifactId>paper-api</artifactId>\n            <version>${paper.version}</version>"));
+        assert!(pom.contains("<paper.version>1.21-R0.1-SNAPSHOT</paper.version>"));
+        assert!(!pom.contains("spigot"));
+
+        // AND the plugin.yml should be replaced by a paper-plugin.yml with a bootstrapper and a loader
+        let paper_plugin_yml = after(PAPER_PLUGIN_YML_PATH).unwrap();
+        assert!(paper_plugin_yml.contains("bootstrapper: com.test.PaperMigrationBootstrap\nloader: com.test.PaperMigrationLoader\ndependencies:\n  server:\n    Vault:\n      load: BEFORE\n      required: true\n      join-classpath: true\n"));
+        assert!(!paper_plugin_yml.contains("commands"));
+        assert_eq!(None, after(PLUGIN_YML_PATH));
+        assert!(after("src/main/java/com/test/PaperMigrationLoader.java")
+            .unwrap()
+            .contains("new DefaultArtifact(\"com.google.code.gson:gson:2.11.0\")"));
+        assert!(after("src/main/java/com/test/PaperMigrationBootstrap.java").is_some());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn migrate_to_paper_should_refuse_paper_plugin_with_commands() {
+        // GIVEN a generated Spigot project with a command registered in onEnable
+        let parameters = Parameters::from([
+            (String::from("name"), String::from("PaperCommands")),
+            (String::from("version"), String::from("1.21")),
+            (String::from("group_id"), String::from("com.test")),
+            (String::from("commands"), String::from("home")),
+        ]);
+        SpigotGenerator.generate(&parameters).unwrap();
+        let root = Path::new("papercommands");
+        let main_path = root.join("src/main/java/com/test/PaperCommands.java");
+        let main = fs::read_to_string(&main_path).unwrap().replace(
+            "getLogger().info(\"Hello, SpigotMC!\");",
+            "getCommand(\"home\").setExecutor(this);",
+        );
+        fs::write(&main_path, main).unwrap();
+        let project = ExistingProject::open(root).unwrap();
+
+        // WHEN we convert it to a Paper plugin
+        let result = migrate_to_paper(&project, true);
+
+        // THEN it should be refused, listing the command and the getCommand call
+        let message = result.unwrap_err().to_string();
+        assert!(message.contains("src/main/resources/plugin.yml declares /home"));
+        assert!(message.contains("src/main/java/com/test/PaperCommands.java:9 calls getCommand"));
+
+        // AND only swapping the API should still be possible
+        assert!(migrate_to_paper(&project, false).is_ok());
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn scan_sources_should_report_legacy_messages() {
+        // GIVEN a project using legacy colour codes
+        let parameters = Parameters::from([
+            (String::from("name"), String::from("PaperScan")),
+            (String::from("version"), String::from("1.21")),
+            (String::from("group_id"), String::from("com.test")),
+        ]);
+        SpigotGenerator.generate(&parameters).unwrap();
+        let root = Path::new("paperscan");
+        let main_path = root.join("src/main/java/com/test/PaperScan.java");
+        let main = fs::read_to_string(&main_path).unwrap().replace(
+            "getLogger().info(\"Hello, SpigotMC!\");",
+            "// ChatColor.RED in a comment\n        Bukkit.broadcastMessage(ChatColor.GREEN + \"Hello\");",
+        );
+        fs::write(&main_path, main).unwrap();
+        let project = ExistingProject::open(root).unwrap();
+
+        // WHEN we scan its sources
+        let usages = scan_sources(&project).unwrap();
+
+        // THEN the code line should be reported once, with its Adventure replacement
+        assert_eq!(1, usages.len());
+        assert_eq!("src/main/java/com/test/PaperScan.java", usages[0].path);
+        assert_eq!(10, usages[0].line);
+        assert_eq!("ChatColor", usages[0].api.api);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,601 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::errors::GeneratorError;
+use crate::project_generator::naming::ProjectNames;
+use crate::project_generator::project_info::ProjectInfo;
+use crate::project_generator::validation::{
+    invalid_parameter, validate_bukkit_plugin_name, validate_command_name, validate_permission_name,
+};
+use crate::project_generator::{ParameterDescriptor, Parameters};
+
+/// The content of a Bukkit `plugin.yml`, in the order the keys are written.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct PluginDescriptor {
+    pub name: String,
+    pub version: String,
+    pub main: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub website: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub load: Option<LoadOrder>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depend: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub softdepend: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub loadbefore: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub libraries: Vec<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub commands: BTreeMap<String, CommandSpec>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub permissions: BTreeMap<String, PermissionSpec>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum LoadOrder {
+    #[serde(rename = "STARTUP")]
+    Startup,
+    #[serde(rename = "POSTWORLD")]
+    PostWorld,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CommandSpec {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permission: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub permission_message: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PermissionSpec {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<PermissionDefault>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub children: BTreeMap<String, PermissionChild>,
+}
+
+/// A child is either granted or revoked with its parent, or declared inline.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PermissionChild {
+    Inherit(bool),
+    Nested(PermissionSpec),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PermissionDefault {
+    True,
+    False,
+    Op,
+    NotOp,
+}
+
+impl Serialize for PermissionDefault {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            PermissionDefault::True => serializer.serialize_bool(true),
+            PermissionDefault::False => serializer.serialize_bool(false),
+            PermissionDefault::Op => serializer.serialize_str("op"),
+            PermissionDefault::NotOp => serializer.serialize_str("not op"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for PermissionDefault {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Bool(bool),
+            String(String),
+        }
+
+        match Raw::deserialize(deserializer)? {
+            Raw::Bool(true) => Ok(PermissionDefault::True),
+            Raw::Bool(false) => Ok(PermissionDefault::False),
+            Raw::String(value) => match value.to_lowercase().replace('_', " ").as_str() {
+                "true" => Ok(PermissionDefault::True),
+                "false" => Ok(PermissionDefault::False),
+                "op" | "isop" | "operator" => Ok(PermissionDefault::Op),
+                "not op" | "notop" | "!op" | "not operator" => Ok(PermissionDefault::NotOp),
+                _ => Err(serde::de::Error::custom(format!(
+                    "unknown permission default '{}'",
+                    value
+                ))),
+            },
+        }
+    }
+}
+
+/// The plugin sections a project spec file can declare, flags taking precedence.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct PluginSpec {
+    api_version: Option<String>,
+    load: Option<String>,
+    prefix: Option<String>,
+    depend: Option<Vec<String>>,
+    softdepend: Option<Vec<String>>,
+    loadbefore: Option<Vec<String>>,
+    libraries: Option<Vec<String>>,
+    #[serde(default)]
+    commands: BTreeMap<String, CommandSpec>,
+    #[serde(default)]
+    permissions: BTreeMap<String, PermissionSpec>,
+}
+
+impl PluginDescriptor {
+    pub fn new(names: &ProjectNames, info: &ProjectInfo, group_id: &str) -> Self {
+        Self::default().with_project(names, info, group_id)
+    }
+
+    /// Fills the keys coming from the project names and metadata.
+    pub fn with_project(
+        mut self,
+        names: &ProjectNames,
+        info: &ProjectInfo,
+        group_id: &str,
+    ) -> Self {
+        self.name = names.plugin_name.clone();
+        self.version = info.project_version.clone();
+        self.main = format!("{}.{}", group_id, names.main_class);
+        self.description = Some(info.description.clone()).filter(|value| !value.is_empty());
+        self.authors = info.authors.clone();
+        self.website = info.website.clone();
+        self
+    }
+
+    /// The optional parameters filling the `plugin.yml` beyond the project metadata.
+    pub fn parameters() -> Vec<ParameterDescriptor> {
+        vec![
+            ParameterDescriptor::new("api_version", "Bukkit api-version, e.g. 1.21", false),
+            ParameterDescriptor::new("load", "When the plugin loads: STARTUP or POSTWORLD", false),
+            ParameterDescriptor::new("prefix", "Logger prefix", false),
+            ParameterDescriptor::new("depend", "Comma separated hard dependencies", false),
+            ParameterDescriptor::new("softdepend", "Comma separated soft dependencies", false),
+            ParameterDescriptor::new(
+                "loadbefore",
+                "Comma separated plugins to load before",
+                false,
+            ),
+            ParameterDescriptor::new("libraries", "Comma separated maven coordinates", false),
+            ParameterDescriptor::new("commands", "Comma separated command names", false),
+            ParameterDescriptor::new(
+                "spec",
+                "Project spec file declaring commands and permissions",
+                false,
+            ),
+        ]
+    }
+
+    pub fn validate_parameter(name: &str, value: &str) -> Result<(), GeneratorError> {
+        match name {
+            "load" if !value.is_empty() => parse_load_order(value).map(|_| ()),
+            "api_version" if !value.is_empty() => validate_api_version(value),
+            "depend" | "softdepend" | "loadbefore" => split_list(value)
+                .iter()
+                .try_for_each(|plugin| validate_bukkit_plugin_name(name, plugin)),
+            "commands" => split_list(value)
+                .iter()
+                .try_for_each(|command| validate_command_name(command)),
+            "libraries" => split_list(value)
+                .iter()
+                .try_for_each(|library| validate_library(library)),
+            "spec" if !value.is_empty() && !Path::new(value).is_file() => {
+                Err(GeneratorError::InvalidConfiguration(
+                    value.to_string(),
+                    String::from("the spec file does not exist"),
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Builds the descriptor from the project and the plugin parameters, the spec file included.
+    pub fn from_parameters(
+        names: &ProjectNames,
+        info: &ProjectInfo,
+        group_id: &str,
+        minecraft_version: &str,
+        parameters: &Parameters,
+    ) -> Result<Self, GeneratorError> {
+        let optional = |name: &str| {
+            parameters
+                .get(name)
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        let list = |name: &str, spec: Option<Vec<String>>| -> Result<Vec<String>, GeneratorError> {
+            let values = match optional(name) {
+                Some(value) => split_list(&value),
+                None => spec.unwrap_or_default(),
+            };
+            for value in &values {
+                Self::validate_parameter(name, value)?;
+            }
+            Ok(values)
+        };
+        let spec = match optional("spec") {
+            Some(path) => read_spec(Path::new(&path))?,
+            None => PluginSpec::default(),
+        };
+
+        let mut descriptor = Self {
+            api_version: optional("api_version")
+                .or(spec.api_version)
+                .or_else(|| default_api_version(minecraft_version)),
+            load: optional("load")
+                .or(spec.load)
+                .map(|value| parse_load_order(&value))
+                .transpose()?,
+            prefix: optional("prefix").or(spec.prefix),
+            depend: list("depend", spec.depend)?,
+            softdepend: list("softdepend", spec.softdepend)?,
+            loadbefore: list("loadbefore", spec.loadbefore)?,
+            libraries: list("libraries", spec.libraries)?,
+            commands: spec.commands,
+            permissions: spec.permissions,
+            ..Self::new(names, info, group_id)
+        };
+        if let Some(api_version) = &descriptor.api_version {
+            validate_api_version(api_version)?;
+        }
+        for (command, spec) in &descriptor.commands {
+            validate_command_name(command)?;
+            spec.aliases
+                .iter()
+                .try_for_each(|alias| validate_command_name(alias))?;
+            if let Some(permission) = &spec.permission {
+                validate_permission_name(permission)?;
+            }
+        }
+        descriptor
+            .permissions
+            .iter()
+            .try_for_each(|(name, spec)| validate_permission(name, spec))?;
+        for command in split_list(&optional("commands").unwrap_or_default()) {
+            validate_command_name(&command)?;
+            if descriptor.commands.contains_key(&command) {
+                continue;
+            }
+            let (permission, permission_spec) = command_permission(&names.plugin_name, &command);
+            descriptor
+                .permissions
+                .entry(permission.clone())
+                .or_insert(permission_spec);
+            descriptor.commands.insert(
+                command,
+                CommandSpec {
+                    usage: Some(String::from("/<command>")),
+                    permission: Some(permission),
+                    ..CommandSpec::default()
+                },
+            );
+        }
+        Ok(descriptor)
+    }
+
+    pub fn to_yaml(&self) -> Result<String, GeneratorError> {
+        serde_yaml::to_string(self)
+            .map_err(|error| GeneratorError::TemplateError(error.to_string()))
+    }
+
+    pub fn from_yaml(content: &str) -> Result<Self, GeneratorError> {
+        serde_yaml::from_str(content).map_err(|error| {
+            GeneratorError::InvalidConfiguration(String::from("plugin.yml"), error.to_string())
+        })
+    }
+}
+
+/// The permission a generated command requires, declared for operators as Bukkit does by default.
+pub fn command_permission(plugin_name: &str, command: &str) -> (String, PermissionSpec) {
+    let permission = format!("{}.command.{}", plugin_name.to_lowercase(), command);
+    let spec = PermissionSpec {
+        description: Some(format!("Allows to use /{}", command)),
+        default: Some(PermissionDefault::Op),
+        ..PermissionSpec::default()
+    };
+    (permission, spec)
+}
+
+fn validate_permission(name: &str, spec: &PermissionSpec) -> Result<(), GeneratorError> {
+    validate_permission_name(name)?;
+    spec.children
+        .iter()
+        .try_for_each(|(child, value)| match value {
+            PermissionChild::Nested(nested) => validate_permission(child, nested),
+            PermissionChild::Inherit(_) => validate_permission_name(child),
+        })
+}
+
+fn read_spec(path: &Path) -> Result<PluginSpec, GeneratorError> {
+    let invalid =
+        |message: String| GeneratorError::InvalidConfiguration(path.display().to_string(), message);
+    let content = fs::read_to_string(path).map_err(|error| invalid(error.to_string()))?;
+    toml::from_str(&content).map_err(|error| invalid(error.to_string()))
+}
+
+pub fn split_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+fn parse_load_order(value: &str) -> Result<LoadOrder, GeneratorError> {
+    match value.to_uppercase().as_str() {
+        "STARTUP" => Ok(LoadOrder::Startup),
+        "POSTWORLD" => Ok(LoadOrder::PostWorld),
+        _ => Err(invalid_parameter(
+            "load",
+            value,
+            "it must be STARTUP or POSTWORLD",
+            String::from("POSTWORLD"),
+        )),
+    }
+}
+
+pub fn validate_api_version(value: &str) -> Result<(), GeneratorError> {
+    let minor = value
+        .strip_prefix("1.")
+        .and_then(|rest| rest.split('.').next())
+        .and_then(|minor| minor.parse::<u32>().ok());
+    match minor {
+        Some(minor) if minor >= 13 => Ok(()),
+        _ => Err(invalid_parameter(
+            "api_version",
+            value,
+            "it must be a Minecraft version from 1.13 onwards",
+            String::from("1.13"),
+        )),
+    }
+}
+
+fn validate_library(value: &str) -> Result<(), GeneratorError> {
+    let parts: Vec<&str> = value.split(':').collect();
+    if (3..=4).contains(&parts.len()) && parts.iter().all(|part| !part.is_empty()) {
+        Ok(())
+    } else {
+        Err(invalid_parameter(
+            "libraries",
+            value,
+            "it must be maven coordinates like group:artifact:version",
+            String::from("com.example:library:1.0.0"),
+        ))
+    }
+}
+
+/// Plugins built against 1.13 or later declare the api-version, older ones must not.
+pub fn default_api_version(minecraft_version: &str) -> Option<String> {
+    let mut parts = minecraft_version.split(['.', '-']);
+    let major = parts.next()?;
+    let minor = parts.next()?.parse::<u32>().ok()?;
+    (major == "1" && minor >= 13).then(|| format!("1.{}", minor))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn project() -> (ProjectNames, ProjectInfo) {
+        (ProjectNames::from_name("Homes"), ProjectInfo::default())
+    }
+
+    #[test]
+    fn to_yaml_should_only_write_set_keys() {
+        // GIVEN a descriptor with only the mandatory keys
+        let (names, info) = project();
+        let descriptor = PluginDescriptor::new(&names, &info, "com.test");
+
+        // WHEN we serialize it
+        let yaml = descriptor.to_yaml().unwrap();
+
+        // THEN only these keys should be written
+        assert_eq!("name: Homes\nversion: 1.0.0\nmain: com.test.Homes\n", yaml);
+    }
+
+    #[test]
+    fn from_parameters_should_read_flags() {
+        // GIVEN plugin parameters given as flags
+        let (names, info) = project();
+        let parameters = Parameters::from([
+            (String::from("load"), String::from("startup")),
+            (String::from("depend"), String::from("Vault, WorldGuard")),
+            (String::from("prefix"), String::from("[Homes]")),
+            (String::from("commands"), String::from("home,sethome")),
+        ]);
+
+        // WHEN we build the descriptor
+        let descriptor =
+            PluginDescriptor::from_parameters(&names, &info, "com.test", "1.21.4", &parameters)
+                .unwrap();
+
+        // THEN the yaml should contain every section
+        let yaml = descriptor.to_yaml().unwrap();
+        assert_eq!(
+            r#"name: Homes
+version: 1.0.0
+main: com.test.Homes
+api-version: '1.21'
+load: STARTUP
+prefix: '[Homes]'
+depend:
+- Vault
+- WorldGuard
+commands:
+  home:
+    usage: /<command>
+    permission: homes.command.home
+  sethome:
+    usage: /<command>
+    permission: homes.command.sethome
+permissions:
+  homes.command.home:
+    description: Allows to use /home
+    default: op
+  homes.command.sethome:
+    description: Allows to use /sethome
+    default: op
+"#,
+            yaml
+        );
+    }
+
+    #[test]
+    fn from_parameters_should_read_spec_file() {
+        // GIVEN a spec file with commands and a permission tree
+        let spec = env::temp_dir().join("mcdev-plugin-descriptor-spec.toml");
+        fs::write(
+            &spec,
+            r#"
+softdepend = ["PlaceholderAPI"]
+
+[commands.home]
+description = "Teleport to your home"
+usage = "/<command> [name]"
+aliases = ["h"]
+permission = "homes.home"
+
+[permissions."homes.*"]
+description = "Every homes permission"
+default = "op"
+children = { "homes.home" = true, "homes.admin" = { default = false, description = "Manage homes" } }
+
+[permissions."homes.home"]
+default = true
+"#,
+        )
+        .unwrap();
+        let (names, info) = project();
+        let parameters =
+            Parameters::from([(String::from("spec"), spec.to_str().unwrap().to_string())]);
+
+        // WHEN we build the descriptor
+        let descriptor =
+            PluginDescriptor::from_parameters(&names, &info, "com.test", "1.8.8", &parameters)
+                .unwrap();
+
+        // THEN the yaml should be produced by the serializer
+        let yaml = descriptor.to_yaml().unwrap();
+        assert_eq!(
+            r#"name: Homes
+version: 1.0.0
+main: com.test.Homes
+softdepend:
+- PlaceholderAPI
+commands:
+  home:
+    description: Teleport to your home
+    usage: /<command> [name]
+    aliases:
+    - h
+    permission: homes.home
+permissions:
+  homes.*:
+    description: Every homes permission
+    default: op
+    children:
+      homes.admin:
+        description: Manage homes
+        default: false
+      homes.home: true
+  homes.home:
+    default: true
+"#,
+            yaml
+        );
+
+        // AND it should be read back identically
+        assert_eq!(descriptor, PluginDescriptor::from_yaml(&yaml).unwrap());
+    }
+
+    #[test]
+    fn from_parameters_should_reject_invalid_spec_names() {
+        // GIVEN spec files declaring a command, an alias or permissions Bukkit cannot read
+        let (names, info) = project();
+        let specs = [
+            "[commands.\"my:home\"]",
+            "[commands.home]\naliases = [\"go home\"]",
+            "[commands.home]\npermission = \"homes home\"",
+            "[permissions.\"homes:home\"]",
+            "[permissions.\"homes.*\"]\nchildren = { \"homes..home\" = true }",
+            "[permissions.\"homes.*\"]\nchildren = { \"homes.admin\" = { children = { \"homes admin\" = true } } }",
+        ];
+
+        for (index, content) in specs.iter().enumerate() {
+            let spec = env::temp_dir().join(format!("mcdev-invalid-spec-{}.toml", index));
+            fs::write(&spec, content).unwrap();
+            let parameters =
+                Parameters::from([(String::from("spec"), spec.to_str().unwrap().to_string())]);
+
+            // WHEN we build the descriptor
+            let result =
+                PluginDescriptor::from_parameters(&names, &info, "com.test", "1.21.4", &parameters);
+            fs::remove_file(&spec).unwrap();
+
+            // THEN it should be refused
+            assert!(
+                matches!(result, Err(GeneratorError::InvalidParameter { .. })),
+                "{} should be refused, got {:?}",
+                content,
+                result
+            );
+        }
+    }
+
+    #[test]
+    fn validate_parameter_should_reject_malformed_values() {
+        // GIVEN malformed plugin parameters
+        // WHEN we validate them
+        // THEN they should be rejected
+        assert!(PluginDescriptor::validate_parameter("load", "LATER").is_err());
+        assert!(PluginDescriptor::validate_parameter("api_version", "1.12").is_err());
+        assert!(PluginDescriptor::validate_parameter("api_version", "1.20.6").is_ok());
+        assert!(PluginDescriptor::validate_parameter("depend", "Vault, World Guard").is_ok());
+        assert!(PluginDescriptor::validate_parameter("softdepend", "World/Guard").is_err());
+        assert!(PluginDescriptor::validate_parameter("commands", "home, my:cmd").is_err());
+        assert!(PluginDescriptor::validate_parameter("commands", "home, set-home").is_ok());
+        assert!(PluginDescriptor::validate_parameter("libraries", "com.zaxxer:HikariCP").is_err());
+        assert!(
+            PluginDescriptor::validate_parameter("libraries", "com.zaxxer:HikariCP:5.1.0").is_ok()
+        );
+        assert!(PluginDescriptor::validate_parameter("spec", "missing-spec.toml").is_err());
+    }
+
+    #[test]
+    fn default_api_version_should_follow_minecraft_version() {
+        // GIVEN minecraft versions
+        // WHEN we derive the api version
+        // THEN only 1.13 and later should have one
+        assert_eq!(Some(String::from("1.21")), default_api_version("1.21.4"));
+        assert_eq!(Some(String::from("1.13")), default_api_version("1.13"));
+        assert_eq!(None, default_api_version("1.8.8"));
+        assert_eq!(None, default_api_version("latest"));
+    }
+}
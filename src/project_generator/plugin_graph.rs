@@ -0,0 +1,589 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+use serde_yaml::{Mapping, Value};
+
+use crate::errors::GeneratorError;
+use crate::project_generator::jar_inspector::{inspect_jar, JarInspection};
+
+/// A Bukkit or Paper plugin of a plugins folder, as its descriptor declares it.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PluginNode {
+    pub jar: String,
+    pub name: String,
+    pub provides: Vec<String>,
+    pub depend: Vec<String>,
+    pub softdepend: Vec<String>,
+    pub loadbefore: Vec<String>,
+    /// Command names and their aliases.
+    pub commands: Vec<(String, Vec<String>)>,
+    /// Library packages shaded without relocation.
+    pub shaded: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct PluginGraph {
+    pub plugins: Vec<PluginNode>,
+    /// The plugin names in the order Bukkit loads them.
+    pub load_order: Vec<String>,
+    pub problems: Vec<String>,
+    pub notes: Vec<String>,
+}
+
+impl PluginGraph {
+    /// Plugins named by others but absent from the folder.
+    fn missing(&self) -> BTreeSet<&str> {
+        let names = self.known_names();
+        self.plugins
+            .iter()
+            .flat_map(|plugin| {
+                plugin
+                    .depend
+                    .iter()
+                    .chain(&plugin.softdepend)
+                    .chain(&plugin.loadbefore)
+            })
+            .filter(|name| !names.contains(name.as_str()))
+            .map(String::as_str)
+            .collect()
+    }
+
+    fn known_names(&self) -> BTreeSet<&str> {
+        self.plugins
+            .iter()
+            .flat_map(|plugin| std::iter::once(&plugin.name).chain(&plugin.provides))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Every edge, from the plugin to the plugin it names or the one providing that name.
+    fn edges(&self) -> Vec<(&str, &str, &'static str)> {
+        let providers = providers(&self.plugins);
+        let mut edges = Vec::new();
+        for plugin in &self.plugins {
+            let lists = [
+                ("depend", &plugin.depend),
+                ("softdepend", &plugin.softdepend),
+                ("loadbefore", &plugin.loadbefore),
+            ];
+            for (kind, names) in lists {
+                edges.extend(
+                    names
+                        .iter()
+                        .map(|name| (plugin.name.as_str(), provider_name(&providers, name), kind)),
+                );
+            }
+        }
+        edges
+    }
+
+    /// The graph in the Graphviz DOT language, missing dependencies in red.
+    pub fn to_dot(&self) -> String {
+        let mut lines = vec![String::from("digraph plugins {")];
+        lines.extend(
+            self.plugins
+                .iter()
+                .map(|plugin| format!("    {};", dot_id(&plugin.name))),
+        );
+        lines.extend(
+            self.missing()
+                .into_iter()
+                .map(|name| format!("    {} [color=red];", dot_id(name))),
+        );
+        lines.extend(self.edges().into_iter().map(|(from, to, kind)| {
+            let style = match kind {
+                "depend" => "",
+                "softdepend" => ", style=dashed",
+                _ => ", style=dotted",
+            };
+            format!(
+                "    {} -> {} [label=\"{}\"{}];",
+                dot_id(from),
+                dot_id(to),
+                kind,
+                style
+            )
+        }));
+        lines.push(String::from("}"));
+        lines.join("\n")
+    }
+
+    /// The graph as a Mermaid flowchart, missing dependencies in red.
+    pub fn to_mermaid(&self) -> String {
+        let missing = self.missing();
+        let names: Vec<&str> = self
+            .plugins
+            .iter()
+            .map(|plugin| plugin.name.as_str())
+            .chain(missing.iter().copied())
+            .collect();
+        let ids: BTreeMap<&str, String> = names
+            .iter()
+            .enumerate()
+            .map(|(index, name)| (*name, format!("p{}", index)))
+            .collect();
+        let mut lines = vec![String::from("graph LR")];
+        lines.extend(
+            names
+                .iter()
+                .map(|name| format!("    {}[\"{}\"]", ids[name], name.replace('"', "'"))),
+        );
+        // Edge ends are plugins of the folder or missing ones, which all have an id
+        lines.extend(self.edges().into_iter().map(|(from, to, kind)| {
+            let arrow = match kind {
+                "depend" => "-->",
+                "softdepend" => "-.->",
+                _ => "==>",
+            };
+            format!("    {} {}|{}| {}", ids[from], arrow, kind, ids[to])
+        }));
+        if !missing.is_empty() {
+            lines.push(String::from("    classDef missing stroke:#f00,color:#f00"));
+            let ids: Vec<&str> = missing.iter().map(|name| ids[name].as_str()).collect();
+            lines.push(format!("    class {} missing", ids.join(",")));
+        }
+        lines.join("\n")
+    }
+}
+
+/// The plugin providing each name of a `provides` list.
+fn providers(plugins: &[PluginNode]) -> BTreeMap<&str, &str> {
+    plugins
+        .iter()
+        .flat_map(|plugin| {
+            plugin
+                .provides
+                .iter()
+                .map(|name| (name.as_str(), plugin.name.as_str()))
+        })
+        .collect()
+}
+
+fn provider_name<'a>(providers: &BTreeMap<&str, &'a str>, name: &'a str) -> &'a str {
+    providers.get(name).copied().unwrap_or(name)
+}
+
+/// A quoted DOT identifier.
+fn dot_id(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Reads the descriptor of every jar of the plugins folder, and simulates how Bukkit loads them.
+pub fn analyze_plugins(directory: &Path) -> Result<PluginGraph, GeneratorError> {
+    let entries = fs::read_dir(directory).map_err(|error| {
+        GeneratorError::InvalidJar(directory.display().to_string(), error.to_string())
+    })?;
+    let mut jars: Vec<_> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|extension| extension == "jar"))
+        .collect();
+    jars.sort();
+
+    let mut graph = PluginGraph::default();
+    for jar in jars {
+        let file_name = jar
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        match inspect_jar(&jar) {
+            Ok(inspection) => match plugin_node(&file_name, &inspection) {
+                Some(plugin) => graph.plugins.push(plugin),
+                None => graph.notes.push(format!(
+                    "{} has no plugin.yml nor paper-plugin.yml",
+                    file_name
+                )),
+            },
+            Err(error) => graph.notes.push(error.to_string()),
+        }
+    }
+
+    remove_duplicates(&mut graph);
+    simulate_load_order(&mut graph);
+    report_command_conflicts(&mut graph);
+    report_duplicate_libraries(&mut graph);
+    Ok(graph)
+}
+
+/// Paper reads the `paper-plugin.yml` of a jar having both descriptors.
+fn plugin_node(jar: &str, inspection: &JarInspection) -> Option<PluginNode> {
+    let descriptor = ["paper-plugin.yml", "plugin.yml"].iter().find_map(|path| {
+        inspection
+            .descriptors
+            .iter()
+            .find(|descriptor| descriptor.path == *path)
+    })?;
+    let yaml: Mapping = serde_yaml::from_str(&descriptor.content).ok()?;
+    let mut plugin = PluginNode {
+        jar: jar.to_string(),
+        name: yaml.get("name").and_then(text)?,
+        provides: list(&yaml, "provides"),
+        shaded: inspection
+            .unrelocated
+            .iter()
+            .map(|(package, _)| package.clone())
+            .collect(),
+        ..PluginNode::default()
+    };
+    match descriptor.path {
+        "paper-plugin.yml" => read_paper_dependencies(&yaml, &mut plugin),
+        _ => {
+            plugin.depend = list(&yaml, "depend");
+            plugin.softdepend = list(&yaml, "softdepend");
+            plugin.loadbefore = list(&yaml, "loadbefore");
+        }
+    }
+    let commands = yaml.get("commands").and_then(Value::as_mapping);
+    for (name, command) in commands.into_iter().flatten() {
+        let Some(name) = text(name) else {
+            continue;
+        };
+        let aliases = match command.get("aliases") {
+            Some(Value::Sequence(aliases)) => aliases.iter().filter_map(text).collect(),
+            Some(alias) => text(alias).into_iter().collect(),
+            None => Vec::new(),
+        };
+        plugin.commands.push((name, aliases));
+    }
+    Some(plugin)
+}
+
+/// `dependencies.server` entries loaded `BEFORE` are dependencies, `AFTER` ones loaded before.
+fn read_paper_dependencies(yaml: &Mapping, plugin: &mut PluginNode) {
+    let server = yaml
+        .get("dependencies")
+        .and_then(|dependencies| dependencies.get("server"))
+        .and_then(Value::as_mapping);
+    for (name, dependency) in server.into_iter().flatten() {
+        let Some(name) = text(name) else {
+            continue;
+        };
+        let required = dependency
+            .get("required")
+            .and_then(Value::as_bool)
+            .unwrap_or(true);
+        match (dependency.get("load").and_then(Value::as_str), required) {
+            (Some("BEFORE"), true) => plugin.depend.push(name),
+            (Some("BEFORE"), false) => plugin.softdepend.push(name),
+            (Some("AFTER"), _) => plugin.loadbefore.push(name),
+            _ => {}
+        }
+    }
+}
+
+fn text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        _ => None,
+    }
+}
+
+fn list(yaml: &Mapping, key: &str) -> Vec<String> {
+    match yaml.get(key) {
+        Some(Value::Sequence(values)) => values.iter().filter_map(text).collect(),
+        Some(value) => text(value).into_iter().collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Bukkit refuses a plugin whose name is already taken, keeping the first jar read.
+fn remove_duplicates(graph: &mut PluginGraph) {
+    let mut seen: BTreeMap<String, String> = BTreeMap::new();
+    let mut problems = Vec::new();
+    graph.plugins.retain(|plugin| match seen.get(&plugin.name) {
+        Some(jar) => {
+            problems.push(format!(
+                "Duplicate plugin name {}: {} and {}, only one is loaded",
+                plugin.name, jar, plugin.jar
+            ));
+            false
+        }
+        None => {
+            seen.insert(plugin.name.clone(), plugin.jar.clone());
+            true
+        }
+    });
+    graph.problems.extend(problems);
+}
+
+/// Follows `SimplePluginManager.loadPlugins`: a plugin loads once its dependencies and the soft
+/// dependencies present are loaded, a soft dependency cycle being broken by loading the first
+/// plugin without hard dependencies left, and a hard one failing every plugin left.
+fn simulate_load_order(graph: &mut PluginGraph) {
+    let mut dependencies: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut soft_dependencies: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for plugin in &graph.plugins {
+        dependencies.insert(plugin.name.clone(), plugin.depend.clone());
+        soft_dependencies
+            .entry(plugin.name.clone())
+            .or_default()
+            .extend(plugin.softdepend.iter().cloned());
+        for name in &plugin.loadbefore {
+            soft_dependencies
+                .entry(name.clone())
+                .or_default()
+                .push(plugin.name.clone());
+        }
+    }
+    let provided = providers(&graph.plugins);
+
+    let mut remaining: Vec<String> = graph
+        .plugins
+        .iter()
+        .map(|plugin| plugin.name.clone())
+        .collect();
+    let mut loaded: BTreeSet<String> = BTreeSet::new();
+    let mut order = Vec::new();
+    let mut problems = Vec::new();
+    let resolve = |name: &str| provider_name(&provided, name).to_string();
+    while !remaining.is_empty() {
+        let mut progressed = false;
+        let mut index = 0;
+        while index < remaining.len() {
+            let plugin = remaining[index].clone();
+            let hard = dependencies.entry(plugin.clone()).or_default();
+            hard.retain(|name| !loaded.contains(&resolve(name)));
+            if let Some(unknown) = hard.iter().find(|name| !remaining.contains(&resolve(name))) {
+                problems.push(format!(
+                    "{} cannot load: its dependency {} is missing",
+                    plugin, unknown
+                ));
+                remaining.remove(index);
+                progressed = true;
+                continue;
+            }
+            let soft = soft_dependencies.entry(plugin.clone()).or_default();
+            soft.retain(|name| remaining.contains(&resolve(name)));
+            if hard.is_empty() && soft.is_empty() {
+                loaded.insert(plugin.clone());
+                order.push(remaining.remove(index));
+                progressed = true;
+                continue;
+            }
+            index += 1;
+        }
+        if progressed {
+            continue;
+        }
+        let next = remaining.iter().position(|plugin| {
+            dependencies
+                .get(plugin)
+                .is_none_or(|dependencies| dependencies.is_empty())
+        });
+        match next {
+            Some(index) => {
+                let plugin = remaining.remove(index);
+                graph.notes.push(format!(
+                    "Soft dependency cycle broken by loading {} first",
+                    plugin
+                ));
+                loaded.insert(plugin.clone());
+                order.push(plugin);
+            }
+            None => {
+                problems.push(format!(
+                    "Dependency cycle: {} cannot load",
+                    remaining.join(", ")
+                ));
+                remaining.clear();
+            }
+        }
+    }
+    graph.load_order = order;
+    graph.problems.extend(problems);
+}
+
+/// Bukkit gives a command label to the first plugin loaded, the others only keeping `plugin:label`.
+fn report_command_conflicts(graph: &mut PluginGraph) {
+    let mut labels: BTreeMap<String, Vec<&str>> = BTreeMap::new();
+    for name in &graph.load_order {
+        let Some(plugin) = graph.plugins.iter().find(|plugin| &plugin.name == name) else {
+            continue;
+        };
+        let plugin_labels: BTreeSet<String> = plugin
+            .commands
+            .iter()
+            .flat_map(|(command, aliases)| std::iter::once(command).chain(aliases))
+            .map(|label| label.to_lowercase())
+            .collect();
+        for label in plugin_labels {
+            labels.entry(label).or_default().push(&plugin.name);
+        }
+    }
+    let problems: Vec<String> = labels
+        .iter()
+        .filter(|(_, plugins)| plugins.len() > 1)
+        .map(|(label, plugins)| {
+            format!(
+                "Command /{} is declared by {}, {} loads first and gets it",
+                label,
+                plugins.join(", "),
+                plugins[0]
+            )
+        })
+        .collect();
+    graph.problems.extend(problems);
+}
+
+/// Unrelocated copies of a library clash once several plugins share it.
+fn report_duplicate_libraries(graph: &mut PluginGraph) {
+    let mut packages: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for plugin in &graph.plugins {
+        for package in &plugin.shaded {
+            packages.entry(package).or_default().push(&plugin.name);
+        }
+    }
+    let problems: Vec<String> = packages
+        .iter()
+        .filter(|(_, plugins)| plugins.len() > 1)
+        .map(|(package, plugins)| {
+            format!(
+                "{} is shaded without relocation by {}",
+                package,
+                plugins.join(", ")
+            )
+        })
+        .collect();
+    graph.problems.extend(problems);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plugin(name: &str, depend: &[&str], softdepend: &[&str], loadbefore: &[&str]) -> PluginNode {
+        let names = |names: &[&str]| names.iter().map(|name| name.to_string()).collect();
+        PluginNode {
+            jar: format!("{}.jar", name),
+            name: name.to_string(),
+            depend: names(depend),
+            softdepend: names(softdepend),
+            loadbefore: names(loadbefore),
+            ..PluginNode::default()
+        }
+    }
+
+    fn graph(plugins: Vec<PluginNode>) -> PluginGraph {
+        let mut graph = PluginGraph {
+            plugins,
+            ..PluginGraph::default()
+        };
+        remove_duplicates(&mut graph);
+        simulate_load_order(&mut graph);
+        report_command_conflicts(&mut graph);
+        report_duplicate_libraries(&mut graph);
+        graph
+    }
+
+    #[test]
+    fn simulate_load_order_should_follow_bukkit() {
+        // GIVEN plugins with dependencies, a loadbefore, a missing dependency and a hard cycle
+        let mut vault = plugin("Vault", &[], &[], &[]);
+        vault.provides = vec![String::from("Economy")];
+        let plugins = vec![
+            plugin("Homes", &["Economy"], &["Essentials"], &[]),
+            plugin("Essentials", &[], &[], &["Vault"]),
+            vault,
+            plugin("Shops", &["Missing"], &[], &[]),
+            PluginNode {
+                jar: String::from("Shops-2.0.jar"),
+                ..plugin("Shops", &[], &[], &[])
+            },
+            plugin("Ping", &["Pong"], &[], &[]),
+            plugin("Pong", &["Ping"], &[], &[]),
+        ];
+
+        // WHEN we simulate their loading
+        let graph = graph(plugins);
+
+        // THEN they should load in dependency order, with every problem reported
+        assert_eq!(vec!["Essentials", "Vault", "Homes"], graph.load_order);
+        assert_eq!(
+            vec![
+                "Duplicate plugin name Shops: Shops.jar and Shops-2.0.jar, only one is loaded",
+                "Shops cannot load: its dependency Missing is missing",
+                "Dependency cycle: Ping, Pong cannot load",
+            ],
+            graph.problems
+        );
+    }
+
+    #[test]
+    fn simulate_load_order_should_break_soft_dependency_cycles() {
+        // GIVEN two plugins soft depending on each other, sharing a command and a library
+        let mut homes = plugin("Homes", &[], &["Warps"], &[]);
+        homes.commands = vec![(String::from("home"), vec![String::from("h")])];
+        homes.shaded = vec![String::from("org.bstats")];
+        let mut warps = plugin("Warps", &[], &["Homes"], &[]);
+        warps.commands = vec![(String::from("warp"), vec![String::from("H")])];
+        warps.shaded = vec![String::from("org.bstats")];
+
+        // WHEN we simulate their loading
+        let graph = graph(vec![homes, warps]);
+
+        // THEN the first one should load first, and the conflicts be reported
+        assert_eq!(vec!["Homes", "Warps"], graph.load_order);
+        assert_eq!(
+            vec!["Soft dependency cycle broken by loading Homes first"],
+            graph.notes
+        );
+        assert_eq!(
+            vec![
+                "Command /h is declared by Homes, Warps, Homes loads first and gets it",
+                "org.bstats is shaded without relocation by Homes, Warps",
+            ],
+            graph.problems
+        );
+    }
+
+    #[test]
+    fn to_mermaid_should_mark_missing_dependencies() {
+        // GIVEN a plugin with a dependency and a missing soft dependency
+        let graph = graph(vec![
+            plugin("Homes", &["Vault"], &["Essentials"], &[]),
+            plugin("Vault", &[], &[], &[]),
+        ]);
+
+        // WHEN we draw the graph
+        let mermaid = graph.to_mermaid();
+        let dot = graph.to_dot();
+
+        // THEN every edge should be drawn and the missing plugin marked
+        assert_eq!(
+            "graph LR\n    p0[\"Homes\"]\n    p1[\"Vault\"]\n    p2[\"Essentials\"]\n    p0 -->|depend| p1\n    p0 -.->|softdepend| p2\n    classDef missing stroke:#f00,color:#f00\n    class p2 missing",
+            mermaid
+        );
+        assert_eq!(
+            "digraph plugins {\n    \"Homes\";\n    \"Vault\";\n    \"Essentials\" [color=red];\n    \"Homes\" -> \"Vault\" [label=\"depend\"];\n    \"Homes\" -> \"Essentials\" [label=\"softdepend\", style=dashed];\n}",
+            dot
+        );
+    }
+
+    #[test]
+    fn to_mermaid_should_link_provided_names_to_their_provider() {
+        // GIVEN a plugin depending on a name provided by Vault, and a name with a quote
+        let mut vault = plugin("Vault", &[], &[], &[]);
+        vault.provides = vec![String::from("Economy")];
+        let graph = graph(vec![
+            plugin("Homes", &["Economy"], &[], &[]),
+            vault,
+            plugin("Say \"Hi\"", &["Vault"], &[], &[]),
+        ]);
+
+        // WHEN we draw the graph
+        let mermaid = graph.to_mermaid();
+        let dot = graph.to_dot();
+
+        // THEN the dependency should point to Vault, without any missing plugin
+        assert_eq!(
+            "graph LR\n    p0[\"Homes\"]\n    p1[\"Vault\"]\n    p2[\"Say 'Hi'\"]\n    p0 -->|depend| p1\n    p2 -->|depend| p1",
+            mermaid
+        );
+        assert_eq!(
+            "digraph plugins {\n    \"Homes\";\n    \"Vault\";\n    \"Say \\\"Hi\\\"\";\n    \"Homes\" -> \"Vault\" [label=\"depend\"];\n    \"Say \\\"Hi\\\"\" -> \"Vault\" [label=\"depend\"];\n}",
+            dot
+        );
+    }
+}
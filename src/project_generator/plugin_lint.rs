@@ -0,0 +1,537 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use serde::Serialize;
+use serde_yaml::{Mapping, Value};
+
+use crate::errors::GeneratorError;
+use crate::project_generator::existing_project::PLUGIN_YML_PATH;
+use crate::project_generator::paper_migration::PAPER_PLUGIN_YML_PATH;
+use crate::project_generator::plugin_descriptor::validate_api_version;
+use crate::project_generator::version_catalog::{find_release, parse_version};
+
+const SOURCE_PATHS: [(&str, &str); 3] = [
+    ("src/main/java", "java"),
+    ("src/main/kotlin", "kt"),
+    ("src/main/kotlin", "java"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
+    }
+}
+
+/// A rule broken by a descriptor, `key` being the YAML path of the offending entry.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LintIssue {
+    pub severity: Severity,
+    pub key: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DescriptorLint {
+    pub path: String,
+    pub issues: Vec<LintIssue>,
+}
+
+impl DescriptorLint {
+    pub fn count(&self, severity: Severity) -> usize {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == severity)
+            .count()
+    }
+}
+
+/// Lints the `plugin.yml` and `paper-plugin.yml` of the project, failing when it has neither.
+pub fn lint_project(root: &Path) -> Result<Vec<DescriptorLint>, GeneratorError> {
+    let lints: Vec<DescriptorLint> = [PLUGIN_YML_PATH, PAPER_PLUGIN_YML_PATH]
+        .into_iter()
+        .filter_map(|path| {
+            let content = fs::read_to_string(root.join(path)).ok()?;
+            Some(lint_descriptor(root, path, &content))
+        })
+        .collect();
+    if lints.is_empty() {
+        return Err(GeneratorError::InvalidProject(
+            root.display().to_string(),
+            format!(
+                "neither {} nor {} exists",
+                PLUGIN_YML_PATH, PAPER_PLUGIN_YML_PATH
+            ),
+        ));
+    }
+    Ok(lints)
+}
+
+/// Checks the rules Bukkit enforces when loading the descriptor, and a few it silently tolerates.
+pub fn lint_descriptor(root: &Path, path: &str, content: &str) -> DescriptorLint {
+    let mut lint = Linter::default();
+    match serde_yaml::from_str::<Mapping>(content) {
+        Ok(descriptor) => {
+            let paper = path == PAPER_PLUGIN_YML_PATH;
+            lint.name(&descriptor);
+            lint.required_string(&descriptor, "version");
+            lint.main(root, &descriptor);
+            lint.api_version(&descriptor, paper);
+            lint.load(&descriptor);
+            let permissions = lint.permissions(&descriptor);
+            match paper {
+                true => lint.paper_commands(&descriptor),
+                false => lint.commands(&descriptor, &permissions),
+            }
+            let name = descriptor.get("name").and_then(Value::as_str);
+            let dependencies = match paper {
+                true => lint.paper_dependencies(&descriptor),
+                false => lint.dependencies(&descriptor),
+            };
+            lint.cycles(name, &dependencies);
+        }
+        Err(error) => lint.error("", format!("unable to parse: {}", error)),
+    }
+    DescriptorLint {
+        path: path.to_string(),
+        issues: lint.issues,
+    }
+}
+
+/// The plugins a descriptor loads after and before.
+#[derive(Default)]
+struct Dependencies {
+    depend: Vec<String>,
+    softdepend: Vec<String>,
+    loadbefore: Vec<String>,
+}
+
+#[derive(Default)]
+struct Linter {
+    issues: Vec<LintIssue>,
+}
+
+impl Linter {
+    fn issue(&mut self, severity: Severity, key: &str, message: String) {
+        self.issues.push(LintIssue {
+            severity,
+            key: key.to_string(),
+            message,
+        });
+    }
+
+    fn error(&mut self, key: &str, message: String) {
+        self.issue(Severity::Error, key, message);
+    }
+
+    fn warning(&mut self, key: &str, message: String) {
+        self.issue(Severity::Warning, key, message);
+    }
+
+    fn required_string(&mut self, descriptor: &Mapping, key: &str) -> Option<String> {
+        match descriptor.get(key) {
+            None | Some(Value::Null) => {
+                self.error(key, String::from("is missing"));
+                None
+            }
+            Some(Value::String(value)) => Some(value.clone()),
+            Some(Value::Number(value)) => {
+                self.warning(
+                    key,
+                    format!("{} should be quoted, YAML reads 1.10 as 1.1", value),
+                );
+                Some(value.to_string())
+            }
+            Some(_) => {
+                self.error(key, String::from("must be a string"));
+                None
+            }
+        }
+    }
+
+    fn name(&mut self, descriptor: &Mapping) {
+        let Some(name) = self.required_string(descriptor, "name") else {
+            return;
+        };
+        let valid = |character: char| {
+            character.is_ascii_alphanumeric() || matches!(character, ' ' | '_' | '.' | '-')
+        };
+        if name.is_empty() || !name.chars().all(valid) {
+            self.error("name", format!("'{}' must match ^[A-Za-z0-9 _.-]+$", name));
+        } else if name.contains(' ') {
+            self.warning(
+                "name",
+                format!("'{}' contains spaces, Bukkit replaces them with '_'", name),
+            );
+        }
+    }
+
+    fn main(&mut self, root: &Path, descriptor: &Mapping) {
+        let Some(main) = self.required_string(descriptor, "main") else {
+            return;
+        };
+        if main.to_lowercase().starts_with("org.bukkit.") {
+            self.error(
+                "main",
+                format!("'{}' may not be within the org.bukkit package", main),
+            );
+        }
+        let relative = main.replace('.', "/");
+        let exists = SOURCE_PATHS.iter().any(|(directory, extension)| {
+            root.join(directory)
+                .join(format!("{}.{}", relative, extension))
+                .is_file()
+        });
+        if !exists {
+            self.error(
+                "main",
+                format!("the class '{}' does not exist in the sources", main),
+            );
+        }
+    }
+
+    fn api_version(&mut self, descriptor: &Mapping, paper: bool) {
+        let api_version = match descriptor.get("api-version") {
+            None | Some(Value::Null) => {
+                let message = "is missing, the plugin is loaded as a legacy plugin";
+                match paper {
+                    true => self.error("api-version", String::from("is missing")),
+                    false => self.warning("api-version", String::from(message)),
+                }
+                return;
+            }
+            Some(Value::String(value)) => value.clone(),
+            Some(Value::Number(value)) => {
+                self.warning(
+                    "api-version",
+                    format!("{} should be quoted, YAML reads 1.20 as 1.2", value),
+                );
+                value.to_string()
+            }
+            Some(_) => {
+                self.error("api-version", String::from("must be a string"));
+                return;
+            }
+        };
+        if validate_api_version(&api_version).is_err() {
+            self.error(
+                "api-version",
+                format!(
+                    "'{}' must be a Minecraft version from 1.13 onwards",
+                    api_version
+                ),
+            );
+        } else if parse_version(&api_version).is_none() {
+            self.error(
+                "api-version",
+                format!("'{}' must look like 1.21.4", api_version),
+            );
+        } else if find_release(&api_version).is_err() {
+            // Only newer versions are left, which an outdated catalog does not make invalid
+            self.warning(
+                "api-version",
+                format!(
+                    "'{}' is newer than the bundled version catalog, which may be outdated",
+                    api_version
+                ),
+            );
+        }
+    }
+
+    fn load(&mut self, descriptor: &Mapping) {
+        match descriptor.get("load") {
+            None | Some(Value::String(_)) => {}
+            Some(_) => return self.error("load", String::from("must be a string")),
+        }
+        if let Some(load) = descriptor.get("load").and_then(Value::as_str) {
+            if load != "STARTUP" && load != "POSTWORLD" {
+                self.error("load", format!("'{}' must be STARTUP or POSTWORLD", load));
+            }
+        }
+    }
+
+    /// The permissions declared at the top level or as inline children.
+    fn permissions(&mut self, descriptor: &Mapping) -> BTreeSet<String> {
+        let mut declared = BTreeSet::new();
+        let Some(permissions) = self.mapping(descriptor, "permissions") else {
+            return declared;
+        };
+        for (name, permission) in permissions {
+            let Some(name) = name.as_str() else {
+                continue;
+            };
+            declared.insert(name.to_string());
+            let children = permission
+                .as_mapping()
+                .and_then(|permission| permission.get("children"))
+                .and_then(Value::as_mapping);
+            for (child, value) in children.into_iter().flatten() {
+                if let (Some(child), true) = (child.as_str(), value.is_mapping()) {
+                    declared.insert(child.to_string());
+                }
+            }
+        }
+        declared
+    }
+
+    fn commands(&mut self, descriptor: &Mapping, permissions: &BTreeSet<String>) {
+        let Some(commands) = self.mapping(descriptor, "commands") else {
+            return;
+        };
+        for (name, command) in commands {
+            let name = name.as_str().unwrap_or_default();
+            let key = format!("commands.{}", name);
+            if name.contains(':') {
+                self.error(&key, format!("'{}' may not contain ':'", name));
+            }
+            let command = match command {
+                Value::Null => &Mapping::new(),
+                Value::Mapping(command) => command,
+                _ => {
+                    self.error(&key, String::from("must be a mapping"));
+                    continue;
+                }
+            };
+            match command.get("usage") {
+                None => self.warning(
+                    &format!("{}.usage", key),
+                    String::from("is missing, players will only see /<command>"),
+                ),
+                Some(Value::String(_)) => {}
+                Some(_) => self.error(&format!("{}.usage", key), String::from("must be a string")),
+            }
+            match command.get("aliases") {
+                None | Some(Value::String(_)) => {}
+                Some(Value::Sequence(aliases)) if aliases.iter().all(Value::is_string) => {}
+                Some(_) => self.error(
+                    &format!("{}.aliases", key),
+                    String::from("must be a string or a list of strings"),
+                ),
+            }
+            match command.get("permission") {
+                None => {}
+                Some(Value::String(permission)) if !permissions.contains(permission) => self
+                    .warning(
+                        &format!("{}.permission", key),
+                        format!("'{}' is not declared in permissions", permission),
+                    ),
+                Some(Value::String(_)) => {}
+                Some(_) => self.error(
+                    &format!("{}.permission", key),
+                    String::from("must be a string"),
+                ),
+            }
+        }
+    }
+
+    fn paper_commands(&mut self, descriptor: &Mapping) {
+        if descriptor.contains_key("commands") {
+            self.warning(
+                "commands",
+                String::from("are ignored by Paper plugins, register them with the Brigadier API"),
+            );
+        }
+    }
+
+    fn dependencies(&mut self, descriptor: &Mapping) -> Dependencies {
+        Dependencies {
+            depend: self.list(descriptor, "depend"),
+            softdepend: self.list(descriptor, "softdepend"),
+            loadbefore: self.list(descriptor, "loadbefore"),
+        }
+    }
+
+    /// The `dependencies.server` entries, loaded `BEFORE` or `AFTER` the plugin.
+    fn paper_dependencies(&mut self, descriptor: &Mapping) -> Dependencies {
+        let mut dependencies = Dependencies::default();
+        let server = descriptor
+            .get("dependencies")
+            .and_then(Value::as_mapping)
+            .and_then(|dependencies| dependencies.get("server"))
+            .and_then(Value::as_mapping);
+        for (name, dependency) in server.into_iter().flatten() {
+            let Some(name) = name.as_str() else {
+                continue;
+            };
+            let load = dependency.get("load").and_then(Value::as_str);
+            let required = dependency
+                .get("required")
+                .and_then(Value::as_bool)
+                .unwrap_or(true);
+            match (load, required) {
+                (Some("BEFORE"), true) => dependencies.depend.push(name.to_string()),
+                (Some("BEFORE"), false) => dependencies.softdepend.push(name.to_string()),
+                (Some("AFTER"), _) => dependencies.loadbefore.push(name.to_string()),
+                (None | Some("OMIT"), _) => {}
+                (Some(load), _) => self.error(
+                    &format!("dependencies.server.{}.load", name),
+                    format!("'{}' must be BEFORE, AFTER or OMIT", load),
+                ),
+            }
+        }
+        dependencies
+    }
+
+    /// Flags the plugins the descriptor must load both after and before.
+    fn cycles(&mut self, name: Option<&str>, dependencies: &Dependencies) {
+        let lists = [
+            ("depend", &dependencies.depend),
+            ("softdepend", &dependencies.softdepend),
+            ("loadbefore", &dependencies.loadbefore),
+        ];
+        for (key, list) in lists {
+            if let Some(name) = name.filter(|name| list.iter().any(|plugin| plugin == name)) {
+                self.error(key, format!("the plugin cannot list itself, '{}'", name));
+            }
+        }
+        for plugin in &dependencies.loadbefore {
+            if dependencies.depend.contains(plugin) {
+                self.error(
+                    "loadbefore",
+                    format!("'{}' is also in depend, the plugins cannot load", plugin),
+                );
+            } else if dependencies.softdepend.contains(plugin) {
+                self.warning(
+                    "loadbefore",
+                    format!(
+                        "'{}' is also in softdepend, Bukkit breaks the cycle in any order",
+                        plugin
+                    ),
+                );
+            }
+        }
+    }
+
+    fn mapping<'a>(&mut self, descriptor: &'a Mapping, key: &str) -> Option<&'a Mapping> {
+        match descriptor.get(key) {
+            None | Some(Value::Null) => None,
+            Some(Value::Mapping(mapping)) => Some(mapping),
+            Some(_) => {
+                self.error(key, String::from("must be a mapping"));
+                None
+            }
+        }
+    }
+
+    fn list(&mut self, descriptor: &Mapping, key: &str) -> Vec<String> {
+        match descriptor.get(key) {
+            None | Some(Value::Null) => Vec::new(),
+            Some(Value::Sequence(values)) if values.iter().all(Value::is_string) => values
+                .iter()
+                .filter_map(Value::as_str)
+                .map(String::from)
+                .collect(),
+            Some(_) => {
+                self.error(key, String::from("must be a list of plugin names"));
+                Vec::new()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn messages(lint: &DescriptorLint) -> Vec<String> {
+        lint.issues
+            .iter()
+            .map(|issue| format!("{} {}: {}", issue.severity.name(), issue.key, issue.message))
+            .collect()
+    }
+
+    #[test]
+    fn lint_descriptor_should_accept_generated_descriptor() {
+        // GIVEN a project with its main class and a complete plugin.yml
+        let root = env::temp_dir().join("mcdev-lint-valid");
+        let main = root.join("src/main/java/com/test/Homes.java");
+        fs::create_dir_all(main.parent().unwrap()).unwrap();
+        fs::write(&main, "package com.test;\n").unwrap();
+        let content = "name: Homes\nversion: 1.0.0\nmain: com.test.Homes\napi-version: '1.21'\ndepend: [Vault]\ncommands:\n  home:\n    usage: /home\n    permission: homes.home\npermissions:\n  homes.home:\n    default: true\n";
+
+        // WHEN we lint it
+        let lint = lint_descriptor(&root, PLUGIN_YML_PATH, content);
+
+        // THEN nothing should be reported
+        assert_eq!(Vec::<String>::new(), messages(&lint));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn lint_descriptor_should_report_broken_rules() {
+        // GIVEN a plugin.yml breaking most load rules
+        let root = env::temp_dir().join("mcdev-lint-invalid");
+        let content = "name: My Plugin!\nversion: 1.0\nmain: org.bukkit.Homes\napi-version: 1.12\nload: LATER\ndepend: [Vault, My Plugin!]\nloadbefore: [Vault]\ncommands:\n  home:\n    usage: [a, b]\n    permission: homes.home\n  spawn:\n";
+
+        // WHEN we lint it
+        let lint = lint_descriptor(&root, PLUGIN_YML_PATH, content);
+
+        // THEN every broken rule should be reported
+        assert_eq!(
+            vec![
+                "error name: 'My Plugin!' must match ^[A-Za-z0-9 _.-]+$",
+                "warning version: 1.0 should be quoted, YAML reads 1.10 as 1.1",
+                "error main: 'org.bukkit.Homes' may not be within the org.bukkit package",
+                "error main: the class 'org.bukkit.Homes' does not exist in the sources",
+                "warning api-version: 1.12 should be quoted, YAML reads 1.20 as 1.2",
+                "error api-version: '1.12' must be a Minecraft version from 1.13 onwards",
+                "error load: 'LATER' must be STARTUP or POSTWORLD",
+                "error commands.home.usage: must be a string",
+                "warning commands.home.permission: 'homes.home' is not declared in permissions",
+                "warning commands.spawn.usage: is missing, players will only see /<command>",
+                "error depend: the plugin cannot list itself, 'My Plugin!'",
+                "error loadbefore: 'Vault' is also in depend, the plugins cannot load",
+            ],
+            messages(&lint)
+        );
+        assert_eq!(8, lint.count(Severity::Error));
+    }
+
+    #[test]
+    fn lint_descriptor_should_warn_for_api_version_newer_than_catalog() {
+        // GIVEN a plugin.yml targeting a version the bundled catalog does not know yet
+        let root = env::temp_dir().join("mcdev-lint-newer");
+        let content = "name: Homes\nversion: 1.0.0\nmain: com.test.Homes\napi-version: '1.99.1'\n";
+
+        // WHEN we lint it
+        let lint = lint_descriptor(&root, PLUGIN_YML_PATH, content);
+
+        // THEN it should only be a warning
+        assert!(messages(&lint).contains(&String::from(
+            "warning api-version: '1.99.1' is newer than the bundled version catalog, which may be outdated"
+        )));
+        assert_eq!(1, lint.count(Severity::Error));
+    }
+
+    #[test]
+    fn lint_descriptor_should_read_paper_dependencies() {
+        // GIVEN a paper-plugin.yml loading a dependency both before and after it
+        let root = env::temp_dir().join("mcdev-lint-paper");
+        let content = "name: Homes\nversion: '1.0'\nmain: com.test.Homes\napi-version: '1.21.4'\ncommands:\n  home: {}\ndependencies:\n  server:\n    Vault:\n      load: BEFORE\n    Essentials:\n      load: AFTER\n      required: false\n    Other:\n      load: SOMETIME\n";
+
+        // WHEN we lint it
+        let lint = lint_descriptor(&root, PAPER_PLUGIN_YML_PATH, content);
+
+        // THEN commands and the unknown load order should be reported
+        assert_eq!(
+            vec![
+                "error main: the class 'com.test.Homes' does not exist in the sources",
+                "warning commands: are ignored by Paper plugins, register them with the Brigadier API",
+                "error dependencies.server.Other.load: 'SOMETIME' must be BEFORE, AFTER or OMIT",
+            ],
+            messages(&lint)
+        );
+    }
+}
@@ -0,0 +1,305 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::errors::GeneratorError;
+use crate::project_generator::template_engine::Context;
+use crate::project_generator::validation::{
+    validate_license, validate_project_version, validate_website,
+};
+use crate::project_generator::{ParameterDescriptor, Parameters};
+
+pub const DEFAULT_PROJECT_VERSION: &str = "1.0.0";
+
+/// The descriptive metadata shared by every generator.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectInfo {
+    pub project_version: String,
+    pub description: String,
+    pub authors: Vec<String>,
+    pub website: Option<String>,
+    pub license: Option<String>,
+}
+
+impl Default for ProjectInfo {
+    fn default() -> Self {
+        Self {
+            project_version: String::from(DEFAULT_PROJECT_VERSION),
+            description: String::new(),
+            authors: Vec::new(),
+            website: None,
+            license: None,
+        }
+    }
+}
+
+impl ProjectInfo {
+    /// The optional parameters any generator can add to its own.
+    pub fn parameters() -> Vec<ParameterDescriptor> {
+        vec![
+            ParameterDescriptor::new("project_version", "Version of the project", false)
+                .with_default(DEFAULT_PROJECT_VERSION),
+            ParameterDescriptor::new("description", "Short description of the project", false),
+            ParameterDescriptor::new("authors", "Comma separated list of authors", false),
+            ParameterDescriptor::new("website", "Website of the project", false),
+            ParameterDescriptor::new("license", "SPDX license identifier, e.g. MIT", false),
+        ]
+    }
+
+    pub fn validate_parameter(name: &str, value: &str) -> Result<(), GeneratorError> {
+        match name {
+            "project_version" => validate_project_version(value),
+            "website" if !value.is_empty() => validate_website(value),
+            "license" if !value.is_empty() => validate_license(value),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn from_parameters(parameters: &Parameters) -> Self {
+        let optional = |name: &str| {
+            parameters
+                .get(name)
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+
+        Self {
+            project_version: optional("project_version")
+                .unwrap_or_else(|| String::from(DEFAULT_PROJECT_VERSION)),
+            description: optional("description").unwrap_or_default(),
+            authors: optional("authors")
+                .map(|authors| {
+                    authors
+                        .split(',')
+                        .map(|author| author.trim().to_string())
+                        .filter(|author| !author.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            website: optional("website"),
+            license: optional("license"),
+        }
+    }
+
+    pub fn extend_context(&self, context: &mut Context) {
+        context
+            .insert("project_version", self.project_version.as_str())
+            .insert("description", self.description.as_str())
+            .insert("authors", self.authors.clone())
+            .insert("website", self.website.clone().unwrap_or_default())
+            .insert("license", self.license.clone().unwrap_or_default());
+        if let Some(license) = &self.license {
+            context.insert("license_text", license_text(license, &self.authors));
+        }
+    }
+}
+
+pub fn current_year() -> u64 {
+    let days = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() / 86_400)
+        .unwrap_or_default();
+    // Civil from days, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let year = year_of_era + era * 400;
+    if month_index >= 10 {
+        year + 1
+    } else {
+        year
+    }
+}
+
+/// The full license text for the common permissive licenses, a reference to SPDX for the others.
+pub fn license_text(license: &str, authors: &[String]) -> String {
+    let holders = if authors.is_empty() {
+        String::from("the authors")
+    } else {
+        authors.join(", ")
+    };
+    let copyright = format!("Copyright (c) {} {}", current_year(), holders);
+
+    match license.to_uppercase().as_str() {
+        "MIT" => format!("MIT License\n\n{}\n\n{}", copyright, MIT_LICENSE),
+        "ISC" => format!("ISC License\n\n{}\n\n{}", copyright, ISC_LICENSE),
+        "BSD-2-CLAUSE" => format!(
+            "BSD 2-Clause License\n\n{}\n\n{}{}",
+            copyright, BSD_CONDITIONS, BSD_DISCLAIMER
+        ),
+        "BSD-3-CLAUSE" => format!(
+            "BSD 3-Clause License\n\n{}\n\n{}{}{}",
+            copyright, BSD_CONDITIONS, BSD_THIRD_CLAUSE, BSD_DISCLAIMER
+        ),
+        "UNLICENSE" => String::from(UNLICENSE),
+        _ => format!(
+            "SPDX-License-Identifier: {license}\n\n{copyright}\n\nThis project is licensed under the {license} license.\nThe full text is available at https://spdx.org/licenses/{license}.html\n"
+        ),
+    }
+}
+
+const MIT_LICENSE: &str =
+    "Permission is hereby granted, free of charge, to any person obtaining a copy
+of this software and associated documentation files (the \"Software\"), to deal
+in the Software without restriction, including without limitation the rights
+to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+copies of the Software, and to permit persons to whom the Software is
+furnished to do so, subject to the following conditions:
+
+The above copyright notice and this permission notice shall be included in all
+copies or substantial portions of the Software.
+
+THE SOFTWARE IS PROVIDED \"AS IS\", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+SOFTWARE.
+";
+
+const ISC_LICENSE: &str = "Permission to use, copy, modify, and/or distribute this software for any
+purpose with or without fee is hereby granted, provided that the above
+copyright notice and this permission notice appear in all copies.
+
+THE SOFTWARE IS PROVIDED \"AS IS\" AND THE AUTHOR DISCLAIMS ALL WARRANTIES
+WITH REGARD TO THIS SOFTWARE INCLUDING ALL IMPLIED WARRANTIES OF
+MERCHANTABILITY AND FITNESS. IN NO EVENT SHALL THE AUTHOR BE LIABLE FOR
+ANY SPECIAL, DIRECT, INDIRECT, OR CONSEQUENTIAL DAMAGES OR ANY DAMAGES
+WHATSOEVER RESULTING FROM LOSS OF USE, DATA OR PROFITS, WHETHER IN AN
+ACTION OF CONTRACT, NEGLIGENCE OR OTHER TORTIOUS ACTION, ARISING OUT OF
+OR IN CONNECTION WITH THE USE OR PERFORMANCE OF THIS SOFTWARE.
+";
+
+const BSD_CONDITIONS: &str = "Redistribution and use in source and binary forms, with or without
+modification, are permitted provided that the following conditions are met:
+
+1. Redistributions of source code must retain the above copyright notice, this
+   list of conditions and the following disclaimer.
+
+2. Redistributions in binary form must reproduce the above copyright notice,
+   this list of conditions and the following disclaimer in the documentation
+   and/or other materials provided with the distribution.
+
+";
+
+const BSD_THIRD_CLAUSE: &str = "3. Neither the name of the copyright holder nor the names of its
+   contributors may be used to endorse or promote products derived from
+   this software without specific prior written permission.
+
+";
+
+const BSD_DISCLAIMER: &str =
+    "THIS SOFTWARE IS PROVIDED BY THE COPYRIGHT HOLDERS AND CONTRIBUTORS \"AS IS\"
+AND ANY EXPRESS OR IMPLIED WARRANTIES, INCLUDING, BUT NOT LIMITED TO, THE
+IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR A PARTICULAR PURPOSE ARE
+DISCLAIMED. IN NO EVENT SHALL THE COPYRIGHT HOLDER OR CONTRIBUTORS BE LIABLE
+FOR ANY DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL
+DAMAGES (INCLUDING, BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR
+SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER
+CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT, STRICT LIABILITY,
+OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE USE
+OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+";
+
+const UNLICENSE: &str = "This is free and unencumbered software released into the public domain.
+
+Anyone is free to copy, modify, publish, use, compile, sell, or
+distribute this software, either in source code form or as a compiled
+binary, for any purpose, commercial or non-commercial, and by any
+means.
+
+In jurisdictions that recognize copyright laws, the author or authors
+of this software dedicate any and all copyright interest in the
+software to the public domain. We make this dedication for the benefit
+of the public at large and to the detriment of our heirs and
+successors. We intend this dedication to be an overt act of
+relinquishment in perpetuity of all present and future rights to this
+software under copyright law.
+
+THE SOFTWARE IS PROVIDED \"AS IS\", WITHOUT WARRANTY OF ANY KIND,
+EXPRESS OR IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF
+MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT.
+IN NO EVENT SHALL THE AUTHORS BE LIABLE FOR ANY CLAIM, DAMAGES OR
+OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE,
+ARISING FROM, OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR
+OTHER DEALINGS IN THE SOFTWARE.
+
+For more information, please refer to <https://unlicense.org>
+";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_parameters_should_use_defaults() {
+        // GIVEN no metadata parameter
+        let parameters = Parameters::new();
+
+        // WHEN we read the project info
+        let info = ProjectInfo::from_parameters(&parameters);
+
+        // THEN the defaults should be used
+        assert_eq!(ProjectInfo::default(), info);
+    }
+
+    #[test]
+    fn from_parameters_should_split_authors() {
+        // GIVEN metadata parameters
+        let parameters = Parameters::from([
+            (String::from("project_version"), String::from("2.1.0")),
+            (String::from("authors"), String::from("Notch, jeb_ ,")),
+            (String::from("license"), String::from("MIT")),
+            (String::from("website"), String::from("")),
+        ]);
+
+        // WHEN we read the project info
+        let info = ProjectInfo::from_parameters(&parameters);
+
+        // THEN every value should be read
+        assert_eq!("2.1.0", info.project_version);
+        assert_eq!(vec!["Notch", "jeb_"], info.authors);
+        assert_eq!(Some(String::from("MIT")), info.license);
+        assert_eq!(None, info.website);
+    }
+
+    #[test]
+    fn license_text_should_contain_copyright_holders() {
+        // GIVEN a known license
+        let authors = vec![String::from("Notch"), String::from("jeb_")];
+
+        // WHEN we get its text
+        let text = license_text("mit", &authors);
+
+        // THEN it should be the full license with the authors
+        assert!(text.starts_with("MIT License\n\nCopyright (c) "));
+        assert!(text.contains(&format!("{} Notch, jeb_", current_year())));
+        assert!(text.contains("Permission is hereby granted"));
+    }
+
+    #[test]
+    fn license_text_should_reference_spdx_for_other_licenses() {
+        // GIVEN a license without bundled text
+        let license = "GPL-3.0-only";
+
+        // WHEN we get its text
+        let text = license_text(license, &[]);
+
+        // THEN it should point to the SPDX page
+        assert!(text.contains("https://spdx.org/licenses/GPL-3.0-only.html"));
+        assert!(text.contains("the authors"));
+    }
+
+    #[test]
+    fn current_year_should_be_plausible() {
+        // GIVEN the current date
+        // WHEN we compute the year
+        let year = current_year();
+
+        // THEN it should be after the tool was written
+        assert!(year >= 2024);
+    }
+}
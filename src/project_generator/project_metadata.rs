@@ -0,0 +1,176 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::errors::GeneratorError;
+use crate::project_generator::file_operations::create_file_with_parents;
+use crate::project_generator::Parameters;
+
+pub const METADATA_FILE_NAME: &str = ".mcdev.toml";
+pub const TOOL_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// A copy of the generated files, the common base of three-way merges when upgrading.
+pub const GENERATED_FILES_DIRECTORY: &str = ".mcdev/generated";
+
+/// What a project was generated from, written at its root so later commands need not ask again.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct ProjectMetadata {
+    /// The generator or template directory name.
+    pub generator: String,
+    pub tool_version: String,
+    /// The version of the template pack, when generated from one.
+    pub template_version: Option<String>,
+    pub build_system: Option<String>,
+    pub language: Option<String>,
+    /// Every resolved parameter.
+    #[serde(default)]
+    pub parameters: BTreeMap<String, String>,
+    /// The SHA-256 of every generated file, by path relative to the project root.
+    #[serde(default)]
+    pub files: BTreeMap<String, String>,
+}
+
+impl ProjectMetadata {
+    pub fn new(generator: &str, parameters: &Parameters) -> Self {
+        Self {
+            generator: generator.to_string(),
+            tool_version: TOOL_VERSION.to_string(),
+            template_version: None,
+            build_system: None,
+            language: None,
+            parameters: parameters
+                .iter()
+                .map(|(name, value)| (name.clone(), value.clone()))
+                .collect(),
+            files: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_template_version(mut self, version: Option<&str>) -> Self {
+        self.template_version = version.map(str::to_string);
+        self
+    }
+
+    pub fn with_build(mut self, build_system: &str, language: &str) -> Self {
+        self.build_system = Some(build_system.to_string());
+        self.language = Some(language.to_string());
+        self
+    }
+
+    /// Records the hash of the generated files, replacing those of the same paths.
+    pub fn record_files(&mut self, files: &[(String, String)]) {
+        for (path, content) in files {
+            self.files.insert(path.clone(), content_hash(content));
+        }
+    }
+
+    pub fn read(root: &Path) -> Result<Self, GeneratorError> {
+        let invalid =
+            |reason: String| GeneratorError::InvalidProject(root.display().to_string(), reason);
+        let content = fs::read_to_string(root.join(METADATA_FILE_NAME)).map_err(|error| {
+            invalid(format!("unable to read {}: {}", METADATA_FILE_NAME, error))
+        })?;
+        toml::from_str(&content)
+            .map_err(|error| invalid(format!("unable to parse {}: {}", METADATA_FILE_NAME, error)))
+    }
+
+    pub fn write(&self, root: &Path) -> Result<(), GeneratorError> {
+        let content = toml::to_string(self)
+            .map_err(|error| GeneratorError::TemplateError(error.to_string()))?;
+        create_file_with_parents(&root.join(METADATA_FILE_NAME).to_string_lossy(), &content)
+    }
+
+    /// The generated files changed or deleted since generation, sorted by path.
+    pub fn modified_files(&self, root: &Path) -> Vec<String> {
+        self.files
+            .iter()
+            .filter(|(path, hash)| match fs::read_to_string(root.join(path)) {
+                Ok(content) => content_hash(&content) != **hash,
+                Err(_) => true,
+            })
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
+}
+
+/// Keeps a copy of the generated files under `.mcdev/generated`.
+pub fn write_generated_files(
+    root: &Path,
+    files: &[(String, String)],
+) -> Result<(), GeneratorError> {
+    let directory = root.join(GENERATED_FILES_DIRECTORY);
+    for (path, content) in files {
+        create_file_with_parents(&directory.join(path).to_string_lossy(), content)?;
+    }
+    Ok(())
+}
+
+/// The file as it was generated, unknown for projects generated before copies were kept.
+pub fn generated_content(root: &Path, path: &str) -> Option<String> {
+    fs::read_to_string(root.join(GENERATED_FILES_DIRECTORY).join(path)).ok()
+}
+
+/// The lowercase hexadecimal SHA-256 of the content.
+pub fn content_hash(content: impl AsRef<[u8]>) -> String {
+    Sha256::digest(content.as_ref())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn content_hash_should_be_sha256() {
+        // GIVEN some content
+        // WHEN we hash it
+        let hash = content_hash("abc");
+
+        // THEN it should be its hexadecimal SHA-256
+        assert_eq!(
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            hash
+        );
+    }
+
+    #[test]
+    fn modified_files_should_compare_with_recorded_hashes() {
+        // GIVEN metadata recorded for two generated files
+        let root = env::temp_dir().join("mcdev-metadata");
+        let _ = fs::remove_dir_all(&root);
+        let files = vec![
+            (String::from("pom.xml"), String::from("<project/>")),
+            (String::from("src/Main.java"), String::from("class Main {}")),
+        ];
+        for (path, content) in &files {
+            create_file_with_parents(&root.join(path).to_string_lossy(), content).unwrap();
+        }
+        let parameters = Parameters::from([(String::from("name"), String::from("Homes"))]);
+        let mut metadata = ProjectMetadata::new("spigot", &parameters).with_build("maven", "java");
+        metadata.record_files(&files);
+        metadata.write(&root).unwrap();
+
+        // WHEN the user edits one of them
+        fs::write(root.join("src/Main.java"), "class Main { int homes; }").unwrap();
+
+        // THEN the metadata should be read back and report it as modified
+        let metadata = ProjectMetadata::read(&root).unwrap();
+        assert_eq!(Some("maven"), metadata.build_system.as_deref());
+        assert_eq!(
+            Some(&String::from("Homes")),
+            metadata.parameters.get("name")
+        );
+        assert_eq!(
+            vec![String::from("src/Main.java")],
+            metadata.modified_files(&root)
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,225 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::errors::GeneratorError;
+use crate::project_generator::template_directory::{find_template_directories, TemplateDirectory};
+use crate::project_generator::{Parameters, ProjectGenerator, ProjectGeneratorType};
+
+pub struct GeneratorRegistry {
+    generators: BTreeMap<String, Box<dyn ProjectGenerator>>,
+    /// The template directories that failed to load, by directory name, with the reason.
+    broken: BTreeMap<String, String>,
+}
+
+impl GeneratorRegistry {
+    pub fn new() -> Self {
+        Self {
+            generators: BTreeMap::new(),
+            broken: BTreeMap::new(),
+        }
+    }
+
+    pub fn register(&mut self, name: &str, generator: Box<dyn ProjectGenerator>) {
+        self.generators.insert(name.to_lowercase(), generator);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&dyn ProjectGenerator> {
+        self.generators
+            .get(&name.to_lowercase())
+            .map(|generator| generator.as_ref())
+    }
+
+    /// The generator with that name, failing with the load error of a broken template directory.
+    pub fn find(&self, name: &str) -> Result<&dyn ProjectGenerator, GeneratorError> {
+        match (self.get(name), self.broken.get(&name.to_lowercase())) {
+            (Some(generator), _) => Ok(generator),
+            (None, Some(reason)) => Err(GeneratorError::BrokenGenerator(
+                name.to_string(),
+                reason.clone(),
+            )),
+            (None, None) => Err(GeneratorError::UnableToDetermineProjectGenerator),
+        }
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.generators.keys().map(|name| name.as_str()).collect()
+    }
+
+    /// Registers the template directory under its manifest name, overriding any generator with that name.
+    pub fn load_template_directory(&mut self, directory: &Path) -> Result<String, GeneratorError> {
+        let template_directory = TemplateDirectory::load(directory)?;
+        let name = template_directory.name().to_string();
+        self.register(&name, Box::new(template_directory));
+        Ok(name)
+    }
+
+    /// Registers every template directory of the folder, skipping the broken ones so the other
+    /// generators stay usable, and returns a warning for each of them.
+    pub fn load_template_directories(&mut self, directory: &Path) -> Vec<String> {
+        let mut warnings = Vec::new();
+        for template_directory in find_template_directories(directory) {
+            if let Err(error) = self.load_template_directory(&template_directory) {
+                let name = template_directory
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_lowercase())
+                    .unwrap_or_default();
+                warnings.push(format!(
+                    "Skipping template directory {}: {}",
+                    template_directory.display(),
+                    error
+                ));
+                self.broken.insert(name, error.to_string());
+            }
+        }
+        warnings
+    }
+
+    pub fn generate(&self, name: &str, parameters: &Parameters) -> Result<(), GeneratorError> {
+        let generator = self.find(name)?;
+        generator.validate(parameters)?;
+        generator.generate(parameters)
+    }
+}
+
+impl Default for GeneratorRegistry {
+    fn default() -> Self {
+        let mut registry = Self::new();
+        for project_type in ProjectGeneratorType::ALL {
+            registry.register(project_type.name(), project_type.generator());
+        }
+        registry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project_generator::project_metadata::ProjectMetadata;
+    use crate::project_generator::{ParameterDescriptor, RenderedProject};
+    use std::cell::RefCell;
+
+    struct RecordingGenerator {
+        generated: RefCell<Vec<Parameters>>,
+    }
+
+    impl ProjectGenerator for RecordingGenerator {
+        fn parameters(&self) -> Vec<ParameterDescriptor> {
+            vec![ParameterDescriptor::new("name", "name?", true)]
+        }
+
+        fn validate(&self, parameters: &Parameters) -> Result<(), GeneratorError> {
+            match parameters.get("name") {
+                Some(_) => Ok(()),
+                None => Err(GeneratorError::MissingParameter(String::from("name"))),
+            }
+        }
+
+        fn render(&self, parameters: &Parameters) -> Result<RenderedProject, GeneratorError> {
+            self.generated.borrow_mut().push(parameters.clone());
+            Ok(RenderedProject {
+                root: String::from("recording"),
+                files: Vec::new(),
+                metadata: ProjectMetadata::new("recording", parameters),
+            })
+        }
+    }
+
+    #[test]
+    fn default_registry_should_contain_spigot() {
+        // GIVEN the default registry
+        let registry = GeneratorRegistry::default();
+
+        // WHEN we look for the spigot generator with an unconventional case
+        let generator = registry.get("SpIgOt");
+
+        // THEN it should be found
+        assert!(generator.is_some());
+        assert_eq!(vec!["spigot"], registry.names());
+    }
+
+    #[test]
+    fn generate_should_validate_before_generating() {
+        // GIVEN a registry with a custom generator
+        let mut registry = GeneratorRegistry::new();
+        registry.register(
+            "recording",
+            Box::new(RecordingGenerator {
+                generated: RefCell::new(Vec::new()),
+            }),
+        );
+
+        // WHEN we generate without the mandatory parameter
+        let result = registry.generate("recording", &Parameters::new());
+
+        // THEN the validation error should be returned
+        assert!(matches!(result, Err(GeneratorError::MissingParameter(_))));
+    }
+
+    #[test]
+    fn load_template_directory_should_override_builtin_generator() {
+        // GIVEN a template directory named after a built-in generator
+        let directory = std::env::temp_dir().join("mcdev-registry-override");
+        std::fs::create_dir_all(&directory).unwrap();
+        std::fs::write(
+            directory.join("manifest.toml"),
+            "name = \"Spigot\"\nextends = \"spigot\"\n[[parameters]]\nname = \"metrics_id\"\nprompt = \"bStats id\"\n",
+        )
+        .unwrap();
+        let mut registry = GeneratorRegistry::default();
+
+        // WHEN we load it
+        let name = registry.load_template_directory(&directory).unwrap();
+
+        // THEN it should replace the built-in spigot generator
+        assert_eq!("Spigot", name);
+        assert_eq!(vec!["spigot"], registry.names());
+        let parameters = registry.get("spigot").unwrap().parameters();
+        assert_eq!("metrics_id", parameters.last().unwrap().name);
+    }
+
+    #[test]
+    fn generate_should_fail_for_unknown_generator() {
+        // GIVEN an empty registry
+        let registry = GeneratorRegistry::new();
+
+        // WHEN we generate an unknown project type
+        let result = registry.generate("forge", &Parameters::new());
+
+        // THEN the generator should not be found
+        assert!(matches!(
+            result,
+            Err(GeneratorError::UnableToDetermineProjectGenerator)
+        ));
+    }
+
+    #[test]
+    fn load_template_directories_should_skip_broken_manifest() {
+        // GIVEN a folder with a valid template directory beside one with a broken manifest
+        let directory = std::env::temp_dir().join("mcdev-registry-broken");
+        let _ = std::fs::remove_dir_all(&directory);
+        std::fs::create_dir_all(directory.join("valid")).unwrap();
+        std::fs::create_dir_all(directory.join("broken")).unwrap();
+        std::fs::write(
+            directory.join("valid/manifest.toml"),
+            "name = \"homes\"\nextends = \"spigot\"\n",
+        )
+        .unwrap();
+        std::fs::write(directory.join("broken/manifest.toml"), "name = ").unwrap();
+        let mut registry = GeneratorRegistry::default();
+
+        // WHEN we load them
+        let warnings = registry.load_template_directories(&directory);
+
+        // THEN the broken one should only be reported, failing when it is selected
+        assert_eq!(1, warnings.len());
+        assert!(warnings[0].contains("broken"));
+        assert_eq!(vec!["homes", "spigot"], registry.names());
+        assert!(registry.find("spigot").is_ok());
+        assert!(matches!(
+            registry.find("Broken"),
+            Err(GeneratorError::BrokenGenerator(name, _)) if name == "Broken"
+        ));
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,750 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use std::{fs, io};
+
+use serde::Serialize;
+
+use crate::errors::GeneratorError;
+use crate::project_generator::build_model::GRADLE_BUILD_PATH;
+use crate::project_generator::config_spec::{
+    config_yml, merge_config_yml, validate_dotted_key, ConfigEntry, ConfigType,
+};
+use crate::project_generator::content_generator::{
+    COMMAND_JAVA_TEMPLATE, CONFIG_JAVA_TEMPLATE, LISTENER_JAVA_TEMPLATE,
+    RELOAD_COMMAND_JAVA_TEMPLATE, SOFT_HOOK_JAVA_TEMPLATE,
+};
+use crate::project_generator::event_catalog::{find_event, parse_event_priority, EventClass};
+use crate::project_generator::existing_project::ExistingProject;
+use crate::project_generator::library_catalog::{Library, PluginRequirement};
+use crate::project_generator::messages::add_message_to_files;
+use crate::project_generator::naming::{camel_case, pascal_case};
+use crate::project_generator::plugin_descriptor::{command_permission, CommandSpec};
+use crate::project_generator::template_engine::{render, Context, Value};
+use crate::project_generator::validation::{
+    invalid_parameter, validate_command_name, validate_java_identifier,
+};
+use crate::project_generator::{gradle_build, maven_pom};
+
+/// A method of a generated listener.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventHandlerSpec {
+    pub event: &'static EventClass,
+    pub priority: &'static str,
+    pub ignore_cancelled: bool,
+}
+
+impl EventHandlerSpec {
+    /// Parses `PlayerJoinEvent` or `BlockBreakEvent:HIGH`, the suffix overriding the default priority.
+    /// `ignoreCancelled` is only kept for cancellable events.
+    pub fn parse(
+        value: &str,
+        default_priority: &str,
+        ignore_cancelled: bool,
+    ) -> Result<Self, GeneratorError> {
+        let (name, priority) = value.split_once(':').unwrap_or((value, default_priority));
+        let event = find_event(name)?;
+        Ok(Self {
+            event,
+            priority: parse_event_priority(priority)?,
+            ignore_cancelled: ignore_cancelled && event.cancellable,
+        })
+    }
+
+    fn annotation(&self) -> String {
+        let mut options = Vec::new();
+        if self.priority != "NORMAL" {
+            options.push(format!("priority = EventPriority.{}", self.priority));
+        }
+        if self.ignore_cancelled {
+            options.push(String::from("ignoreCancelled = true"));
+        }
+        match options.is_empty() {
+            true => String::from("@EventHandler"),
+            false => format!("@EventHandler({})", options.join(", ")),
+        }
+    }
+}
+
+/// Adds a command executor and tab completer, declares the command and registers it.
+pub fn add_command(
+    project: &mut ExistingProject,
+    name: &str,
+    spec: CommandSpec,
+) -> Result<String, GeneratorError> {
+    let command = create_command(
+        project,
+        name,
+        spec,
+        COMMAND_JAVA_TEMPLATE,
+        Context::new(),
+        "",
+    )?;
+    let main_class = project.main_class_registering(&command.imports, &command.statements)?;
+
+    project.create_source(&command.path, &command.source)?;
+    project.write_main_class(&main_class)?;
+    project.save_plugin_yml()?;
+    Ok(command.class_name)
+}
+
+/// A class rendered by the scaffolding, not written yet, with what `onEnable` needs to register it.
+struct Registration {
+    class_name: String,
+    path: PathBuf,
+    source: String,
+    imports: Vec<String>,
+    statements: Vec<String>,
+}
+
+/// Declares the command and its permission in the `plugin.yml`, without saving it, and renders
+/// its class from the template, constructed with `arguments`.
+fn create_command(
+    project: &mut ExistingProject,
+    name: &str,
+    mut spec: CommandSpec,
+    template: &str,
+    mut context: Context,
+    arguments: &str,
+) -> Result<Registration, GeneratorError> {
+    validate_command_name(name)?;
+    let package = format!("{}.commands", project.package())
+        .trim_start_matches('.')
+        .to_string();
+    let class_name = format!("{}Command", pascal_case(name));
+
+    context
+        .insert("package", package.as_str())
+        .insert("class_name", class_name.as_str());
+    let path = project.java_path(&package, &class_name);
+    project.check_new_source(&path)?;
+    let source = render(template, &context)?;
+
+    spec.usage.get_or_insert_with(|| String::from("/<command>"));
+    let generated_permission = match spec.permission {
+        Some(_) => None,
+        None => Some(command_permission(project.plugin_name(), name)),
+    };
+    if let Some((permission, _)) = &generated_permission {
+        spec.permission = Some(permission.clone());
+    }
+    project.add_plugin_yml_entry("commands", name, yaml_value(&spec)?)?;
+    if let Some((permission, permission_spec)) = generated_permission {
+        let declared = project
+            .plugin_yml()
+            .get("permissions")
+            .and_then(|permissions| permissions.get(&permission))
+            .is_some();
+        if !declared {
+            project.add_plugin_yml_entry(
+                "permissions",
+                &permission,
+                yaml_value(&permission_spec)?,
+            )?;
+        }
+    }
+
+    let variable = camel_case(&class_name);
+    Ok(Registration {
+        imports: vec![format!("{}.{}", package, class_name)],
+        statements: vec![
+            format!(
+                "{} {} = new {}({});",
+                class_name, variable, class_name, arguments
+            ),
+            format!("getCommand(\"{}\").setExecutor({});", name, variable),
+            format!("getCommand(\"{}\").setTabCompleter({});", name, variable),
+        ],
+        class_name,
+        path,
+        source,
+    })
+}
+
+fn yaml_value<T: Serialize>(value: &T) -> Result<serde_yaml::Value, GeneratorError> {
+    serde_yaml::to_value(value).map_err(|error| GeneratorError::TemplateError(error.to_string()))
+}
+
+/// Creates a listener handling the events and registers it in `onEnable`.
+/// Without a class name, it is named after its only event or after the main class.
+pub fn add_listener(
+    project: &ExistingProject,
+    class_name: Option<&str>,
+    handlers: &[EventHandlerSpec],
+) -> Result<String, GeneratorError> {
+    let first = handlers
+        .first()
+        .ok_or_else(|| GeneratorError::MissingParameter(String::from("event")))?;
+    if let Some(duplicate) = handlers.iter().enumerate().find_map(|(index, handler)| {
+        handlers[..index]
+            .iter()
+            .any(|other| other.event == handler.event)
+            .then_some(handler.event.name)
+    }) {
+        return Err(invalid_parameter(
+            "event",
+            duplicate,
+            "it is listed twice",
+            duplicate.to_string(),
+        ));
+    }
+    let class_name = match class_name {
+        Some(class_name) => {
+            validate_java_identifier("listener", class_name)?;
+            class_name.to_string()
+        }
+        None if handlers.len() == 1 => format!(
+            "{}Listener",
+            first
+                .event
+                .name
+                .strip_suffix("Event")
+                .unwrap_or(first.event.name)
+        ),
+        None => format!("{}Listener", project.main_class()),
+    };
+    let package = format!("{}.listeners", project.package())
+        .trim_start_matches('.')
+        .to_string();
+
+    let mut imports: BTreeSet<String> = BTreeSet::from([
+        String::from("org.bukkit.event.EventHandler"),
+        String::from("org.bukkit.event.Listener"),
+    ]);
+    let mut methods = Vec::new();
+    for handler in handlers {
+        imports.insert(handler.event.qualified_name());
+        if handler.priority != "NORMAL" {
+            imports.insert(String::from("org.bukkit.event.EventPriority"));
+        }
+        methods.push(Value::from(BTreeMap::from([
+            (
+                String::from("annotation"),
+                Value::from(handler.annotation()),
+            ),
+            (
+                String::from("method"),
+                Value::from(handler.event.handler_name()),
+            ),
+            (String::from("event"), Value::from(handler.event.name)),
+        ])));
+    }
+
+    let mut context = Context::new();
+    context
+        .insert("package", package.as_str())
+        .insert("class_name", class_name.as_str())
+        .insert("imports", imports.into_iter().collect::<Vec<_>>())
+        .insert("handlers", methods);
+    let path = project.java_path(&package, &class_name);
+    project.check_new_source(&path)?;
+    let source = render(LISTENER_JAVA_TEMPLATE, &context)?;
+    let main_class = project.main_class_registering(
+        &[format!("{}.{}", package, class_name)],
+        &[format!(
+            "getServer().getPluginManager().registerEvents(new {}(), this);",
+            class_name
+        )],
+    )?;
+
+    project.create_source(&path, &source)?;
+    project.write_main_class(&main_class)?;
+    Ok(class_name)
+}
+
+/// Adds the defaults to the `config.yml`, creating it if needed, a typed class loading it and a command reloading it.
+/// The class is named after the main class and the command after the plugin unless given.
+pub fn add_config(
+    project: &mut ExistingProject,
+    entries: &[ConfigEntry],
+    class_name: Option<&str>,
+    command: Option<&str>,
+) -> Result<String, GeneratorError> {
+    let class_name = match class_name {
+        Some(class_name) => {
+            validate_java_identifier("config class", class_name)?;
+            class_name.to_string()
+        }
+        None => format!("{}Config", project.main_class()),
+    };
+    let package = format!("{}.config", project.package())
+        .trim_start_matches('.')
+        .to_string();
+    let variable = camel_case(&class_name);
+    let config_path = project.resource_path("config.yml");
+    let config = match fs::read_to_string(&config_path) {
+        Ok(existing) => merge_config_yml(&existing, entries)?,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => config_yml(entries)?,
+        Err(error) => {
+            return Err(GeneratorError::InvalidProject(
+                config_path.display().to_string(),
+                error.to_string(),
+            ))
+        }
+    };
+    let command = match command {
+        Some(command) => command.to_string(),
+        None => project
+            .plugin_name()
+            .to_lowercase()
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+            .collect(),
+    };
+
+    let mut command_context = Context::new();
+    command_context
+        .insert("config_package", package.as_str())
+        .insert("config_class", class_name.as_str());
+    let reload_command = create_command(
+        project,
+        &command,
+        CommandSpec {
+            description: Some(String::from("Reloads the configuration")),
+            usage: Some(String::from("/<command> reload")),
+            ..CommandSpec::default()
+        },
+        RELOAD_COMMAND_JAVA_TEMPLATE,
+        command_context,
+        &variable,
+    )?;
+
+    let mut imports = BTreeSet::from([
+        String::from("org.bukkit.configuration.file.FileConfiguration"),
+        String::from("org.bukkit.plugin.java.JavaPlugin"),
+    ]);
+    let mut types = BTreeMap::new();
+    for entry in entries {
+        types.insert(entry.kind.name().to_string(), Value::from(true));
+        if entry.kind == ConfigType::List {
+            imports.insert(String::from("java.util.Arrays"));
+            imports.insert(String::from("java.util.List"));
+        }
+    }
+    let mut context = Context::new();
+    context
+        .insert("package", package.as_str())
+        .insert("class_name", class_name.as_str())
+        .insert("imports", imports.into_iter().collect::<Vec<_>>())
+        .insert(
+            "entries",
+            entries
+                .iter()
+                .map(ConfigEntry::context_value)
+                .collect::<Vec<_>>(),
+        )
+        .insert("types", types);
+    let path = project.java_path(&package, &class_name);
+    project.check_new_source(&path)?;
+    let source = render(CONFIG_JAVA_TEMPLATE, &context)?;
+
+    let mut imports = vec![format!("{}.{}", package, class_name)];
+    imports.extend(reload_command.imports);
+    let mut statements = vec![
+        String::from("saveDefaultConfig();"),
+        format!("{} {} = new {}(this);", class_name, variable, class_name),
+        format!("{}.load();", variable),
+    ];
+    statements.extend(reload_command.statements);
+    let main_class = project.main_class_registering(&imports, &statements)?;
+
+    project.write_resource("config.yml", &config)?;
+    project.create_source(&path, &source)?;
+    project.create_source(&reload_command.path, &reload_command.source)?;
+    project.write_main_class(&main_class)?;
+    project.save_plugin_yml()?;
+    Ok(class_name)
+}
+
+/// Appends the message to every `messages_<locale>.yml`, `translations` giving the text of some
+/// locales. Returns the updated locales.
+pub fn add_message(
+    project: &ExistingProject,
+    key: &str,
+    text: &str,
+    translations: &BTreeMap<String, String>,
+) -> Result<Vec<String>, GeneratorError> {
+    validate_dotted_key("message key", key)?;
+    let files = project.message_files()?;
+    if files.is_empty() {
+        return Err(GeneratorError::InvalidProject(
+            project.root().display().to_string(),
+            String::from("it has no messages_<locale>.yml, generate it with --locales"),
+        ));
+    }
+    if let Some(locale) = translations
+        .keys()
+        .find(|locale| !files.iter().any(|(l, _)| l == *locale))
+    {
+        return Err(GeneratorError::InvalidProject(
+            project.root().display().to_string(),
+            format!("it has no messages_{}.yml", locale),
+        ));
+    }
+
+    let updated = add_message_to_files(&files, key, text, translations)?;
+    for (locale, content) in &updated {
+        project.write_resource(&format!("messages_{}.yml", locale), content)?;
+    }
+    Ok(updated.into_iter().map(|(locale, _)| locale).collect())
+}
+
+/// Declares the library in the `pom.xml` or `build.gradle.kts` and its plugin in the `plugin.yml`
+/// `depend` or `softdepend`. With `hook`, also creates `<package>.hooks.<Plugin>Hook` checking the
+/// plugin is enabled. Returns the hook class name.
+pub fn add_dependency(
+    project: &mut ExistingProject,
+    library: &Library,
+    hook: bool,
+) -> Result<Option<String>, GeneratorError> {
+    let (build_path, build) = project.read_build()?;
+    let hook_plugin = match (hook, library.plugin()) {
+        (false, _) => None,
+        (true, Some(plugin)) => Some(plugin),
+        (true, None) => {
+            return Err(invalid_parameter(
+                "hook",
+                library.id,
+                "the library is bundled in the jar, not provided by a plugin",
+                String::from("vault"),
+            ))
+        }
+    };
+    let dependency = library.dependency();
+    let build = match build_path {
+        GRADLE_BUILD_PATH => gradle_build::add_dependency(&build, &dependency, project.package())?,
+        _ => maven_pom::add_dependency(&build, &dependency, project.package())?,
+    };
+
+    let mut hook_source = None;
+    if let Some(plugin) = hook_plugin {
+        let class_name = format!(
+            "{}Hook",
+            plugin
+                .chars()
+                .filter(char::is_ascii_alphanumeric)
+                .collect::<String>()
+        );
+        let package = format!("{}.hooks", project.package())
+            .trim_start_matches('.')
+            .to_string();
+        let mut context = Context::new();
+        context
+            .insert("package", package.as_str())
+            .insert("class_name", class_name.as_str())
+            .insert("plugin", plugin);
+        let path = project.java_path(&package, &class_name);
+        project.check_new_source(&path)?;
+        hook_source = Some((class_name, path, render(SOFT_HOOK_JAVA_TEMPLATE, &context)?));
+    }
+    match library.requirement {
+        PluginRequirement::None => {}
+        PluginRequirement::Depend(plugin) => {
+            project.add_plugin_yml_list_entry("depend", plugin)?;
+        }
+        PluginRequirement::SoftDepend(plugin) => {
+            project.add_plugin_yml_list_entry("softdepend", plugin)?;
+        }
+    }
+
+    project.write_build(build_path, &build)?;
+    project.save_plugin_yml()?;
+    match hook_source {
+        Some((class_name, path, source)) => {
+            project.create_source(&path, &source)?;
+            Ok(Some(class_name))
+        }
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project_generator::config_spec::parse_config_spec;
+    use crate::project_generator::library_catalog::find_library;
+    use crate::project_generator::spigot::{SpigotGenerator, SpigotProject};
+    use crate::project_generator::{Parameters, ProjectGenerator};
+    use std::fs;
+    use std::path::Path;
+
+    #[test]
+    fn add_command_should_scaffold_and_register_command() {
+        // GIVEN a generated spigot project
+        let project = SpigotProject::new(
+            String::from("ScaffoldCommand"),
+            String::from("1.21"),
+            String::from("com.test"),
+            None,
+        );
+        project.generate_project().unwrap();
+        let mut existing = ExistingProject::open("scaffoldcommand".as_ref()).unwrap();
+
+        // WHEN we add a command
+        let spec = CommandSpec {
+            description: Some(String::from("Teleport home")),
+            ..CommandSpec::default()
+        };
+        let class_name = add_command(&mut existing, "home", spec).unwrap();
+
+        // THEN the executor should be created in the commands package
+        assert_eq!("HomeCommand", class_name);
+        let command =
+            fs::read_to_string("scaffoldcommand/src/main/java/com/test/commands/HomeCommand.java")
+                .unwrap();
+        assert!(command.starts_with("package com.test.commands;\n"));
+        assert!(command.contains("implements CommandExecutor, TabCompleter"));
+
+        // AND the command should be declared in the plugin.yml
+        let plugin_yml =
+            fs::read_to_string("scaffoldcommand/src/main/resources/plugin.yml").unwrap();
+        assert!(plugin_yml.ends_with(
+            "commands:\n  home:\n    description: Teleport home\n    usage: /<command>\n    permission: scaffoldcommand.command.home\npermissions:\n  scaffoldcommand.command.home:\n    description: Allows to use /home\n    default: op\n"
+        ));
+
+        // AND it should be registered in onEnable
+        let main =
+            fs::read_to_string("scaffoldcommand/src/main/java/com/test/ScaffoldCommand.java")
+                .unwrap();
+        assert!(main.contains("import com.test.commands.HomeCommand;\n"));
+        assert!(main.contains("        getCommand(\"home\").setExecutor(homeCommand);\n"));
+
+        // AND adding it again should be refused
+        let result = add_command(&mut existing, "home", CommandSpec::default());
+        assert!(matches!(result, Err(GeneratorError::InvalidProject(..))));
+
+        fs::remove_dir_all("scaffoldcommand").unwrap();
+    }
+
+    #[test]
+    fn add_command_should_write_nothing_without_on_enable() {
+        // GIVEN a generated spigot project whose main class has no onEnable
+        let project = SpigotProject::new(
+            String::from("ScaffoldNoEnable"),
+            String::from("1.21"),
+            String::from("com.test"),
+            None,
+        );
+        project.generate_project().unwrap();
+        let main_path = "scaffoldnoenable/src/main/java/com/test/ScaffoldNoEnable.java";
+        fs::write(
+            main_path,
+            "package com.test;\n\npublic class ScaffoldNoEnable extends JavaPlugin {\n}\n",
+        )
+        .unwrap();
+        let plugin_yml_path = "scaffoldnoenable/src/main/resources/plugin.yml";
+        let plugin_yml = fs::read_to_string(plugin_yml_path).unwrap();
+        let mut existing = ExistingProject::open("scaffoldnoenable".as_ref()).unwrap();
+
+        // WHEN we add a command
+        let result = add_command(&mut existing, "warp", CommandSpec::default());
+
+        // THEN it should fail without leaving the class nor the declaration behind
+        assert!(matches!(result, Err(GeneratorError::InvalidProject(..))));
+        assert!(
+            !Path::new("scaffoldnoenable/src/main/java/com/test/commands/WarpCommand.java")
+                .exists()
+        );
+        assert_eq!(plugin_yml, fs::read_to_string(plugin_yml_path).unwrap());
+
+        fs::remove_dir_all("scaffoldnoenable").unwrap();
+    }
+
+    #[test]
+    fn add_listener_should_scaffold_and_register_listener() {
+        // GIVEN a generated spigot project
+        let project = SpigotProject::new(
+            String::from("ScaffoldListener"),
+            String::from("1.21"),
+            String::from("com.test"),
+            None,
+        );
+        project.generate_project().unwrap();
+        let existing = ExistingProject::open("scaffoldlistener".as_ref()).unwrap();
+
+        // WHEN we add a listener for two events, ignoring cancelled ones
+        let handlers = vec![
+            EventHandlerSpec::parse("PlayerJoinEvent", "NORMAL", true).unwrap(),
+            EventHandlerSpec::parse("BlockBreakEvent:highest", "NORMAL", true).unwrap(),
+        ];
+        let class_name = add_listener(&existing, None, &handlers).unwrap();
+
+        // THEN the listener should import and handle both events
+        assert_eq!("ScaffoldListenerListener", class_name);
+        let listener = fs::read_to_string(
+            "scaffoldlistener/src/main/java/com/test/listeners/ScaffoldListenerListener.java",
+        )
+        .unwrap();
+        assert_eq!(
+            r#"package com.test.listeners;
+
+import org.bukkit.event.EventHandler;
+import org.bukkit.event.EventPriority;
+import org.bukkit.event.Listener;
+import org.bukkit.event.block.BlockBreakEvent;
+import org.bukkit.event.player.PlayerJoinEvent;
+
+public class ScaffoldListenerListener implements Listener {
+
+    @EventHandler
+    public void onPlayerJoin(PlayerJoinEvent event) {
+    }
+
+    @EventHandler(priority = EventPriority.HIGHEST, ignoreCancelled = true)
+    public void onBlockBreak(BlockBreakEvent event) {
+    }
+}
+"#,
+            listener
+        );
+
+        // AND it should be registered in onEnable
+        let main =
+            fs::read_to_string("scaffoldlistener/src/main/java/com/test/ScaffoldListener.java")
+                .unwrap();
+        assert!(main.contains("import com.test.listeners.ScaffoldListenerListener;\n"));
+        assert!(main.contains(
+            "        getServer().getPluginManager().registerEvents(new ScaffoldListenerListener(), this);\n"
+        ));
+
+        fs::remove_dir_all("scaffoldlistener").unwrap();
+    }
+
+    #[test]
+    fn event_handler_spec_should_reject_unknown_events() {
+        // GIVEN an event missing from the catalog
+        // WHEN we parse it
+        let result = EventHandlerSpec::parse("PlayerJumpingEvent", "NORMAL", false);
+
+        // THEN it should be rejected
+        assert!(matches!(
+            result,
+            Err(GeneratorError::InvalidParameter { .. })
+        ));
+    }
+
+    #[test]
+    fn add_config_should_scaffold_config_and_reload_command() {
+        // GIVEN a generated spigot project
+        let project = SpigotProject::new(
+            String::from("ScaffoldConfig"),
+            String::from("1.21"),
+            String::from("com.test"),
+            None,
+        );
+        project.generate_project().unwrap();
+        let mut existing = ExistingProject::open("scaffoldconfig".as_ref()).unwrap();
+
+        // WHEN we add a config
+        let entries =
+            parse_config_spec("spawn.world:string=world,max-homes:int=3,worlds:list=a|b").unwrap();
+        let class_name = add_config(&mut existing, &entries, None, None).unwrap();
+
+        // THEN the config.yml should hold the defaults
+        assert_eq!("ScaffoldConfigConfig", class_name);
+        let config_yml =
+            fs::read_to_string("scaffoldconfig/src/main/resources/config.yml").unwrap();
+        assert_eq!(
+            "spawn:\n  world: world\nmax-homes: 3\nworlds:\n- a\n- b\n",
+            config_yml
+        );
+
+        // AND the config class should read every key with its default
+        let config = fs::read_to_string(
+            "scaffoldconfig/src/main/java/com/test/config/ScaffoldConfigConfig.java",
+        )
+        .unwrap();
+        assert!(config.contains("import java.util.Arrays;\nimport java.util.List;\n"));
+        assert!(config.contains("        maxHomes = readInt(config, \"max-homes\", 3);\n"));
+        assert!(config.contains("    public List<String> getWorlds() {\n"));
+        assert!(config.contains("private String readString("));
+        assert!(!config.contains("private double readDouble("));
+
+        // AND the reload command should be declared and everything registered in onEnable
+        let plugin_yml =
+            fs::read_to_string("scaffoldconfig/src/main/resources/plugin.yml").unwrap();
+        assert!(plugin_yml.contains("  scaffoldconfig:\n    description: Reloads the configuration\n    usage: /<command> reload\n"));
+        let main = fs::read_to_string("scaffoldconfig/src/main/java/com/test/ScaffoldConfig.java")
+            .unwrap();
+        assert!(main.contains(
+            "        saveDefaultConfig();\n        ScaffoldConfigConfig scaffoldConfigConfig = new ScaffoldConfigConfig(this);\n        scaffoldConfigConfig.load();\n        ScaffoldconfigCommand scaffoldconfigCommand = new ScaffoldconfigCommand(scaffoldConfigConfig);\n"
+        ));
+
+        fs::remove_dir_all("scaffoldconfig").unwrap();
+    }
+
+    #[test]
+    fn add_message_should_append_to_every_locale() {
+        // GIVEN a project generated with english and french messages
+        let parameters = Parameters::from([
+            (String::from("name"), String::from("ScaffoldMessage")),
+            (String::from("version"), String::from("1.21")),
+            (String::from("group_id"), String::from("com.test")),
+            (String::from("locales"), String::from("en,fr")),
+        ]);
+        SpigotGenerator.generate(&parameters).unwrap();
+        let existing = ExistingProject::open("scaffoldmessage".as_ref()).unwrap();
+
+        // WHEN we add a message translated in french
+        let translations = BTreeMap::from([(String::from("fr"), String::from("Maison définie"))]);
+        let locales = add_message(&existing, "home.set", "Home set", &translations).unwrap();
+
+        // THEN both files should contain it
+        assert_eq!(vec!["en", "fr"], locales);
+        let english =
+            fs::read_to_string("scaffoldmessage/src/main/resources/messages_en.yml").unwrap();
+        let french =
+            fs::read_to_string("scaffoldmessage/src/main/resources/messages_fr.yml").unwrap();
+        assert!(english.ends_with("home:\n  set: Home set\n"));
+        assert!(french.ends_with("home:\n  set: Maison définie\n"));
+
+        // AND translations for missing locales should be refused
+        let translations = BTreeMap::from([(String::from("de"), String::from("Heim"))]);
+        let result = add_message(&existing, "home.delete", "Home deleted", &translations);
+        assert!(matches!(result, Err(GeneratorError::InvalidProject(..))));
+
+        fs::remove_dir_all("scaffoldmessage").unwrap();
+    }
+
+    #[test]
+    fn add_dependency_should_patch_pom_and_plugin_yml() {
+        // GIVEN a project generated with ProtocolLib
+        let parameters = Parameters::from([
+            (String::from("name"), String::from("ScaffoldDependency")),
+            (String::from("version"), String::from("1.21")),
+            (String::from("group_id"), String::from("com.test")),
+            (String::from("dependencies"), String::from("protocollib")),
+        ]);
+        SpigotGenerator.generate(&parameters).unwrap();
+        let mut project = ExistingProject::open("scaffolddependency".as_ref()).unwrap();
+        assert_eq!(
+            Some(&serde_yaml::Value::from(vec!["ProtocolLib"])),
+            project.plugin_yml().get("depend")
+        );
+
+        // WHEN we add Vault with a hook and bStats
+        let vault = find_library("vault").unwrap();
+        let hook = add_dependency(&mut project, vault, true).unwrap();
+        add_dependency(&mut project, find_library("bstats").unwrap(), false).unwrap();
+
+        // THEN the pom should declare them with the jitpack repository and bStats relocated
+        assert_eq!(Some(String::from("VaultHook")), hook);
+        let pom = fs::read_to_string("scaffolddependency/pom.xml").unwrap();
+        assert!(pom.contains("<url>https://jitpack.io</url>"));
+        assert!(pom.contains("<artifactId>VaultAPI</artifactId>"));
+        assert!(pom.contains("<shadedPattern>com.test.libs.bstats</shadedPattern>"));
+
+        // AND Vault should be a soft dependency with a hook class
+        let plugin_yml =
+            fs::read_to_string("scaffolddependency/src/main/resources/plugin.yml").unwrap();
+        assert!(plugin_yml.contains("softdepend:\n- Vault\n"));
+        let hook =
+            fs::read_to_string("scaffolddependency/src/main/java/com/test/hooks/VaultHook.java")
+                .unwrap();
+        assert!(hook.contains("getPlugin(\"Vault\")"));
+
+        // AND adding it again should be refused
+        assert!(add_dependency(&mut project, vault, false).is_err());
+
+        fs::remove_dir_all("scaffolddependency").unwrap();
+    }
+}
@@ -2,36 +2,246 @@ use std::env;
 
 use crate::errors::GeneratorError;
 use crate::project_generator::content_generator::{
-    generate_main_java_content, generate_plugin_yml_content, generate_pom_xml_content,
+    project_context, render_template_files, TemplateFile, DATABASE_JAVA_TEMPLATE,
+    FIRST_MIGRATION_SQL_TEMPLATE, LICENSE_TEMPLATE, MAIN_JAVA_TEMPLATE, MESSAGES_JAVA_TEMPLATE,
+    MIGRATION_RUNNER_JAVA_TEMPLATE, PLAYER_DAO_JAVA_TEMPLATE, PLUGIN_YML_TEMPLATE,
+    POM_XML_TEMPLATE,
 };
-use crate::project_generator::file_operations::{create_directory, create_file};
+use crate::project_generator::database::DatabaseType;
+use crate::project_generator::dependencies::{self, Dependency};
+use crate::project_generator::file_operations::{create_directory, create_file_with_parents};
+use crate::project_generator::library_catalog::{self, Library, PluginRequirement};
+use crate::project_generator::messages::MessagesOptions;
+use crate::project_generator::metrics::Metrics;
+use crate::project_generator::naming::ProjectNames;
+use crate::project_generator::plugin_descriptor::{default_api_version, PluginDescriptor};
+use crate::project_generator::project_info::ProjectInfo;
+use crate::project_generator::project_metadata::ProjectMetadata;
+use crate::project_generator::template_engine::Context;
+use crate::project_generator::validation::{
+    validate_artifact_id, validate_group_id, validate_java_identifier, validate_mod_id,
+    validate_plugin_name,
+};
+use crate::project_generator::{
+    ParameterDescriptor, Parameters, ProjectGenerator, ProjectGeneratorType, RenderedProject,
+};
+
+pub const SPIGOT_REPOSITORY: &str =
+    "https://hub.spigotmc.org/nexus/content/repositories/snapshots/";
+
+#[derive(Debug, Default, PartialEq)]
+pub struct SpigotGenerator;
+
+impl ProjectGenerator for SpigotGenerator {
+    fn parameters(&self) -> Vec<ParameterDescriptor> {
+        vec![
+            ParameterDescriptor::new("name", "Please provide a name for your project", true),
+            ParameterDescriptor::new("version", "Please provide a version for your project", true),
+            ParameterDescriptor::new(
+                "group_id",
+                "Please provide a group id for your project",
+                true,
+            ),
+            ParameterDescriptor::new(
+                "path",
+                "Please provide a path for your project, leave empty for current directory",
+                false,
+            ),
+            ParameterDescriptor::new("display_name", "Human readable project name", false),
+            ParameterDescriptor::new("artifact_id", "Maven artifact id, in kebab-case", false),
+            ParameterDescriptor::new("main_class", "Main class name, in PascalCase", false),
+            ParameterDescriptor::new("plugin_name", "Plugin name, without spaces", false),
+            ParameterDescriptor::new("mod_id", "Lowercase mod id", false),
+        ]
+        .into_iter()
+        .chain(ProjectInfo::parameters())
+        .chain(PluginDescriptor::parameters())
+        .chain(MessagesOptions::parameters())
+        .chain(DatabaseType::parameters())
+        .chain(Metrics::parameters())
+        .chain(library_catalog::parameters())
+        .collect()
+    }
+
+    fn validate(&self, parameters: &Parameters) -> Result<(), GeneratorError> {
+        for parameter in self.parameters().iter().filter(|p| p.mandatory) {
+            match parameters.get(&parameter.name) {
+                Some(value) if !value.trim().is_empty() => {}
+                _ => return Err(GeneratorError::MissingParameter(parameter.name.clone())),
+            }
+        }
+        for (name, value) in parameters {
+            self.validate_parameter(name, value)?;
+        }
+        ProjectNames::from_parameters(parameters)?.validate()
+    }
+
+    fn validate_parameter(&self, name: &str, value: &str) -> Result<(), GeneratorError> {
+        match name {
+            "group_id" => validate_group_id(value),
+            "artifact_id" => validate_artifact_id(value),
+            "main_class" => validate_java_identifier(name, value),
+            "plugin_name" => validate_plugin_name(value),
+            "mod_id" => validate_mod_id(value),
+            _ => ProjectInfo::validate_parameter(name, value)
+                .and_then(|_| PluginDescriptor::validate_parameter(name, value))
+                .and_then(|_| MessagesOptions::validate_parameter(name, value))
+                .and_then(|_| DatabaseType::validate_parameter(name, value))
+                .and_then(|_| Metrics::validate_parameter(name, value))
+                .and_then(|_| library_catalog::validate_parameter(name, value)),
+        }
+    }
+
+    fn render(&self, parameters: &Parameters) -> Result<RenderedProject, GeneratorError> {
+        let project = SpigotProject::from_parameters(parameters)?;
+        project.render(
+            &project.template_files(),
+            &Context::new(),
+            ProjectMetadata::new(ProjectGeneratorType::Spigot.name(), parameters),
+        )
+    }
+}
 
 #[derive(Debug, PartialEq)]
-pub struct SpigotGenerator {
-    name: String,
+pub struct SpigotProject {
+    names: ProjectNames,
+    info: ProjectInfo,
     version: String,
     group_id: String,
     path: String,
+    plugin: PluginDescriptor,
+    messages: Option<MessagesOptions>,
+    database: Option<DatabaseType>,
+    metrics: Option<Metrics>,
+    libraries: Vec<&'static Library>,
 }
 
-impl SpigotGenerator {
+impl SpigotProject {
     pub fn new(name: String, version: String, group_id: String, path: Option<String>) -> Self {
+        Self::with_names(ProjectNames::from_name(&name), version, group_id, path)
+    }
+
+    pub fn with_names(
+        names: ProjectNames,
+        version: String,
+        group_id: String,
+        path: Option<String>,
+    ) -> Self {
         Self {
-            name: Self::format_name(name),
+            plugin: PluginDescriptor {
+                api_version: default_api_version(&version),
+                ..PluginDescriptor::default()
+            },
+            names,
+            info: ProjectInfo::default(),
             version,
             group_id,
             path: Self::get_project_path(path),
+            messages: None,
+            database: None,
+            metrics: None,
+            libraries: Vec::new(),
+        }
+    }
+
+    pub fn from_parameters(parameters: &Parameters) -> Result<Self, GeneratorError> {
+        let mandatory = |name: &str| {
+            parameters
+                .get(name)
+                .cloned()
+                .ok_or_else(|| GeneratorError::MissingParameter(name.to_string()))
+        };
+
+        let names = ProjectNames::from_parameters(parameters)?;
+        let info = ProjectInfo::from_parameters(parameters);
+        let version = mandatory("version")?;
+        let group_id = mandatory("group_id")?;
+        let plugin =
+            PluginDescriptor::from_parameters(&names, &info, &group_id, &version, parameters)?;
+
+        Ok(
+            Self::with_names(names, version, group_id, parameters.get("path").cloned())
+                .with_info(info)
+                .with_plugin(plugin)
+                .with_messages(MessagesOptions::from_parameters(parameters)?)
+                .with_database(DatabaseType::from_parameters(parameters)?)
+                .with_metrics(Metrics::from_parameters(parameters)?)
+                .with_libraries(library_catalog::from_parameters(parameters)?),
+        )
+    }
+
+    pub fn with_info(mut self, info: ProjectInfo) -> Self {
+        self.info = info;
+        self
+    }
+
+    /// Sets the commands, permissions and load order of the `plugin.yml`.
+    pub fn with_plugin(mut self, plugin: PluginDescriptor) -> Self {
+        self.plugin = plugin;
+        self
+    }
+
+    /// Generates the `Messages` class and a messages file per locale.
+    pub fn with_messages(mut self, messages: Option<MessagesOptions>) -> Self {
+        self.messages = messages;
+        self
+    }
+
+    /// Generates the HikariCP connection pool, the migration runner and an example DAO.
+    pub fn with_database(mut self, database: Option<DatabaseType>) -> Self {
+        self.database = database;
+        self
+    }
+
+    /// Starts bStats metrics in the main class.
+    pub fn with_metrics(mut self, metrics: Option<Metrics>) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// Adds libraries from the catalog, declaring the plugins providing them in the `plugin.yml`.
+    pub fn with_libraries(mut self, libraries: Vec<&'static Library>) -> Self {
+        self.libraries = libraries;
+        self
+    }
+
+    /// The build dependencies, starting with the Spigot API.
+    pub fn dependencies(&self) -> Vec<Dependency> {
+        let mut dependencies =
+            vec![
+                Dependency::provided("org.spigotmc", "spigot-api", "${spigot.version}")
+                    .with_repository("spigot-repo", SPIGOT_REPOSITORY),
+            ];
+        dependencies.extend(self.messages.iter().flat_map(MessagesOptions::dependencies));
+        dependencies.extend(self.database.iter().flat_map(DatabaseType::dependencies));
+        dependencies.extend(self.metrics.iter().flat_map(Metrics::dependencies));
+        for library in &self.libraries {
+            let dependency = library.dependency();
+            if !dependencies.iter().any(|d| {
+                d.group_id == dependency.group_id && d.artifact_id == dependency.artifact_id
+            }) {
+                dependencies.push(dependency);
+            }
         }
+        dependencies
     }
 
-    fn format_name(name: String) -> String {
-        name.split(|c: char| !c.is_alphanumeric())
-            .filter(|s| !s.is_empty())
-            .map(|s| {
-                let mut chars = s.chars();
-                chars.next().unwrap().to_uppercase().collect::<String>() + chars.as_str()
-            })
-            .collect()
+    pub fn plugin_descriptor(&self) -> PluginDescriptor {
+        let mut plugin = self
+            .plugin
+            .clone()
+            .with_project(&self.names, &self.info, &self.group_id);
+        for library in &self.libraries {
+            let (list, name) = match library.requirement {
+                PluginRequirement::None => continue,
+                PluginRequirement::Depend(name) => (&mut plugin.depend, name),
+                PluginRequirement::SoftDepend(name) => (&mut plugin.softdepend, name),
+            };
+            if !list.iter().any(|entry| entry == name) {
+                list.push(name.to_string());
+            }
+        }
+        plugin
     }
 
     fn get_project_path(path: Option<String>) -> String {
@@ -50,40 +260,117 @@ impl SpigotGenerator {
         }
     }
 
+    pub fn template_files(&self) -> Vec<TemplateFile> {
+        let mut files = vec![
+            TemplateFile::new("pom.xml", POM_XML_TEMPLATE),
+            TemplateFile::new("src/main/resources/plugin.yml", PLUGIN_YML_TEMPLATE),
+            TemplateFile::new(
+                "src/main/java/{{ group_id | package_path }}/{{ main_class }}.java",
+                MAIN_JAVA_TEMPLATE,
+            ),
+            TemplateFile::new("LICENSE", LICENSE_TEMPLATE).when("license"),
+            TemplateFile::new(
+                "src/main/java/{{ group_id | package_path }}/messages/Messages.java",
+                MESSAGES_JAVA_TEMPLATE,
+            )
+            .when("messages"),
+            TemplateFile::new("src/main/resources/config.yml", "{{ config_yml }}").when("database"),
+            TemplateFile::new(
+                "src/main/java/{{ group_id | package_path }}/database/Database.java",
+                DATABASE_JAVA_TEMPLATE,
+            )
+            .when("database"),
+            TemplateFile::new(
+                "src/main/java/{{ group_id | package_path }}/database/MigrationRunner.java",
+                MIGRATION_RUNNER_JAVA_TEMPLATE,
+            )
+            .when("database"),
+            TemplateFile::new(
+                "src/main/java/{{ group_id | package_path }}/database/PlayerDao.java",
+                PLAYER_DAO_JAVA_TEMPLATE,
+            )
+            .when("database"),
+            TemplateFile::new(
+                "src/main/resources/migrations/V1__create_players.sql",
+                FIRST_MIGRATION_SQL_TEMPLATE,
+            )
+            .when("database"),
+        ];
+        for locale in self.messages.iter().flat_map(|messages| &messages.locales) {
+            files.push(TemplateFile::new(
+                &format!("src/main/resources/messages_{}.yml", locale),
+                "{{ messages_yml }}",
+            ));
+        }
+        files
+    }
+
+    pub fn context(&self) -> Result<Context, GeneratorError> {
+        let mut context = project_context(&self.names, &self.info, &self.version, &self.group_id);
+        context.insert("plugin_yml", self.plugin_descriptor().to_yaml()?);
+        if let Some(messages) = &self.messages {
+            messages.extend_context(&mut context, &self.names.plugin_name)?;
+        }
+        if let Some(database) = &self.database {
+            database.extend_context(&mut context);
+            let config = database.config(&self.names.plugin_name.to_lowercase());
+            context.insert(
+                "config_yml",
+                serde_yaml::to_string(&config)
+                    .map_err(|error| GeneratorError::TemplateError(error.to_string()))?,
+            );
+        }
+        if let Some(metrics) = &self.metrics {
+            metrics.extend_context(&mut context);
+        }
+        dependencies::extend_context(&mut context, &self.dependencies(), &self.group_id)?;
+        Ok(context)
+    }
+
     pub fn generate_project(&self) -> Result<(), GeneratorError> {
-        let project_name = self.name.to_lowercase();
+        self.generate_files(&self.template_files(), &Context::new())
+    }
+
+    pub fn generate_files(
+        &self,
+        files: &[TemplateFile],
+        extra_context: &Context,
+    ) -> Result<(), GeneratorError> {
+        let project_name = self.names.folder();
         create_directory(&project_name)?;
 
-        create_file(
-            &format!("{}/pom.xml", project_name),
-            &generate_pom_xml_content(&self.name, &self.version, &self.group_id),
-        )?;
-
-        let resources_path = format!("{}/src/main/resources", project_name);
-        create_directory(&resources_path)?;
-        create_file(
-            &format!("{}/plugin.yml", resources_path),
-            &generate_plugin_yml_content(&self.name, &self.group_id),
-        )?;
-
-        let java_path = format!(
-            "{}/src/main/java/{}",
-            project_name,
-            self.group_id.replace(".", "/")
-        );
-        create_directory(&java_path)?;
-        create_file(
-            &format!("{}/{}.java", java_path, self.name),
-            &generate_main_java_content(&self.name, &self.group_id),
-        )?;
+        let mut context = self.context()?;
+        context.extend(extra_context);
+        for (path, content) in render_template_files(files, &context)? {
+            create_file_with_parents(&format!("{}/{}", project_name, path), &content)?;
+        }
 
         Ok(())
     }
+
+    /// Renders the files in the project folder, the metadata recording a Maven build in Java.
+    pub fn render(
+        &self,
+        files: &[TemplateFile],
+        extra_context: &Context,
+        metadata: ProjectMetadata,
+    ) -> Result<RenderedProject, GeneratorError> {
+        let mut context = self.context()?;
+        context.extend(extra_context);
+        Ok(RenderedProject {
+            root: self.names.folder(),
+            files: render_template_files(files, &context)?,
+            metadata: metadata.with_build("maven", "java"),
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::project_generator::content_generator::{
+        generate_main_java_content, generate_plugin_yml_content, generate_pom_xml_content,
+    };
     use std::fs;
     use std::path::Path;
 
@@ -93,7 +380,7 @@ mod tests {
     #[test]
     fn generate_project_should_generate_pom_xml_file() {
         // GIVEN a spigot generator;
-        let spigot_generator = SpigotGenerator::new(
+        let spigot_generator = SpigotProject::new(
             String::from("TestOne"),
             String::from("1.8.8"),
             String::from("com.test"),
@@ -106,7 +393,7 @@ mod tests {
         // THEN the project folder should contain a pom.xml file
         assert!(result.is_ok(), "Project generation failed");
 
-        let name_in_lowercase = spigot_generator.name.to_lowercase();
+        let name_in_lowercase = spigot_generator.names.folder();
         let project_path = Path::new(&name_in_lowercase);
         assert!(
             project_path.exists() && project_path.is_dir(),
@@ -123,10 +410,13 @@ mod tests {
         let pom_xml_content =
             fs::read_to_string(pom_file_path).expect("Unable to read pom.xml file");
         let expected_pom_xml_content = generate_pom_xml_content(
-            &spigot_generator.name,
+            &spigot_generator.names,
+            &spigot_generator.info,
             &spigot_generator.version,
             &spigot_generator.group_id,
-        );
+            &spigot_generator.dependencies(),
+        )
+        .unwrap();
         assert_eq!(pom_xml_content, expected_pom_xml_content);
 
         // Clean up
@@ -136,7 +426,7 @@ mod tests {
     #[test]
     fn generate_project_should_generate_plugin_yml_file() {
         // GIVEN a spigot generator;
-        let spigot_generator = SpigotGenerator::new(
+        let spigot_generator = SpigotProject::new(
             String::from("TestThree"),
             String::from("1.21"),
             String::from("com.test"),
@@ -149,7 +439,7 @@ mod tests {
         // THEN the project folder should contain a main java file
         assert!(result.is_ok(), "Project generation failed");
 
-        let name_in_lowercase = spigot_generator.name.to_lowercase();
+        let name_in_lowercase = spigot_generator.names.folder();
         let project_path = Path::new(&name_in_lowercase);
         let plugin_yml_file_path = project_path.join("src/main/resources/plugin.yml");
         assert!(
@@ -161,7 +451,7 @@ mod tests {
         let plugin_yml_content =
             fs::read_to_string(plugin_yml_file_path).expect("Unable to read plugin.yml file");
         let expected_plugin_yml_content =
-            generate_plugin_yml_content(&spigot_generator.name, &spigot_generator.group_id);
+            generate_plugin_yml_content(&spigot_generator.plugin_descriptor()).unwrap();
         assert_eq!(plugin_yml_content, expected_plugin_yml_content);
 
         // Clean up
@@ -171,7 +461,7 @@ mod tests {
     #[test]
     fn generate_project_should_generate_main_java_file() {
         // GIVEN a spigot generator;
-        let spigot_generator = SpigotGenerator::new(
+        let spigot_generator = SpigotProject::new(
             String::from("TestTwo"),
             String::from("1.21"),
             String::from("com.test"),
@@ -184,7 +474,7 @@ mod tests {
         // THEN the project folder should contain a main java file
         assert!(result.is_ok(), "Project generation failed");
 
-        let name_in_lowercase = spigot_generator.name.to_lowercase();
+        let name_in_lowercase = spigot_generator.names.folder();
         let project_path = Path::new(&name_in_lowercase);
         let main_java_file_path = project_path.join("src/main/java/com/test/TestTwo.java");
         assert!(
@@ -196,32 +486,21 @@ mod tests {
         let main_java_content =
             fs::read_to_string(main_java_file_path).expect("Unable to read main java file");
         let expected_main_java_content =
-            generate_main_java_content(&spigot_generator.name, &spigot_generator.group_id);
+            generate_main_java_content(&spigot_generator.names, &spigot_generator.group_id, None)
+                .unwrap();
         assert_eq!(main_java_content, expected_main_java_content);
 
         // Clean up
         clean_up(&name_in_lowercase);
     }
 
-    #[test]
-    fn parse_name_should_parse_into_pascal_case() {
-        // GIVEN a name
-        let name = String::from("ezezz-ezfze_zefze=ff:pofkj");
-
-        // WHEN we parsed the name
-        let parsed_name = SpigotGenerator::format_name(name);
-
-        // THEN the name should look like so
-        assert_eq!("EzezzEzfzeZefzeFfPofkj".to_string(), parsed_name)
-    }
-
     #[test]
     fn get_project_path_should_return_test_folder_path_from_relative_path() {
         // GIVEN a path
         let path = Some(String::from("./test"));
 
         // WHEN we get the path
-        let path = SpigotGenerator::get_project_path(path);
+        let path = SpigotProject::get_project_path(path);
 
         // THEN the path should be this
         assert_eq!(
@@ -236,7 +515,7 @@ mod tests {
         let path = Some(String::from("/test"));
 
         // WHEN we get the path
-        let path = SpigotGenerator::get_project_path(path);
+        let path = SpigotProject::get_project_path(path);
 
         // THEN the path should be this
         assert_eq!("/test", path);
@@ -248,7 +527,7 @@ mod tests {
         let path = Some(String::from(""));
 
         // WHEN we get the path
-        let path = SpigotGenerator::get_project_path(path);
+        let path = SpigotProject::get_project_path(path);
 
         // THEN the path should be this
         assert_eq!(
@@ -263,7 +542,7 @@ mod tests {
         let path = None;
 
         // WHEN we get the path
-        let path = SpigotGenerator::get_project_path(path);
+        let path = SpigotProject::get_project_path(path);
 
         // THEN the path should be this
         assert_eq!(
@@ -271,4 +550,325 @@ mod tests {
             path
         );
     }
+
+    #[test]
+    fn validate_should_reject_empty_mandatory_parameter() {
+        // GIVEN parameters with an empty group id
+        let parameters = Parameters::from([
+            (String::from("name"), String::from("TestFour")),
+            (String::from("version"), String::from("1.21")),
+            (String::from("group_id"), String::from(" ")),
+        ]);
+
+        // WHEN we validate them
+        let result = SpigotGenerator.validate(&parameters);
+
+        // THEN the group id should be reported as missing
+        assert!(
+            matches!(result, Err(GeneratorError::MissingParameter(name)) if name == "group_id")
+        );
+    }
+
+    #[test]
+    fn validate_should_reject_invalid_group_id() {
+        // GIVEN parameters with a group id that is not a valid java package
+        let parameters = Parameters::from([
+            (String::from("name"), String::from("TestFour")),
+            (String::from("version"), String::from("1.21")),
+            (String::from("group_id"), String::from("com.class")),
+        ]);
+
+        // WHEN we validate them
+        let result = SpigotGenerator.validate(&parameters);
+
+        // THEN the group id should be rejected
+        assert!(matches!(result, Err(GeneratorError::InvalidGroupId { .. })));
+    }
+
+    #[test]
+    fn validate_should_accept_short_and_accented_names() {
+        // GIVEN a one letter name and a name with an accent
+        let parameters = |name: &str| {
+            Parameters::from([
+                (String::from("name"), name.to_string()),
+                (String::from("version"), String::from("1.21")),
+                (String::from("group_id"), String::from("com.test")),
+            ])
+        };
+
+        // WHEN we validate them
+        // THEN the derived names should be accepted
+        assert!(SpigotGenerator.validate(&parameters("X")).is_ok());
+        assert!(SpigotGenerator.validate(&parameters("Café Plugin")).is_ok());
+    }
+
+    #[test]
+    fn validate_should_reject_invalid_name_override() {
+        // GIVEN parameters overriding the artifact id with spaces
+        let parameters = Parameters::from([
+            (String::from("name"), String::from("TestFour")),
+            (String::from("version"), String::from("1.21")),
+            (String::from("group_id"), String::from("com.test")),
+            (String::from("artifact_id"), String::from("Test Four")),
+        ]);
+
+        // WHEN we validate them
+        let result = SpigotGenerator.validate(&parameters);
+
+        // THEN the artifact id should be rejected
+        assert!(matches!(
+            result,
+            Err(GeneratorError::InvalidParameter { name, .. }) if name == "artifact_id"
+        ));
+    }
+
+    #[test]
+    fn generate_project_should_use_overridden_main_class() {
+        // GIVEN a spigot project with a custom main class
+        let mut names = ProjectNames::from_name("Test Five");
+        names.main_class = String::from("TestFiveMain");
+        let spigot_generator =
+            SpigotProject::with_names(names, String::from("1.21"), String::from("com.test"), None);
+
+        // WHEN we generate the project
+        let result = spigot_generator.generate_project();
+
+        // THEN the main class file should be named after it
+        assert!(result.is_ok(), "Project generation failed");
+        let main_java_file_path =
+            Path::new("testfivemain/src/main/java/com/test/TestFiveMain.java");
+        assert!(
+            main_java_file_path.is_file(),
+            "Main java file does not exist"
+        );
+
+        // Clean up
+        clean_up("testfivemain");
+    }
+
+    #[test]
+    fn generate_project_should_generate_license_file() {
+        // GIVEN a spigot project with a license
+        let spigot_generator = SpigotProject::new(
+            String::from("TestSix"),
+            String::from("1.21"),
+            String::from("com.test"),
+            None,
+        )
+        .with_info(ProjectInfo {
+            authors: vec![String::from("Notch")],
+            license: Some(String::from("MIT")),
+            ..ProjectInfo::default()
+        });
+
+        // WHEN we generate the project
+        let result = spigot_generator.generate_project();
+
+        // THEN a LICENSE file should be generated for the authors
+        assert!(result.is_ok(), "Project generation failed");
+        let license = fs::read_to_string("testsix/LICENSE").expect("Unable to read LICENSE file");
+        assert!(license.starts_with("MIT License"));
+        assert!(license.contains("Notch"));
+
+        // Clean up
+        clean_up("testsix");
+    }
+
+    #[test]
+    fn validate_should_accept_parameters_without_path() {
+        // GIVEN parameters without the optional path
+        let parameters = Parameters::from([
+            (String::from("name"), String::from("TestFour")),
+            (String::from("version"), String::from("1.21")),
+            (String::from("group_id"), String::from("com.test")),
+        ]);
+
+        // WHEN we validate them
+        let result = SpigotGenerator.validate(&parameters);
+
+        // THEN the parameters should be valid
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn generate_should_write_plugin_sections_from_parameters() {
+        // GIVEN parameters declaring commands, dependencies and a load order
+        let parameters = Parameters::from([
+            (String::from("name"), String::from("TestSeven")),
+            (String::from("version"), String::from("1.20.6")),
+            (String::from("group_id"), String::from("com.test")),
+            (String::from("load"), String::from("STARTUP")),
+            (String::from("depend"), String::from("Vault")),
+            (String::from("commands"), String::from("warp")),
+        ]);
+
+        // WHEN we generate the project
+        let result = SpigotGenerator.generate(&parameters);
+
+        // THEN the plugin.yml should declare them
+        assert!(result.is_ok(), "Project generation failed");
+        let plugin_yml = fs::read_to_string("testseven/src/main/resources/plugin.yml")
+            .expect("Unable to read plugin.yml file");
+        assert!(plugin_yml.contains("api-version: '1.20'\nload: STARTUP\ndepend:\n- Vault\n"));
+        assert!(plugin_yml.contains("commands:\n  warp:\n    usage: /<command>\n"));
+
+        // Clean up
+        clean_up("testseven");
+    }
+
+    #[test]
+    fn generate_should_write_messages_for_every_locale() {
+        // GIVEN parameters requesting french messages with MiniMessage
+        let parameters = Parameters::from([
+            (String::from("name"), String::from("TestEight")),
+            (String::from("version"), String::from("1.21")),
+            (String::from("group_id"), String::from("com.test")),
+            (String::from("locales"), String::from("fr")),
+            (String::from("message_format"), String::from("minimessage")),
+        ]);
+
+        // WHEN we generate the project
+        let result = SpigotGenerator.generate(&parameters);
+
+        // THEN english and french messages should be generated
+        assert!(result.is_ok(), "Project generation failed");
+        let english = fs::read_to_string("testeight/src/main/resources/messages_en.yml").unwrap();
+        let french = fs::read_to_string("testeight/src/main/resources/messages_fr.yml").unwrap();
+        assert!(english.contains("prefix: '<gray>[<aqua>TestEight</aqua>]</gray> '\n"));
+        assert_eq!(english, french);
+
+        // AND the main class should load them
+        let project = SpigotProject::from_parameters(&parameters).unwrap();
+        let main = fs::read_to_string("testeight/src/main/java/com/test/TestEight.java").unwrap();
+        assert_eq!(
+            generate_main_java_content(&project.names, "com.test", project.messages.as_ref())
+                .unwrap(),
+            main
+        );
+        assert!(main.contains("        messages = new Messages(this);\n"));
+        assert!(main.contains("        messages.close();\n"));
+        let messages =
+            fs::read_to_string("testeight/src/main/java/com/test/messages/Messages.java").unwrap();
+        assert!(messages.contains("private static final String[] LOCALES = { \"en\", \"fr\" };"));
+        assert!(messages.contains("MiniMessage.miniMessage()"));
+        assert!(!messages.contains("ChatColor"));
+
+        // AND the adventure libraries should be shaded and relocated once
+        let pom = fs::read_to_string("testeight/pom.xml").unwrap();
+        assert!(pom.contains("<artifactId>adventure-platform-bukkit</artifactId>\n            <version>4.3.4</version>\n            <scope>compile</scope>"));
+        assert_eq!(
+            1,
+            pom.matches("<shadedPattern>com.test.libs.kyori</shadedPattern>")
+                .count()
+        );
+        let plugin_yml = fs::read_to_string("testeight/src/main/resources/plugin.yml").unwrap();
+        assert!(!plugin_yml.contains("libraries"));
+
+        // Clean up
+        clean_up("testeight");
+    }
+
+    #[test]
+    fn generate_should_write_database_layer() {
+        // GIVEN parameters requesting a sqlite database
+        let parameters = Parameters::from([
+            (String::from("name"), String::from("TestNine")),
+            (String::from("version"), String::from("1.21")),
+            (String::from("group_id"), String::from("com.test")),
+            (String::from("database"), String::from("sqlite")),
+        ]);
+
+        // WHEN we generate the project
+        let result = SpigotGenerator.generate(&parameters);
+
+        // THEN the pool, migration runner, DAO and first migration should be generated
+        assert!(result.is_ok(), "Project generation failed");
+        for path in [
+            "src/main/java/com/test/database/Database.java",
+            "src/main/java/com/test/database/MigrationRunner.java",
+            "src/main/java/com/test/database/PlayerDao.java",
+            "src/main/resources/migrations/V1__create_players.sql",
+        ] {
+            assert!(
+                Path::new("testnine").join(path).is_file(),
+                "{} is missing",
+                path
+            );
+        }
+        let config = fs::read_to_string("testnine/src/main/resources/config.yml").unwrap();
+        assert!(config.starts_with("database:\n  type: sqlite\n"));
+
+        // AND HikariCP should be shaded and relocated under the group id
+        let pom = fs::read_to_string("testnine/pom.xml").unwrap();
+        assert!(pom.contains("<artifactId>HikariCP</artifactId>\n            <version>4.0.3</version>\n            <scope>compile</scope>"));
+        assert!(pom.contains("<shadedPattern>com.test.libs.hikari</shadedPattern>"));
+
+        // AND the main class should connect and close it
+        let main = fs::read_to_string("testnine/src/main/java/com/test/TestNine.java").unwrap();
+        assert!(main.contains("        database.connect(getFile());\n"));
+        assert!(main.contains("        database.close();\n"));
+
+        // Clean up
+        clean_up("testnine");
+    }
+
+    #[test]
+    fn generate_should_write_java_8_database_layer_before_1_17() {
+        // GIVEN parameters requesting a database for a Java 8 server
+        let parameters = Parameters::from([
+            (String::from("name"), String::from("OldDatabase")),
+            (String::from("version"), String::from("1.16.5")),
+            (String::from("group_id"), String::from("com.test")),
+            (String::from("database"), String::from("sqlite")),
+        ]);
+
+        // WHEN we generate the project
+        SpigotGenerator.generate(&parameters).unwrap();
+
+        // THEN the pom should target Java 8 with a HikariCP release running on it
+        let pom = fs::read_to_string("olddatabase/pom.xml").unwrap();
+        assert!(pom.contains("<maven.compiler.target>8</maven.compiler.target>"));
+        assert!(
+            pom.contains("<artifactId>HikariCP</artifactId>\n            <version>4.0.3</version>")
+        );
+
+        // AND the migration runner should only use Java 8 APIs
+        let runner =
+            fs::read_to_string("olddatabase/src/main/java/com/test/database/MigrationRunner.java")
+                .unwrap();
+        assert!(!runner.contains("readAllBytes"));
+        assert!(!runner.contains("isBlank"));
+
+        // Clean up
+        clean_up("olddatabase");
+    }
+
+    #[test]
+    fn generate_should_record_project_metadata() {
+        // GIVEN parameters for a new project
+        let parameters = Parameters::from([
+            (String::from("name"), String::from("TestTen")),
+            (String::from("version"), String::from("1.21")),
+            (String::from("group_id"), String::from("com.test")),
+        ]);
+
+        // WHEN we generate the project
+        SpigotGenerator.generate(&parameters).unwrap();
+
+        // THEN the metadata should record the parameters and the hash of every file
+        let metadata = ProjectMetadata::read(Path::new("testten")).unwrap();
+        assert_eq!("spigot", metadata.generator);
+        assert_eq!(Some("maven"), metadata.build_system.as_deref());
+        assert_eq!(
+            Some(&String::from("com.test")),
+            metadata.parameters.get("group_id")
+        );
+        assert!(metadata
+            .files
+            .contains_key("src/main/java/com/test/TestTen.java"));
+        assert!(metadata.modified_files(Path::new("testten")).is_empty());
+
+        // Clean up
+        clean_up("testten");
+    }
 }
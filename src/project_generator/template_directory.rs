@@ -0,0 +1,566 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::errors::GeneratorError;
+use crate::project_generator::content_generator::{render_template_files, TemplateFile};
+use crate::project_generator::file_operations::is_contained_path;
+use crate::project_generator::project_info::ProjectInfo;
+use crate::project_generator::project_metadata::ProjectMetadata;
+use crate::project_generator::spigot::SpigotProject;
+use crate::project_generator::template_engine::{render, Context};
+use crate::project_generator::{
+    get_project_type, ParameterDescriptor, Parameters, ProjectGenerator, ProjectGeneratorType,
+    RenderedProject,
+};
+
+pub const MANIFEST_FILE_NAME: &str = "manifest.toml";
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct TemplateManifest {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub version: Option<String>,
+    #[serde(default = "default_changelog")]
+    pub changelog: String,
+    pub extends: Option<String>,
+    #[serde(default = "default_root")]
+    pub root: String,
+    #[serde(default)]
+    pub parameters: Vec<ManifestParameter>,
+    #[serde(default)]
+    pub files: Vec<ManifestFile>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct ManifestParameter {
+    pub name: String,
+    pub prompt: String,
+    #[serde(default)]
+    pub mandatory: bool,
+    pub default: Option<String>,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct ManifestFile {
+    pub path: String,
+    pub template: String,
+    pub when: Option<String>,
+}
+
+fn default_root() -> String {
+    String::from("{{ name | lower }}")
+}
+
+fn default_changelog() -> String {
+    String::from("CHANGELOG.md")
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TemplateDirectory {
+    directory: PathBuf,
+    manifest: TemplateManifest,
+    base: Option<ProjectGeneratorType>,
+}
+
+impl TemplateDirectory {
+    pub fn load(directory: &Path) -> Result<Self, GeneratorError> {
+        let invalid = |message: String| {
+            GeneratorError::InvalidTemplateDirectory(directory.display().to_string(), message)
+        };
+
+        let manifest = fs::read_to_string(directory.join(MANIFEST_FILE_NAME)).map_err(|error| {
+            invalid(format!("unable to read {}: {}", MANIFEST_FILE_NAME, error))
+        })?;
+        let manifest: TemplateManifest =
+            toml::from_str(&manifest).map_err(|error| invalid(error.to_string()))?;
+
+        let base = match &manifest.extends {
+            Some(extends) => Some(
+                get_project_type(extends)
+                    .ok_or_else(|| invalid(format!("unknown base generator '{}'", extends)))?,
+            ),
+            None => None,
+        };
+
+        let paths = std::iter::once(("root", &manifest.root)).chain(
+            manifest
+                .files
+                .iter()
+                .flat_map(|file| [("path", &file.path), ("template", &file.template)]),
+        );
+        for (key, path) in paths {
+            if !is_contained_path(path) {
+                return Err(invalid(format!(
+                    "{} '{}' must be a relative path inside the directory",
+                    key, path
+                )));
+            }
+        }
+
+        for file in &manifest.files {
+            if !directory.join(&file.template).is_file() {
+                return Err(invalid(format!("missing template file {}", file.template)));
+            }
+        }
+
+        Ok(Self {
+            directory: directory.to_path_buf(),
+            manifest,
+            base,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.manifest.name
+    }
+
+    pub fn manifest(&self) -> &TemplateManifest {
+        &self.manifest
+    }
+
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    fn template_files(&self) -> Result<Vec<TemplateFile>, GeneratorError> {
+        self.manifest
+            .files
+            .iter()
+            .map(|file| {
+                let template =
+                    fs::read_to_string(self.directory.join(&file.template)).map_err(|error| {
+                        GeneratorError::InvalidTemplateDirectory(
+                            self.directory.display().to_string(),
+                            format!("unable to read {}: {}", file.template, error),
+                        )
+                    })?;
+                let template_file = TemplateFile::new(&file.path, &template);
+                Ok(match &file.when {
+                    Some(condition) => template_file.when(condition),
+                    None => template_file,
+                })
+            })
+            .collect()
+    }
+
+    /// The values of the manifest parameters, and of every parameter for a standalone directory.
+    /// The values the base generator derives, such as the formatted `name`, are left to it.
+    fn own_context(&self, parameters: &Parameters, base_context: Option<&Context>) -> Context {
+        let mut context = Context::new();
+        if base_context.is_none() {
+            ProjectInfo::from_parameters(parameters).extend_context(&mut context);
+            for (name, value) in parameters {
+                context.insert(name, value.as_str());
+            }
+        }
+        for parameter in &self.manifest.parameters {
+            if base_context.is_some_and(|base| base.get(&parameter.name).is_some()) {
+                continue;
+            }
+            let value = parameters
+                .get(&parameter.name)
+                .cloned()
+                .or_else(|| parameter.default.clone())
+                .unwrap_or_default();
+            context.insert(&parameter.name, value);
+        }
+        context
+    }
+}
+
+/// Replaces the base files sharing a path with an overlay file and appends the others.
+pub fn overlay_template_files(
+    mut base: Vec<TemplateFile>,
+    overlay: Vec<TemplateFile>,
+) -> Vec<TemplateFile> {
+    for file in overlay {
+        match base.iter_mut().find(|existing| existing.path == file.path) {
+            Some(existing) => *existing = file,
+            None => base.push(file),
+        }
+    }
+    base
+}
+
+impl ProjectGenerator for TemplateDirectory {
+    fn parameters(&self) -> Vec<ParameterDescriptor> {
+        let mut parameters = match &self.base {
+            Some(base) => base.generator().parameters(),
+            None => ProjectInfo::parameters(),
+        };
+        for parameter in &self.manifest.parameters {
+            let mut descriptor =
+                ParameterDescriptor::new(&parameter.name, &parameter.prompt, parameter.mandatory);
+            descriptor.default = parameter.default.clone();
+            parameters.retain(|existing| existing.name != descriptor.name);
+            parameters.push(descriptor);
+        }
+        parameters
+    }
+
+    fn validate(&self, parameters: &Parameters) -> Result<(), GeneratorError> {
+        if let Some(base) = &self.base {
+            base.generator().validate(parameters)?;
+        }
+        for parameter in self.manifest.parameters.iter().filter(|p| p.mandatory) {
+            match parameters.get(&parameter.name) {
+                Some(value) if !value.trim().is_empty() => {}
+                _ => return Err(GeneratorError::MissingParameter(parameter.name.clone())),
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_parameter(&self, name: &str, value: &str) -> Result<(), GeneratorError> {
+        match &self.base {
+            Some(base) => base.generator().validate_parameter(name, value),
+            None => ProjectInfo::validate_parameter(name, value),
+        }
+    }
+
+    fn render(&self, parameters: &Parameters) -> Result<RenderedProject, GeneratorError> {
+        let files = self.template_files()?;
+        let metadata = ProjectMetadata::new(self.name(), parameters)
+            .with_template_version(self.manifest.version.as_deref());
+
+        let rendered = match &self.base {
+            Some(ProjectGeneratorType::Spigot) => {
+                let project = SpigotProject::from_parameters(parameters)?;
+                let context = self.own_context(parameters, Some(&project.context()?));
+                let files = overlay_template_files(project.template_files(), files);
+                project.render(&files, &context, metadata)?
+            }
+            None => {
+                let context = self.own_context(parameters, None);
+                RenderedProject {
+                    root: render(&self.manifest.root, &context)?,
+                    files: render_template_files(&files, &context)?,
+                    metadata,
+                }
+            }
+        };
+        rendered.check_paths()?;
+        Ok(rendered)
+    }
+}
+
+/// Lists the template directories found directly under `directory`.
+pub fn find_template_directories(directory: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(directory) else {
+        return Vec::new();
+    };
+    let mut directories: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.join(MANIFEST_FILE_NAME).is_file())
+        .collect();
+    directories.sort();
+    directories
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn write_template_directory(name: &str, manifest: &str, files: &[(&str, &str)]) -> PathBuf {
+        let directory = env::temp_dir().join(format!("mcdev-template-directory-{}", name));
+        let _ = fs::remove_dir_all(&directory);
+        fs::create_dir_all(&directory).expect("Unable to create template directory");
+        fs::write(directory.join(MANIFEST_FILE_NAME), manifest).expect("Unable to write manifest");
+        for (path, content) in files {
+            fs::write(directory.join(path), content).expect("Unable to write template");
+        }
+        directory
+    }
+
+    #[test]
+    fn load_should_fail_without_manifest() {
+        // GIVEN a directory without manifest
+        let directory = env::temp_dir().join("mcdev-template-directory-empty");
+        fs::create_dir_all(&directory).unwrap();
+
+        // WHEN we load it
+        let result = TemplateDirectory::load(&directory);
+
+        // THEN an error should be returned
+        assert!(matches!(
+            result,
+            Err(GeneratorError::InvalidTemplateDirectory(_, _))
+        ));
+    }
+
+    #[test]
+    fn load_should_fail_for_missing_template_file() {
+        // GIVEN a manifest referencing a template that does not exist
+        let directory = write_template_directory(
+            "missing-file",
+            "name = \"broken\"\n[[files]]\npath = \"README.md\"\ntemplate = \"README.md.tmpl\"\n",
+            &[],
+        );
+
+        // WHEN we load it
+        let result = TemplateDirectory::load(&directory);
+
+        // THEN an error should be returned
+        assert!(matches!(
+            result,
+            Err(GeneratorError::InvalidTemplateDirectory(_, message)) if message.contains("README.md.tmpl")
+        ));
+    }
+
+    #[test]
+    fn load_should_fail_for_paths_leaving_the_directory() {
+        // GIVEN manifests with a path, a template and a root leaving their directory
+        let manifests = [
+            "name = \"escape\"\n[[files]]\npath = \"../../escaped.txt\"\ntemplate = \"README.md\"\n",
+            "name = \"escape\"\n[[files]]\npath = \"README.md\"\ntemplate = \"/etc/passwd\"\n",
+            "name = \"escape\"\nroot = \"../{{ name }}\"\n",
+        ];
+
+        for manifest in manifests {
+            let directory = write_template_directory("escape", manifest, &[("README.md", "")]);
+
+            // WHEN we load it
+            let result = TemplateDirectory::load(&directory);
+
+            // THEN an error should be returned
+            assert!(
+                matches!(
+                    result,
+                    Err(GeneratorError::InvalidTemplateDirectory(_, ref message)) if message.contains("relative path")
+                ),
+                "{:?}",
+                result
+            );
+        }
+    }
+
+    #[test]
+    fn render_should_fail_for_rendered_paths_leaving_the_project() {
+        // GIVEN a manifest whose path comes from a parameter
+        let directory = write_template_directory(
+            "rendered-escape",
+            "name = \"escape\"\nroot = \"project\"\n[[files]]\npath = \"{{ target }}/escaped.txt\"\ntemplate = \"README.md\"\n",
+            &[("README.md", "escaped")],
+        );
+        let template_directory = TemplateDirectory::load(&directory).unwrap();
+        let parameters = Parameters::from([(String::from("target"), String::from("../.."))]);
+
+        // WHEN we render it
+        let result = template_directory.render(&parameters);
+
+        // THEN an error should be returned before anything is written
+        assert!(matches!(
+            result,
+            Err(GeneratorError::PathOutsideProject(path)) if path == "../../escaped.txt"
+        ));
+        assert!(!Path::new("project").exists());
+    }
+
+    #[test]
+    fn parameters_should_extend_base_generator_parameters() {
+        // GIVEN a template directory extending spigot
+        let directory = write_template_directory(
+            "parameters",
+            "name = \"company\"\nextends = \"spigot\"\n[[parameters]]\nname = \"metrics_id\"\nprompt = \"bStats id\"\ndefault = \"0\"\n",
+            &[],
+        );
+        let template_directory = TemplateDirectory::load(&directory).unwrap();
+
+        // WHEN we get its parameters
+        let names: Vec<String> = template_directory
+            .parameters()
+            .into_iter()
+            .map(|parameter| parameter.name)
+            .collect();
+
+        // THEN the spigot parameters should be followed by the manifest ones
+        assert_eq!(Some(&String::from("name")), names.first());
+        assert_eq!(Some(&String::from("metrics_id")), names.last());
+    }
+
+    #[test]
+    fn overlay_template_files_should_replace_and_append() {
+        // GIVEN base files and an overlay
+        let base = vec![
+            TemplateFile::new("pom.xml", "base pom"),
+            TemplateFile::new("Main.java", "base main"),
+        ];
+        let overlay = vec![
+            TemplateFile::new("Main.java", "company main"),
+            TemplateFile::new("Metrics.java", "metrics"),
+        ];
+
+        // WHEN we overlay them
+        let files = overlay_template_files(base, overlay);
+
+        // THEN the main file should be replaced and the metrics one appended
+        assert_eq!(
+            vec![
+                TemplateFile::new("pom.xml", "base pom"),
+                TemplateFile::new("Main.java", "company main"),
+                TemplateFile::new("Metrics.java", "metrics"),
+            ],
+            files
+        );
+    }
+
+    #[test]
+    fn generate_should_override_spigot_main_class() {
+        // GIVEN a template directory overriding the spigot main class
+        let directory = write_template_directory(
+            "override",
+            r#"name = "company"
+extends = "spigot"
+
+[[parameters]]
+name = "metrics_id"
+prompt = "bStats id"
+default = "1234"
+
+[[files]]
+path = "src/main/java/{{ group_id | package_path }}/{{ name }}.java"
+template = "Main.java"
+"#,
+            &[(
+                "Main.java",
+                "package {{ group_id }};\npublic class {{ name }} extends CompanyPlugin { int metrics = {{ metrics_id }}; }\n",
+            )],
+        );
+        let template_directory = TemplateDirectory::load(&directory).unwrap();
+        let parameters = Parameters::from([
+            (String::from("name"), String::from("TemplateOverlay")),
+            (String::from("version"), String::from("1.21")),
+            (String::from("group_id"), String::from("com.company")),
+        ]);
+
+        // WHEN we generate the project
+        let result = template_directory.generate(&parameters);
+
+        // THEN the main class should come from the template directory
+        assert!(result.is_ok(), "Project generation failed");
+        let main_class =
+            fs::read_to_string("templateoverlay/src/main/java/com/company/TemplateOverlay.java")
+                .expect("Unable to read main class");
+        assert_eq!(
+            "package com.company;\npublic class TemplateOverlay extends CompanyPlugin { int metrics = 1234; }\n",
+            main_class
+        );
+
+        // AND the other built-in files should still be generated
+        assert!(Path::new("templateoverlay/pom.xml").is_file());
+        assert!(Path::new("templateoverlay/src/main/resources/plugin.yml").is_file());
+
+        // Clean up
+        fs::remove_dir_all("templateoverlay").expect("Unable to remove project folder");
+    }
+
+    #[test]
+    fn render_should_keep_base_names_for_unformatted_name() {
+        // GIVEN a template directory extending spigot and asking for the name again
+        let directory = write_template_directory(
+            "unformatted-name",
+            r#"name = "company"
+extends = "spigot"
+
+[[parameters]]
+name = "name"
+prompt = "Name of the company plugin"
+mandatory = true
+
+[[files]]
+path = "src/main/java/{{ group_id | package_path }}/{{ main_class }}.java"
+template = "Main.java"
+"#,
+            &[(
+                "Main.java",
+                "public class {{ name }} extends CompanyPlugin {}\n",
+            )],
+        );
+        let template_directory = TemplateDirectory::load(&directory).unwrap();
+        let parameters = Parameters::from([
+            (String::from("name"), String::from("company homes")),
+            (String::from("version"), String::from("1.21")),
+            (String::from("group_id"), String::from("com.company")),
+        ]);
+
+        // WHEN we render the project
+        let rendered = template_directory.render(&parameters).unwrap();
+
+        // THEN the main class should be named after its file
+        assert_eq!("companyhomes", rendered.root);
+        let main_class = rendered
+            .files
+            .iter()
+            .find(|(path, _)| path == "src/main/java/com/company/CompanyHomes.java")
+            .map(|(_, content)| content.as_str());
+        assert_eq!(
+            Some("public class CompanyHomes extends CompanyPlugin {}\n"),
+            main_class
+        );
+    }
+
+    #[test]
+    fn generate_should_render_standalone_template_directory() {
+        // GIVEN a standalone template directory
+        let directory = write_template_directory(
+            "standalone",
+            r#"name = "datapack"
+root = "{{ name | snake }}"
+
+[[parameters]]
+name = "name"
+prompt = "Datapack name"
+mandatory = true
+
+[[files]]
+path = "pack.mcmeta"
+template = "pack.mcmeta"
+"#,
+            &[(
+                "pack.mcmeta",
+                "{\"pack\": {\"description\": \"{{ name }}\"}}",
+            )],
+        );
+        let template_directory = TemplateDirectory::load(&directory).unwrap();
+        let parameters = Parameters::from([(String::from("name"), String::from("StandalonePack"))]);
+
+        // WHEN we generate the project
+        let result = template_directory.generate(&parameters);
+
+        // THEN the files should be generated under the rendered root
+        assert!(result.is_ok(), "Project generation failed");
+        let content =
+            fs::read_to_string("standalone_pack/pack.mcmeta").expect("Unable to read pack.mcmeta");
+        assert_eq!("{\"pack\": {\"description\": \"StandalonePack\"}}", content);
+
+        // AND the license should be skipped as none was given
+        assert!(!Path::new("standalone_pack/LICENSE").exists());
+
+        // Clean up
+        fs::remove_dir_all("standalone_pack").expect("Unable to remove project folder");
+    }
+
+    #[test]
+    fn find_template_directories_should_only_list_directories_with_manifest() {
+        // GIVEN a folder with one template directory and one unrelated directory
+        let root = env::temp_dir().join("mcdev-template-directory-find");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("company")).unwrap();
+        fs::create_dir_all(root.join("unrelated")).unwrap();
+        fs::write(
+            root.join("company").join(MANIFEST_FILE_NAME),
+            "name = \"company\"",
+        )
+        .unwrap();
+
+        // WHEN we look for template directories
+        let directories = find_template_directories(&root);
+
+        // THEN only the one with a manifest should be found
+        assert_eq!(vec![root.join("company")], directories);
+    }
+}
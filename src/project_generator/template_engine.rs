@@ -0,0 +1,680 @@
+use std::collections::BTreeMap;
+
+use crate::errors::GeneratorError;
+use crate::project_generator::naming::{camel_case, kebab_case, pascal_case, snake_case};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    String(String),
+    Bool(bool),
+    List(Vec<Value>),
+    Map(BTreeMap<String, Value>),
+}
+
+impl Value {
+    fn is_truthy(&self) -> bool {
+        match self {
+            Value::String(value) => !value.is_empty(),
+            Value::Bool(value) => *value,
+            Value::List(values) => !values.is_empty(),
+            Value::Map(values) => !values.is_empty(),
+        }
+    }
+
+    fn render(&self) -> Result<String, GeneratorError> {
+        match self {
+            Value::String(value) => Ok(value.clone()),
+            Value::Bool(value) => Ok(value.to_string()),
+            Value::List(_) | Value::Map(_) => Err(GeneratorError::TemplateError(String::from(
+                "Unable to render a list or a map, use a for block instead",
+            ))),
+        }
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::String(value.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::String(value)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(values: Vec<T>) -> Self {
+        Value::List(values.into_iter().map(Into::into).collect())
+    }
+}
+
+impl From<BTreeMap<String, Value>> for Value {
+    fn from(values: BTreeMap<String, Value>) -> Self {
+        Value::Map(values)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Context {
+    values: BTreeMap<String, Value>,
+}
+
+impl Context {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert<V: Into<Value>>(&mut self, name: &str, value: V) -> &mut Self {
+        self.values.insert(name.to_string(), value.into());
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        self.values.get(name)
+    }
+
+    /// Evaluates the value like an `if` block would.
+    pub fn is_truthy(&self, path: &str) -> bool {
+        evaluate_condition(path, self)
+    }
+
+    pub fn extend(&mut self, other: &Context) {
+        for (name, value) in &other.values {
+            self.values.insert(name.clone(), value.clone());
+        }
+    }
+
+    fn lookup(&self, path: &str) -> Option<&Value> {
+        let mut segments = path.split('.');
+        let mut value = self.values.get(segments.next()?)?;
+        for segment in segments {
+            value = match value {
+                Value::Map(values) => values.get(segment)?,
+                _ => return None,
+            };
+        }
+        Some(value)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Node {
+    Text(String),
+    Expression(String),
+    If {
+        condition: String,
+        then_branch: Vec<Node>,
+        else_branch: Vec<Node>,
+    },
+    For {
+        variable: String,
+        iterable: String,
+        body: Vec<Node>,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Text(String),
+    Expression(String),
+    Tag(String),
+}
+
+pub fn render(template: &str, context: &Context) -> Result<String, GeneratorError> {
+    let tokens = tokenize(template)?;
+    let mut tokens = tokens.into_iter();
+    let (nodes, end) = parse(&mut tokens, &[])?;
+    if let Some(tag) = end {
+        return Err(template_error(&format!("Unexpected '{{% {} %}}'", tag)));
+    }
+
+    let mut output = String::new();
+    render_nodes(&nodes, &mut context.clone(), &mut output)?;
+    Ok(output)
+}
+
+fn template_error(message: &str) -> GeneratorError {
+    GeneratorError::TemplateError(message.to_string())
+}
+
+fn tokenize(template: &str) -> Result<Vec<Token>, GeneratorError> {
+    let mut tokens = Vec::new();
+    let mut rest = template;
+    let mut rest_at_line_start = true;
+
+    while let Some(start) = find_opening(rest) {
+        let (text, tail) = rest.split_at(start);
+        let is_tag = tail.starts_with("{%");
+        let closing = if is_tag { "%}" } else { "}}" };
+        let end = tail[2..]
+            .find(closing)
+            .ok_or_else(|| template_error(&format!("Missing '{}'", closing)))?;
+        let inner = tail[2..2 + end].trim().to_string();
+        let mut after = &tail[2 + end + 2..];
+        let mut text = text.to_string();
+        let at_line_start = text.contains('\n') || rest_at_line_start;
+        rest_at_line_start = false;
+
+        if is_tag {
+            // A block tag alone on its line does not leave an empty line behind it.
+            let line_start = text.rfind('\n').map(|index| index + 1).unwrap_or(0);
+            let line_end = after.find('\n');
+            let before_is_blank = text[line_start..].chars().all(|c| c == ' ' || c == '\t');
+            let after_is_blank = after[..line_end.unwrap_or(after.len())]
+                .chars()
+                .all(|c| c == ' ' || c == '\t' || c == '\r');
+            if before_is_blank && after_is_blank && at_line_start {
+                text.truncate(line_start);
+                after = &after[line_end.map(|index| index + 1).unwrap_or(after.len())..];
+                rest_at_line_start = true;
+            }
+        }
+
+        if is_tag && inner == "raw" {
+            let end = after
+                .find("{% endraw %}")
+                .ok_or_else(|| template_error("Missing '{% endraw %}'"))?;
+            text.push_str(&after[..end]);
+            tokens.push(Token::Text(text));
+            rest = &after[end + "{% endraw %}".len()..];
+            rest_at_line_start = false;
+            continue;
+        }
+
+        if !text.is_empty() {
+            tokens.push(Token::Text(text));
+        }
+        tokens.push(if is_tag {
+            Token::Tag(inner)
+        } else {
+            Token::Expression(inner)
+        });
+        rest = after;
+    }
+
+    if !rest.is_empty() {
+        tokens.push(Token::Text(rest.to_string()));
+    }
+    Ok(tokens)
+}
+
+fn find_opening(template: &str) -> Option<usize> {
+    match (template.find("{{"), template.find("{%")) {
+        (Some(expression), Some(tag)) => Some(expression.min(tag)),
+        (expression, tag) => expression.or(tag),
+    }
+}
+
+fn parse<I: Iterator<Item = Token>>(
+    tokens: &mut I,
+    terminators: &[&str],
+) -> Result<(Vec<Node>, Option<String>), GeneratorError> {
+    let mut nodes = Vec::new();
+
+    while let Some(token) = tokens.next() {
+        match token {
+            Token::Text(text) => nodes.push(Node::Text(text)),
+            Token::Expression(expression) => nodes.push(Node::Expression(expression)),
+            Token::Tag(tag) => {
+                let keyword = tag.split_whitespace().next().unwrap_or_default();
+                if terminators.contains(&keyword) {
+                    return Ok((nodes, Some(keyword.to_string())));
+                }
+                match keyword {
+                    "if" => nodes.push(parse_if(tokens, &tag)?),
+                    "for" => nodes.push(parse_for(tokens, &tag)?),
+                    _ => return Err(template_error(&format!("Unknown tag '{}'", tag))),
+                }
+            }
+        }
+    }
+
+    Ok((nodes, None))
+}
+
+fn parse_if<I: Iterator<Item = Token>>(tokens: &mut I, tag: &str) -> Result<Node, GeneratorError> {
+    let condition = tag["if".len()..].trim().to_string();
+    let (then_branch, end) = parse(tokens, &["else", "endif"])?;
+    let else_branch = match end.as_deref() {
+        Some("else") => match parse(tokens, &["endif"])? {
+            (nodes, Some(_)) => nodes,
+            (_, None) => return Err(template_error("Missing '{% endif %}'")),
+        },
+        Some(_) => Vec::new(),
+        None => return Err(template_error("Missing '{% endif %}'")),
+    };
+
+    Ok(Node::If {
+        condition,
+        then_branch,
+        else_branch,
+    })
+}
+
+fn parse_for<I: Iterator<Item = Token>>(tokens: &mut I, tag: &str) -> Result<Node, GeneratorError> {
+    let parts: Vec<&str> = tag.split_whitespace().collect();
+    let [_, variable, "in", iterable] = parts.as_slice() else {
+        return Err(template_error(&format!("Malformed tag '{}'", tag)));
+    };
+    let (body, end) = parse(tokens, &["endfor"])?;
+    if end.is_none() {
+        return Err(template_error("Missing '{% endfor %}'"));
+    }
+
+    Ok(Node::For {
+        variable: variable.to_string(),
+        iterable: iterable.to_string(),
+        body,
+    })
+}
+
+fn render_nodes(
+    nodes: &[Node],
+    context: &mut Context,
+    output: &mut String,
+) -> Result<(), GeneratorError> {
+    for node in nodes {
+        match node {
+            Node::Text(text) => output.push_str(text),
+            Node::Expression(expression) => {
+                output.push_str(&evaluate_expression(expression, context)?)
+            }
+            Node::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                if evaluate_condition(condition, context) {
+                    render_nodes(then_branch, context, output)?;
+                } else {
+                    render_nodes(else_branch, context, output)?;
+                }
+            }
+            Node::For {
+                variable,
+                iterable,
+                body,
+            } => {
+                let items = match context.lookup(iterable) {
+                    Some(Value::List(items)) => items.clone(),
+                    Some(_) => {
+                        return Err(template_error(&format!("'{}' is not a list", iterable)))
+                    }
+                    None => Vec::new(),
+                };
+                let previous = (context.get(variable).cloned(), context.get("loop").cloned());
+                for (index, item) in items.iter().enumerate() {
+                    let mut loop_state = BTreeMap::new();
+                    loop_state.insert(String::from("index"), Value::from((index + 1).to_string()));
+                    loop_state.insert(String::from("first"), Value::from(index == 0));
+                    loop_state.insert(String::from("last"), Value::from(index + 1 == items.len()));
+                    context.insert(variable, item.clone());
+                    context.insert("loop", loop_state);
+                    render_nodes(body, context, output)?;
+                }
+                restore(context, variable, previous.0);
+                restore(context, "loop", previous.1);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn restore(context: &mut Context, name: &str, value: Option<Value>) {
+    match value {
+        Some(value) => {
+            context.insert(name, value);
+        }
+        None => {
+            context.values.remove(name);
+        }
+    }
+}
+
+fn evaluate_condition(condition: &str, context: &Context) -> bool {
+    match condition.strip_prefix("not ") {
+        Some(condition) => !evaluate_condition(condition.trim(), context),
+        None => context
+            .lookup(condition)
+            .map(Value::is_truthy)
+            .unwrap_or(false),
+    }
+}
+
+fn evaluate_expression(expression: &str, context: &Context) -> Result<String, GeneratorError> {
+    let mut parts = expression.split('|').map(str::trim);
+    let path = parts.next().unwrap_or_default();
+    let mut value = match path.strip_prefix('"').and_then(|p| p.strip_suffix('"')) {
+        Some(literal) => literal.to_string(),
+        None => context
+            .lookup(path)
+            .ok_or_else(|| template_error(&format!("Undefined variable '{}'", path)))?
+            .render()?,
+    };
+
+    for filter in parts {
+        value = apply_filter(filter, &value)?;
+    }
+    Ok(value)
+}
+
+fn apply_filter(filter: &str, value: &str) -> Result<String, GeneratorError> {
+    match filter {
+        "lower" => Ok(value.to_lowercase()),
+        "upper" => Ok(value.to_uppercase()),
+        "pascal" => Ok(pascal_case(value)),
+        "camel" => Ok(camel_case(value)),
+        "snake" => Ok(snake_case(value)),
+        "kebab" => Ok(kebab_case(value)),
+        "package_path" => Ok(value.replace('.', "/")),
+        "xml" => Ok(value
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+            .replace('\'', "&apos;")),
+        "yaml" => Ok(yaml_scalar(value)),
+        _ => Err(template_error(&format!("Unknown filter '{}'", filter))),
+    }
+}
+
+/// Quotes the value when YAML would not read it back as the same plain string.
+fn yaml_scalar(value: &str) -> String {
+    let plain = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_alphanumeric() || " _.-/@+():#".contains(c))
+        && !value.contains(": ")
+        && !value.contains(" #")
+        && !value.ends_with(':')
+        && !value.starts_with([' ', '-', '@', ':', '#'])
+        && !value.ends_with(' ')
+        && ![
+            "true", "false", "yes", "no", "on", "off", "y", "n", "null", "~",
+        ]
+        .contains(&value.to_lowercase().as_str())
+        && !is_yaml_number(value);
+
+    if plain {
+        return value.to_string();
+    }
+    let escaped: String = value
+        .chars()
+        .map(|c| match c {
+            '"' => String::from("\\\""),
+            '\\' => String::from("\\\\"),
+            '\n' => String::from("\\n"),
+            c => c.to_string(),
+        })
+        .collect();
+    format!("\"{}\"", escaped)
+}
+
+/// Tells whether YAML would read the plain value as a number, in YAML 1.2 or in the YAML 1.1 that
+/// Bukkit reads with SnakeYAML: `1.10`, `.5`, `1e3`, `010`, `0x1F`, `0b101`, `1_000`, `1:30` or `.inf`.
+fn is_yaml_number(value: &str) -> bool {
+    let unsigned = value.strip_prefix(['+', '-']).unwrap_or(value);
+    if [".inf", ".nan"].contains(&unsigned.to_lowercase().as_str()) {
+        return true;
+    }
+    let digits = unsigned.replace('_', "");
+    let all = |text: &str, radix: u32| !text.is_empty() && text.chars().all(|c| c.is_digit(radix));
+    for (prefix, radix) in [("0x", 16), ("0o", 8), ("0b", 2)] {
+        if let Some(rest) = digits.strip_prefix(prefix) {
+            return all(rest, radix);
+        }
+    }
+    let (mantissa, exponent) = match digits.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+        None => (digits.as_str(), None),
+    };
+    if exponent
+        .is_some_and(|exponent| !all(exponent.strip_prefix(['+', '-']).unwrap_or(exponent), 10))
+    {
+        return false;
+    }
+    // Sexagesimal numbers such as `1:30` are integers in YAML 1.1.
+    let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let integer_valid = match integer {
+        "" => mantissa.starts_with('.') && all(fraction, 10),
+        integer => integer.split(':').all(|part| all(part, 10)),
+    };
+    integer_valid && (fraction.is_empty() || all(fraction, 10))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> Context {
+        let mut context = Context::new();
+        context
+            .insert("name", "my-cool_plugin")
+            .insert("group_id", "com.test")
+            .insert("enabled", true)
+            .insert("empty", "")
+            .insert("commands", vec!["home", "spawn"]);
+        context
+    }
+
+    #[test]
+    fn render_should_replace_variables() {
+        // GIVEN a template with variables
+        let template = "package {{ group_id }};";
+
+        // WHEN we render it
+        let result = render(template, &context()).unwrap();
+
+        // THEN the variables should be replaced
+        assert_eq!("package com.test;", result);
+    }
+
+    #[test]
+    fn render_should_keep_single_braces_untouched() {
+        // GIVEN a template containing java code with a literal {name}
+        let template = "String s = \"{name}\"; void run() { }";
+
+        // WHEN we render it
+        let result = render(template, &context()).unwrap();
+
+        // THEN nothing should be replaced
+        assert_eq!(template, result);
+    }
+
+    #[test]
+    fn render_should_apply_filters() {
+        // GIVEN a template using every filter
+        let template = "{{ name | pascal }} {{ name | snake }} {{ name | kebab }} {{ name | camel }} {{ group_id | package_path }} {{ name | upper | lower }}";
+
+        // WHEN we render it
+        let result = render(template, &context()).unwrap();
+
+        // THEN each filter should be applied
+        assert_eq!(
+            "MyCoolPlugin my_cool_plugin my-cool-plugin myCoolPlugin com/test my-cool_plugin",
+            result
+        );
+    }
+
+    #[test]
+    fn render_should_split_camel_case_words() {
+        // GIVEN a pascal case value
+        let mut context = Context::new();
+        context.insert("name", "HomeTeleporter2Plus");
+
+        // WHEN we render it in snake case
+        let result = render("{{ name | snake }}", &context).unwrap();
+
+        // THEN the words should be split on case changes
+        assert_eq!("home_teleporter2_plus", result);
+    }
+
+    #[test]
+    fn render_should_handle_if_else_blocks() {
+        // GIVEN a template with conditions
+        let template = "{% if enabled %}on{% else %}off{% endif %}-{% if empty %}set{% else %}unset{% endif %}-{% if not missing %}absent{% endif %}";
+
+        // WHEN we render it
+        let result = render(template, &context()).unwrap();
+
+        // THEN the right branches should be rendered
+        assert_eq!("on-unset-absent", result);
+    }
+
+    #[test]
+    fn render_should_handle_for_blocks_with_loop_state() {
+        // GIVEN a template iterating over a list
+        let template = "{% for command in commands %}{{ command }}{% if not loop.last %}, {% endif %}{% endfor %}";
+
+        // WHEN we render it
+        let result = render(template, &context()).unwrap();
+
+        // THEN every element should be rendered
+        assert_eq!("home, spawn", result);
+    }
+
+    #[test]
+    fn render_should_drop_lines_only_holding_block_tags() {
+        // GIVEN a multi-line template with block tags on their own lines
+        let template = "commands:\n{% if enabled %}\n{% for command in commands %}\n  {{ command }}:\n{% endfor %}\n{% endif %}\nend";
+
+        // WHEN we render it
+        let result = render(template, &context()).unwrap();
+
+        // THEN no empty line should be left behind
+        assert_eq!("commands:\n  home:\n  spawn:\nend", result);
+    }
+
+    #[test]
+    fn render_should_access_map_fields() {
+        // GIVEN a context with a list of maps
+        let mut command = BTreeMap::new();
+        command.insert(String::from("name"), Value::from("home"));
+        let mut context = Context::new();
+        context.insert("commands", Value::List(vec![Value::Map(command)]));
+
+        // WHEN we render a field of each map
+        let result = render("{% for c in commands %}{{ c.name }}{% endfor %}", &context).unwrap();
+
+        // THEN the field should be rendered
+        assert_eq!("home", result);
+    }
+
+    #[test]
+    fn render_should_escape_raw_blocks_and_literals() {
+        // GIVEN a template with escaped template syntax
+        let template = "{% raw %}{{ name }}{% endraw %} {{ \"{{\" }}";
+
+        // WHEN we render it
+        let result = render(template, &context()).unwrap();
+
+        // THEN the syntax should be kept as is
+        assert_eq!("{{ name }} {{", result);
+    }
+
+    #[test]
+    fn render_should_escape_xml() {
+        // GIVEN a value with xml special characters
+        let mut context = Context::new();
+        context.insert("description", "Tom & Jerry <3");
+
+        // WHEN we render it with the xml filter
+        let result = render("{{ description | xml }}", &context).unwrap();
+
+        // THEN it should be escaped
+        assert_eq!("Tom &amp; Jerry &lt;3", result);
+    }
+
+    #[test]
+    fn render_should_fail_on_undefined_variable() {
+        // GIVEN a template with an unknown variable
+        let template = "{{ unknown }}";
+
+        // WHEN we render it
+        let result = render(template, &context());
+
+        // THEN an error should be returned
+        assert!(matches!(result, Err(GeneratorError::TemplateError(_))));
+    }
+
+    #[test]
+    fn render_should_fail_on_unclosed_block() {
+        // GIVEN a template with an unclosed if block
+        let template = "{% if enabled %}on";
+
+        // WHEN we render it
+        let result = render(template, &context());
+
+        // THEN an error should be returned
+        assert!(matches!(result, Err(GeneratorError::TemplateError(_))));
+    }
+
+    #[test]
+    fn render_should_quote_yaml_when_needed() {
+        // GIVEN values that are not all safe as plain yaml scalars
+        let mut context = Context::new();
+        context
+            .insert("plain", "Notch")
+            .insert("url", "https://example.com/#top")
+            .insert("colon", "A plugin: the best")
+            .insert("boolean", "yes")
+            .insert("quote", "say \"hi\"");
+
+        // WHEN we render them with the yaml filter
+        let result = render(
+            "{{ plain | yaml }} {{ url | yaml }} {{ colon | yaml }} {{ boolean | yaml }} {{ quote | yaml }}",
+            &context,
+        )
+        .unwrap();
+
+        // THEN only the unsafe ones should be quoted
+        assert_eq!(
+            "Notch https://example.com/#top \"A plugin: the best\" \"yes\" \"say \\\"hi\\\"\"",
+            result
+        );
+    }
+
+    #[test]
+    fn yaml_filter_should_quote_numbers_booleans_and_null() {
+        // GIVEN strings YAML would read as numbers, booleans or null, and strings it would not
+        let quoted = [
+            "1.10", "1.0", "42", "-7", "+.5", "1e3", "010", "0x1F", "0b101", "1_000", "1:30",
+            ".inf", "-.Inf", ".NaN", "Off", "Y", "NULL", "~",
+        ];
+        let plain = [
+            "1.21.4",
+            "1.0.0",
+            "v1.10",
+            "1.10-SNAPSHOT",
+            "0xZ",
+            "1e",
+            "12:30pm",
+        ];
+
+        for value in quoted {
+            // WHEN we render them with the yaml filter
+            let mut context = Context::new();
+            context.insert("value", value);
+            let result = render("{{ value | yaml }}", &context).unwrap();
+
+            // THEN the first ones should be quoted, keeping the value a string
+            assert_eq!(format!("\"{}\"", value), result);
+        }
+        for value in plain {
+            let mut context = Context::new();
+            context.insert("value", value);
+            let result = render("{{ value | yaml }}", &context).unwrap();
+
+            // AND the others left plain
+            assert_eq!(value, result);
+        }
+    }
+}
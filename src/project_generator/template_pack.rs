@@ -0,0 +1,407 @@
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use flate2::read::GzDecoder;
+use tar::Archive;
+
+use crate::errors::GeneratorError;
+use crate::project_generator::file_operations::{copy_directory, is_contained_path};
+use crate::project_generator::template_directory::{
+    find_template_directories, TemplateDirectory, MANIFEST_FILE_NAME,
+};
+
+#[derive(Debug, PartialEq)]
+pub struct TemplatePack {
+    pub name: String,
+    pub version: String,
+    pub description: String,
+    pub path: PathBuf,
+}
+
+impl TemplatePack {
+    fn from_directory(template_directory: &TemplateDirectory) -> Result<Self, GeneratorError> {
+        let manifest = template_directory.manifest();
+        let version = manifest.version.clone().ok_or_else(|| {
+            pack_error(&format!("{} has no version in its manifest", manifest.name))
+        })?;
+
+        Ok(Self {
+            name: manifest.name.clone(),
+            version,
+            description: manifest.description.clone(),
+            path: template_directory.directory().to_path_buf(),
+        })
+    }
+
+    pub fn changelog(&self) -> Result<String, GeneratorError> {
+        let template_directory = TemplateDirectory::load(&self.path)?;
+        fs::read_to_string(self.path.join(&template_directory.manifest().changelog))
+            .map_err(|error| pack_error(&format!("unable to read changelog: {}", error)))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum PackSource {
+    Directory(PathBuf),
+    Archive(PathBuf),
+    Git(String),
+}
+
+fn pack_error(message: &str) -> GeneratorError {
+    GeneratorError::TemplatePackError(message.to_string())
+}
+
+fn detect_source(source: &str) -> Result<PackSource, GeneratorError> {
+    // git would read such a source as one of its options
+    if source.starts_with('-') {
+        return Err(pack_error(&format!("{} must not start with '-'", source)));
+    }
+    let path = Path::new(source);
+    let is_archive = [".tar.gz", ".tgz", ".tar"]
+        .iter()
+        .any(|extension| source.ends_with(extension));
+    let is_bare_repository = path.join("HEAD").is_file() && path.join("objects").is_dir();
+
+    if source.contains("://") || source.starts_with("git@") || is_bare_repository {
+        Ok(PackSource::Git(source.to_string()))
+    } else if is_archive && path.is_file() {
+        Ok(PackSource::Archive(path.to_path_buf()))
+    } else if path.is_dir() {
+        Ok(PackSource::Directory(path.to_path_buf()))
+    } else if source.ends_with(".git") {
+        Ok(PackSource::Git(source.to_string()))
+    } else {
+        Err(pack_error(&format!(
+            "{} is not a directory, archive or git repository",
+            source
+        )))
+    }
+}
+
+fn fetch_source(source: &PackSource, staging: &Path) -> Result<(), GeneratorError> {
+    match source {
+        PackSource::Directory(directory) => copy_directory(directory, staging),
+        PackSource::Archive(archive) => {
+            let file = File::open(archive)
+                .map_err(|error| pack_error(&format!("unable to open archive: {}", error)))?;
+            let extracted = if archive.to_string_lossy().ends_with(".tar") {
+                Archive::new(file).unpack(staging)
+            } else {
+                Archive::new(GzDecoder::new(file)).unpack(staging)
+            };
+            extracted.map_err(|error| pack_error(&format!("unable to extract archive: {}", error)))
+        }
+        PackSource::Git(url) => {
+            let output = Command::new("git")
+                .args(["clone", "--quiet", "--depth", "1", "--"])
+                .arg(url)
+                .arg(staging)
+                .output()
+                .map_err(|error| pack_error(&format!("unable to run git: {}", error)))?;
+            if !output.status.success() {
+                return Err(pack_error(&format!(
+                    "git clone failed: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                )));
+            }
+            fs::remove_dir_all(staging.join(".git"))
+                .map_err(|error| pack_error(&format!("unable to remove .git: {}", error)))
+        }
+    }
+}
+
+fn locate_manifest(staging: &Path) -> Result<PathBuf, GeneratorError> {
+    if staging.join(MANIFEST_FILE_NAME).is_file() {
+        return Ok(staging.to_path_buf());
+    }
+    match find_template_directories(staging).as_slice() {
+        [directory] => Ok(directory.clone()),
+        _ => Err(pack_error(&format!("no {} found", MANIFEST_FILE_NAME))),
+    }
+}
+
+fn validate_pack_name(name: &str) -> Result<(), GeneratorError> {
+    let valid = !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.');
+    if valid {
+        Ok(())
+    } else {
+        Err(pack_error(&format!("{} is not a valid pack name", name)))
+    }
+}
+
+/// Installs the pack into `packs_dir`, replacing an already installed pack with the same name.
+pub fn install_template_pack(
+    packs_dir: &Path,
+    source: &str,
+) -> Result<TemplatePack, GeneratorError> {
+    let source = detect_source(source)?;
+    let staging = packs_dir.join(format!(".staging-{}", std::process::id()));
+    remove_staging(&staging)?;
+    fs::create_dir_all(packs_dir).map_err(GeneratorError::DirectoryCreationError)?;
+
+    let installed = fetch_source(&source, &staging).and_then(|_| {
+        let pack_root = locate_manifest(&staging)?;
+        // Loading refuses manifests whose paths leave the pack, the changelog is checked here
+        let template_directory = TemplateDirectory::load(&pack_root)
+            .map_err(|error| pack_error(&format!("refusing the pack: {}", error)))?;
+        let pack = TemplatePack::from_directory(&template_directory)?;
+        validate_pack_name(&pack.name)?;
+        let changelog = &template_directory.manifest().changelog;
+        if !is_contained_path(changelog) {
+            return Err(pack_error(&format!(
+                "refusing the pack: changelog '{}' must be a relative path inside the pack",
+                changelog
+            )));
+        }
+        if !pack_root
+            .join(&template_directory.manifest().changelog)
+            .is_file()
+        {
+            return Err(pack_error(&format!(
+                "{} has no {}",
+                pack.name,
+                template_directory.manifest().changelog
+            )));
+        }
+
+        let destination = packs_dir.join(&pack.name);
+        if destination.exists() {
+            fs::remove_dir_all(&destination).map_err(|error| {
+                pack_error(&format!(
+                    "unable to remove the installed {}: {}",
+                    pack.name, error
+                ))
+            })?;
+        }
+        fs::rename(&pack_root, &destination).map_err(|error| {
+            pack_error(&format!(
+                "unable to move {} into place: {}",
+                pack.name, error
+            ))
+        })?;
+        Ok(TemplatePack {
+            path: destination,
+            ..pack
+        })
+    });
+
+    remove_staging(&staging)?;
+    installed
+}
+
+fn remove_staging(staging: &Path) -> Result<(), GeneratorError> {
+    match staging.exists() {
+        true => fs::remove_dir_all(staging).map_err(|error| {
+            pack_error(&format!(
+                "unable to remove {}: {}",
+                staging.display(),
+                error
+            ))
+        }),
+        false => Ok(()),
+    }
+}
+
+pub fn list_template_packs(packs_dir: &Path) -> Result<Vec<TemplatePack>, GeneratorError> {
+    find_template_directories(packs_dir)
+        .iter()
+        .map(|directory| TemplatePack::from_directory(&TemplateDirectory::load(directory)?))
+        .collect()
+}
+
+pub fn find_template_pack(packs_dir: &Path, name: &str) -> Result<TemplatePack, GeneratorError> {
+    list_template_packs(packs_dir)?
+        .into_iter()
+        .find(|pack| pack.name == name)
+        .ok_or_else(|| pack_error(&format!("{} is not installed", name)))
+}
+
+pub fn remove_template_pack(packs_dir: &Path, name: &str) -> Result<TemplatePack, GeneratorError> {
+    let pack = find_template_pack(packs_dir, name)?;
+    fs::remove_dir_all(&pack.path)
+        .map_err(|error| pack_error(&format!("unable to remove {}: {}", name, error)))?;
+    Ok(pack)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::env;
+
+    const MANIFEST: &str = "name = \"company\"\nversion = \"1.2.0\"\ndescription = \"Company base plugin\"\nextends = \"spigot\"\n";
+
+    fn temp_path(name: &str) -> PathBuf {
+        let path = env::temp_dir().join(format!("mcdev-template-pack-{}", name));
+        let _ = fs::remove_dir_all(&path);
+        path
+    }
+
+    fn write_pack(directory: &Path, manifest: &str) {
+        fs::create_dir_all(directory).unwrap();
+        fs::write(directory.join(MANIFEST_FILE_NAME), manifest).unwrap();
+        fs::write(directory.join("CHANGELOG.md"), "## 1.2.0\n- Add metrics\n").unwrap();
+    }
+
+    fn git(directory: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(["-c", "user.name=test", "-c", "user.email=test@test"])
+            .args(args)
+            .current_dir(directory)
+            .output()
+            .expect("Unable to run git")
+            .status;
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    #[test]
+    fn install_template_pack_should_copy_directory() {
+        // GIVEN a pack in a local directory
+        let source = temp_path("directory-source");
+        write_pack(&source, MANIFEST);
+        let packs_dir = temp_path("directory-cache");
+
+        // WHEN we install it
+        let pack = install_template_pack(&packs_dir, source.to_str().unwrap()).unwrap();
+
+        // THEN it should be listed with its version and changelog
+        assert_eq!("company", pack.name);
+        assert_eq!("1.2.0", pack.version);
+        assert_eq!(vec![pack], list_template_packs(&packs_dir).unwrap());
+        let changelog = find_template_pack(&packs_dir, "company")
+            .unwrap()
+            .changelog()
+            .unwrap();
+        assert!(changelog.contains("Add metrics"));
+    }
+
+    #[test]
+    fn install_template_pack_should_extract_tarball() {
+        // GIVEN a pack archived in a tarball with a top level directory
+        let source = temp_path("tarball-source");
+        write_pack(&source.join("company-pack"), MANIFEST);
+        let archive_path = temp_path("tarball.tar.gz");
+        let encoder = GzEncoder::new(File::create(&archive_path).unwrap(), Compression::default());
+        let mut builder = tar::Builder::new(encoder);
+        builder
+            .append_dir_all("company-pack", source.join("company-pack"))
+            .unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+        let packs_dir = temp_path("tarball-cache");
+
+        // WHEN we install it
+        let pack = install_template_pack(&packs_dir, archive_path.to_str().unwrap()).unwrap();
+
+        // THEN it should be installed under its manifest name
+        assert_eq!(packs_dir.join("company"), pack.path);
+        assert!(pack.path.join(MANIFEST_FILE_NAME).is_file());
+    }
+
+    #[test]
+    fn install_template_pack_should_clone_local_bare_repository() {
+        // GIVEN a pack pushed to a local bare repository
+        let work_tree = temp_path("git-work-tree");
+        write_pack(&work_tree, MANIFEST);
+        git(&work_tree, &["init", "--quiet"]);
+        git(&work_tree, &["add", "."]);
+        git(&work_tree, &["commit", "--quiet", "-m", "Initial pack"]);
+        let bare = temp_path("git-bare.git");
+        git(
+            &work_tree,
+            &["clone", "--quiet", "--bare", ".", bare.to_str().unwrap()],
+        );
+        let packs_dir = temp_path("git-cache");
+
+        // WHEN we install it
+        let pack = install_template_pack(&packs_dir, bare.to_str().unwrap()).unwrap();
+
+        // THEN it should be installed without the git metadata
+        assert_eq!("company", pack.name);
+        assert!(!pack.path.join(".git").exists());
+    }
+
+    #[test]
+    fn install_template_pack_should_reject_pack_without_version() {
+        // GIVEN a pack without version
+        let source = temp_path("no-version-source");
+        write_pack(&source, "name = \"company\"\n");
+        let packs_dir = temp_path("no-version-cache");
+
+        // WHEN we install it
+        let result = install_template_pack(&packs_dir, source.to_str().unwrap());
+
+        // THEN it should be rejected and nothing left in the cache
+        assert!(matches!(result, Err(GeneratorError::TemplatePackError(_))));
+        assert!(list_template_packs(&packs_dir).unwrap().is_empty());
+        assert_eq!(0, fs::read_dir(&packs_dir).unwrap().count());
+    }
+
+    #[test]
+    fn install_template_pack_should_refuse_sources_read_as_git_options() {
+        // GIVEN a source git would read as an option
+        let packs_dir = temp_path("option-cache");
+
+        // WHEN we install it
+        let result = install_template_pack(&packs_dir, "--upload-pack=touch pwned.git");
+
+        // THEN it should be refused before running git
+        assert!(
+            matches!(result, Err(GeneratorError::TemplatePackError(message)) if message.contains("must not start with '-'"))
+        );
+        assert!(!Path::new("pwned").exists());
+    }
+
+    #[test]
+    fn install_template_pack_should_refuse_paths_leaving_the_pack() {
+        // GIVEN packs writing a file or reading their changelog outside of their directory
+        let manifests = [
+            format!(
+                "{}[[files]]\npath = \"../../escaped.txt\"\ntemplate = \"CHANGELOG.md\"\n",
+                MANIFEST
+            ),
+            format!("{}changelog = \"../../CHANGELOG.md\"\n", MANIFEST),
+        ];
+
+        for manifest in manifests {
+            let source = temp_path("escape-source");
+            write_pack(&source, &manifest);
+            let packs_dir = temp_path("escape-cache");
+
+            // WHEN we install it
+            let result = install_template_pack(&packs_dir, source.to_str().unwrap());
+
+            // THEN it should be refused and nothing left in the cache
+            assert!(
+                matches!(result, Err(GeneratorError::TemplatePackError(ref message)) if message.starts_with("refusing the pack")),
+                "{:?}",
+                result
+            );
+            assert_eq!(0, fs::read_dir(&packs_dir).unwrap().count());
+        }
+    }
+
+    #[test]
+    fn remove_template_pack_should_delete_installed_pack() {
+        // GIVEN an installed pack
+        let source = temp_path("remove-source");
+        write_pack(&source, MANIFEST);
+        let packs_dir = temp_path("remove-cache");
+        install_template_pack(&packs_dir, source.to_str().unwrap()).unwrap();
+
+        // WHEN we remove it
+        let removed = remove_template_pack(&packs_dir, "company").unwrap();
+
+        // THEN it should no longer be installed
+        assert!(!removed.path.exists());
+        assert!(matches!(
+            remove_template_pack(&packs_dir, "company"),
+            Err(GeneratorError::TemplatePackError(_))
+        ));
+    }
+}
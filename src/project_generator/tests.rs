@@ -1,5 +1,5 @@
 #[cfg(test)]
-use super::super::*;
+use super::*;
 
 #[test]
 fn get_project_generator_type_should_return_spigot() {
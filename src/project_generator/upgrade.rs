@@ -0,0 +1,215 @@
+use std::fs;
+use std::path::Path;
+
+use crate::errors::GeneratorError;
+use crate::project_generator::build_model::render_gradle;
+use crate::project_generator::file_operations::create_file_with_parents;
+use crate::project_generator::merge::Merge;
+use crate::project_generator::project_metadata::{
+    content_hash, generated_content, ProjectMetadata,
+};
+use crate::project_generator::{Parameters, ProjectGenerator};
+
+/// What happened to a generated file during an upgrade.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FileUpgrade {
+    Unchanged,
+    /// Untouched by the user, replaced by the new template output.
+    Updated,
+    Created,
+    /// Deleted by the user, left deleted.
+    Skipped,
+    /// The user's and the template's changes merged without conflict.
+    Merged,
+    /// Merged with conflict markers.
+    Conflicted(usize),
+    /// The user's lines kept, the template changes written to `<file>.rej`.
+    Rejected(usize),
+}
+
+/// Re-renders the project with its recorded parameters and merges the new output into the user's files.
+/// Conflicts are marked in the files, or written to `.rej` files with `reject`.
+pub fn upgrade_project(
+    root: &Path,
+    generator: &dyn ProjectGenerator,
+    reject: bool,
+) -> Result<Vec<(String, FileUpgrade)>, GeneratorError> {
+    let metadata = ProjectMetadata::read(root)?;
+    let parameters: Parameters = metadata.parameters.clone().into_iter().collect();
+    let mut rendered = generator.render(&parameters)?;
+    if metadata.build_system.as_deref() == Some("gradle") {
+        render_gradle(&mut rendered)?;
+    }
+    rendered.check_paths()?;
+
+    let mut report = Vec::new();
+    for (path, template) in &rendered.files {
+        let file = root.join(path);
+        let upgrade = match fs::read_to_string(&file) {
+            Err(_) if metadata.files.contains_key(path) => FileUpgrade::Skipped,
+            Err(_) => {
+                write(&file, template)?;
+                FileUpgrade::Created
+            }
+            Ok(current) if current == *template => FileUpgrade::Unchanged,
+            Ok(current) if metadata.files.get(path) == Some(&content_hash(&current)) => {
+                write(&file, template)?;
+                FileUpgrade::Updated
+            }
+            Ok(current) => {
+                // Without the generated copy, every difference is a conflict
+                let base = generated_content(root, path).unwrap_or_default();
+                let merge = Merge::new(&base, &current, template);
+                let conflicts = merge.conflicts().len();
+                if conflicts == 0 {
+                    write(&file, &merge.with_markers())?;
+                    FileUpgrade::Merged
+                } else if reject {
+                    write(&file, &merge.keeping_ours())?;
+                    write(&root.join(format!("{}.rej", path)), &merge.rejects())?;
+                    FileUpgrade::Rejected(conflicts)
+                } else {
+                    write(&file, &merge.with_markers())?;
+                    FileUpgrade::Conflicted(conflicts)
+                }
+            }
+        };
+        report.push((path.clone(), upgrade));
+    }
+    rendered.record(root)?;
+    Ok(report)
+}
+
+fn write(path: &Path, content: &str) -> Result<(), GeneratorError> {
+    create_file_with_parents(&path.to_string_lossy(), content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project_generator::build_model::convert_to_gradle;
+    use crate::project_generator::spigot::SpigotGenerator;
+
+    #[test]
+    fn upgrade_project_should_merge_user_changes_with_new_templates() {
+        // GIVEN a generated project whose main class was edited by the user
+        let parameters = Parameters::from([
+            (String::from("name"), String::from("UpgradeMerge")),
+            (String::from("version"), String::from("1.21")),
+            (String::from("group_id"), String::from("com.test")),
+        ]);
+        SpigotGenerator.generate(&parameters).unwrap();
+        let root = Path::new("upgrademerge");
+        let main_path = root.join("src/main/java/com/test/UpgradeMerge.java");
+        let main = fs::read_to_string(&main_path).unwrap();
+        let old_main = main.replace("Goodbye, SpigotMC!", "Bye");
+        fs::write(
+            &main_path,
+            old_main.replace("Hello, SpigotMC!", "Homes enabled"),
+        )
+        .unwrap();
+        fs::write(
+            root.join(".mcdev/generated/src/main/java/com/test/UpgradeMerge.java"),
+            &old_main,
+        )
+        .unwrap();
+
+        // AND an untouched pom generated by an older template
+        let pom = fs::read_to_string(root.join("pom.xml")).unwrap();
+        let old_pom = pom.replace("<packaging>jar</packaging>\n", "");
+        fs::write(root.join("pom.xml"), &old_pom).unwrap();
+        fs::write(root.join(".mcdev/generated/pom.xml"), &old_pom).unwrap();
+        let mut metadata = ProjectMetadata::read(root).unwrap();
+        metadata
+            .files
+            .insert(String::from("pom.xml"), content_hash(&old_pom));
+        metadata.write(root).unwrap();
+
+        // WHEN we upgrade it
+        let report = upgrade_project(root, &SpigotGenerator, false).unwrap();
+
+        // THEN the untouched pom should be updated and the main class merged
+        assert!(report.contains(&(String::from("pom.xml"), FileUpgrade::Updated)));
+        assert!(report.contains(&(
+            String::from("src/main/java/com/test/UpgradeMerge.java"),
+            FileUpgrade::Merged
+        )));
+        let merged = fs::read_to_string(&main_path).unwrap();
+        assert!(merged.contains("Homes enabled"));
+        assert!(merged.contains("Goodbye, SpigotMC!"));
+        assert!(fs::read_to_string(root.join("pom.xml"))
+            .unwrap()
+            .contains("<packaging>jar</packaging>"));
+
+        // AND the metadata should now record the new output
+        let metadata = ProjectMetadata::read(root).unwrap();
+        assert_eq!(
+            vec![String::from("src/main/java/com/test/UpgradeMerge.java")],
+            metadata.modified_files(root)
+        );
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn upgrade_project_should_keep_converted_gradle_build() {
+        // GIVEN a generated project converted to Gradle
+        let parameters = Parameters::from([
+            (String::from("name"), String::from("UpgradeGradle")),
+            (String::from("version"), String::from("1.21")),
+            (String::from("group_id"), String::from("com.test")),
+        ]);
+        SpigotGenerator.generate(&parameters).unwrap();
+        let root = Path::new("upgradegradle");
+        for change in convert_to_gradle(root).unwrap().changes {
+            change.write(root).unwrap();
+        }
+
+        // WHEN we upgrade it
+        let report = upgrade_project(root, &SpigotGenerator, false).unwrap();
+
+        // THEN the Gradle build should be kept and no pom rendered
+        assert!(report.contains(&(String::from("build.gradle.kts"), FileUpgrade::Unchanged)));
+        assert!(report.iter().all(|(path, _)| path != "pom.xml"));
+        assert!(!root.join("pom.xml").exists());
+        let metadata = ProjectMetadata::read(root).unwrap();
+        assert_eq!(Some("gradle"), metadata.build_system.as_deref());
+        assert!(metadata.files.contains_key("settings.gradle.kts"));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn upgrade_project_should_write_rejects_for_conflicts() {
+        // GIVEN a project where the user and the template changed the same line
+        let parameters = Parameters::from([
+            (String::from("name"), String::from("UpgradeReject")),
+            (String::from("version"), String::from("1.21")),
+            (String::from("group_id"), String::from("com.test")),
+        ]);
+        SpigotGenerator.generate(&parameters).unwrap();
+        let root = Path::new("upgradereject");
+        let main_path = root.join("src/main/java/com/test/UpgradeReject.java");
+        let main = fs::read_to_string(&main_path).unwrap();
+        let edited = main.replace("Hello, SpigotMC!", "Homes enabled");
+        fs::write(&main_path, &edited).unwrap();
+        let generated_main =
+            root.join(".mcdev/generated/src/main/java/com/test/UpgradeReject.java");
+        fs::write(&generated_main, main.replace("Hello, SpigotMC!", "Hello!")).unwrap();
+
+        // WHEN we upgrade it with rejects
+        let report = upgrade_project(root, &SpigotGenerator, true).unwrap();
+
+        // THEN the user's line should be kept and the template's one rejected
+        assert!(report.contains(&(
+            String::from("src/main/java/com/test/UpgradeReject.java"),
+            FileUpgrade::Rejected(1)
+        )));
+        assert_eq!(edited, fs::read_to_string(&main_path).unwrap());
+        let rejects =
+            fs::read_to_string(root.join("src/main/java/com/test/UpgradeReject.java.rej")).unwrap();
+        assert!(rejects.contains("+        getLogger().info(\"Hello, SpigotMC!\");\n"));
+
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,498 @@
+use crate::errors::GeneratorError;
+use crate::project_generator::naming::{derived_artifact_id, derived_class_name, derived_mod_id};
+
+const JAVA_RESERVED_WORDS: [&str; 54] = [
+    "abstract",
+    "assert",
+    "boolean",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extends",
+    "false",
+    "final",
+    "finally",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "implements",
+    "import",
+    "instanceof",
+    "int",
+    "interface",
+    "long",
+    "native",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "short",
+    "static",
+    "strictfp",
+    "super",
+    "switch",
+    "synchronized",
+    "this",
+    "throw",
+    "throws",
+    "transient",
+    "true",
+    "try",
+    "void",
+    "volatile",
+    "while",
+    "_",
+];
+
+fn is_reserved_word(segment: &str) -> bool {
+    JAVA_RESERVED_WORDS.contains(&segment)
+}
+
+fn invalid_segment_reason(segment: &str) -> Option<String> {
+    if segment.is_empty() {
+        return Some(String::from("it contains an empty segment"));
+    }
+    if let Some(c) = segment
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || *c == '_'))
+    {
+        return Some(format!("segment '{}' contains '{}'", segment, c));
+    }
+    if segment.starts_with(|c: char| c.is_ascii_digit()) {
+        return Some(format!("segment '{}' starts with a digit", segment));
+    }
+    if is_reserved_word(segment) {
+        return Some(format!("segment '{}' is a reserved Java keyword", segment));
+    }
+    if segment.chars().any(|c| c.is_ascii_uppercase()) {
+        return Some(format!("segment '{}' is not lowercase", segment));
+    }
+    None
+}
+
+/// Checks the group id can be used as a Java package name.
+pub fn validate_group_id(group_id: &str) -> Result<(), GeneratorError> {
+    let reason = if group_id.trim().is_empty() {
+        Some(String::from("it is empty"))
+    } else {
+        group_id.split('.').find_map(invalid_segment_reason)
+    };
+
+    match reason {
+        Some(reason) => Err(GeneratorError::InvalidGroupId {
+            group_id: group_id.to_string(),
+            reason,
+            suggestion: suggest_group_id(group_id),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Turns the group id into the closest valid lowercase Java package name.
+pub fn suggest_group_id(group_id: &str) -> String {
+    let segments: Vec<String> = group_id
+        .to_lowercase()
+        .split('.')
+        .map(|segment| {
+            segment
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect::<String>()
+                .trim_matches('_')
+                .to_string()
+        })
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| match segment {
+            segment if segment.starts_with(|c: char| c.is_ascii_digit()) => {
+                format!("_{}", segment)
+            }
+            segment if is_reserved_word(&segment) => format!("{}_", segment),
+            segment => segment,
+        })
+        .collect();
+
+    if segments.is_empty() {
+        String::from("com.example")
+    } else {
+        segments.join(".")
+    }
+}
+
+pub fn invalid_parameter(
+    name: &str,
+    value: &str,
+    reason: &str,
+    suggestion: String,
+) -> GeneratorError {
+    GeneratorError::InvalidParameter {
+        name: name.to_string(),
+        value: value.to_string(),
+        reason: reason.to_string(),
+        suggestion,
+    }
+}
+
+pub fn validate_java_identifier(name: &str, value: &str) -> Result<(), GeneratorError> {
+    let valid_characters = value.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    let reason = if value.is_empty() {
+        Some("it is empty")
+    } else if !valid_characters {
+        Some("it is not a valid Java identifier")
+    } else if value.starts_with(|c: char| c.is_ascii_digit()) {
+        Some("it starts with a digit")
+    } else if is_reserved_word(value) {
+        Some("it is a reserved Java keyword")
+    } else {
+        None
+    };
+
+    match reason {
+        Some(reason) => Err(invalid_parameter(
+            name,
+            value,
+            reason,
+            derived_class_name(value),
+        )),
+        None => Ok(()),
+    }
+}
+
+pub fn validate_artifact_id(value: &str) -> Result<(), GeneratorError> {
+    let valid = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.');
+    if valid {
+        Ok(())
+    } else {
+        Err(invalid_parameter(
+            "artifact_id",
+            value,
+            "it may only contain letters, digits, '-', '_' and '.'",
+            derived_artifact_id(value),
+        ))
+    }
+}
+
+pub fn validate_plugin_name(value: &str) -> Result<(), GeneratorError> {
+    let valid = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.');
+    if valid {
+        Ok(())
+    } else {
+        Err(invalid_parameter(
+            "plugin_name",
+            value,
+            "it may only contain letters, digits, '-', '_' and '.'",
+            derived_class_name(value),
+        ))
+    }
+}
+
+/// Names other plugins are referred to by in `depend`, `softdepend` and `loadbefore`, which Bukkit
+/// allows to contain spaces.
+pub fn validate_bukkit_plugin_name(name: &str, value: &str) -> Result<(), GeneratorError> {
+    let allowed = |c: char| c.is_ascii_alphanumeric() || matches!(c, ' ' | '_' | '.' | '-');
+    if !value.is_empty() && value.chars().all(allowed) {
+        Ok(())
+    } else {
+        Err(invalid_parameter(
+            name,
+            value,
+            "it must match ^[A-Za-z0-9 _.-]+$",
+            value.chars().filter(|c| allowed(*c)).collect(),
+        ))
+    }
+}
+
+pub fn validate_mod_id(value: &str) -> Result<(), GeneratorError> {
+    let valid = (2..=64).contains(&value.len())
+        && value.starts_with(|c: char| c.is_ascii_lowercase())
+        && value
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(invalid_parameter(
+            "mod_id",
+            value,
+            "it must be 2 to 64 lowercase letters, digits or '_' starting with a letter",
+            derived_mod_id(value),
+        ))
+    }
+}
+
+pub fn validate_project_version(value: &str) -> Result<(), GeneratorError> {
+    if !value.is_empty() && !value.contains(char::is_whitespace) {
+        Ok(())
+    } else {
+        Err(invalid_parameter(
+            "project_version",
+            value,
+            "it must not be empty nor contain spaces",
+            value.split_whitespace().collect::<Vec<_>>().join("-"),
+        ))
+    }
+}
+
+pub fn validate_website(value: &str) -> Result<(), GeneratorError> {
+    if value.starts_with("http://") || value.starts_with("https://") {
+        Ok(())
+    } else {
+        Err(invalid_parameter(
+            "website",
+            value,
+            "it must be an http or https url",
+            format!("https://{}", value),
+        ))
+    }
+}
+
+pub fn validate_license(value: &str) -> Result<(), GeneratorError> {
+    let valid = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.' || c == '+');
+    if valid {
+        Ok(())
+    } else {
+        Err(invalid_parameter(
+            "license",
+            value,
+            "it must be an SPDX license identifier",
+            value
+                .chars()
+                .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '.' || *c == '+')
+                .collect(),
+        ))
+    }
+}
+
+/// Bukkit command names are matched case-insensitively and may not contain spaces or ':'.
+pub fn validate_command_name(value: &str) -> Result<(), GeneratorError> {
+    let valid = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(invalid_parameter(
+            "command",
+            value,
+            "it may only contain lowercase letters, digits, '-' and '_'",
+            value
+                .to_lowercase()
+                .chars()
+                .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+                .collect(),
+        ))
+    }
+}
+
+/// Permission nodes are dot separated, '*' standing for every node below its parent.
+pub fn validate_permission_name(value: &str) -> Result<(), GeneratorError> {
+    let valid_char = |c: char| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | '*');
+    let valid = !value.is_empty()
+        && value.chars().all(valid_char)
+        && value.split('.').all(|node| !node.is_empty());
+    if valid {
+        Ok(())
+    } else {
+        Err(invalid_parameter(
+            "permission",
+            value,
+            "it must be dot separated nodes of letters, digits, '-', '_' or '*'",
+            value
+                .to_lowercase()
+                .chars()
+                .map(|c| {
+                    if c.is_whitespace() || c == ':' {
+                        '.'
+                    } else {
+                        c
+                    }
+                })
+                .filter(|c| valid_char(*c))
+                .collect::<String>()
+                .split('.')
+                .filter(|node| !node.is_empty())
+                .collect::<Vec<_>>()
+                .join("."),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invalid_reason(group_id: &str) -> String {
+        match validate_group_id(group_id) {
+            Err(GeneratorError::InvalidGroupId { reason, .. }) => reason,
+            other => panic!("Expected an invalid group id, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn validate_group_id_should_accept_conventional_package() {
+        // GIVEN a conventional group id
+        let group_id = "com.my_org.plugin2";
+
+        // WHEN we validate it
+        let result = validate_group_id(group_id);
+
+        // THEN it should be valid
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn validate_group_id_should_reject_reserved_keyword() {
+        // GIVEN a group id containing a java keyword
+        let group_id = "com.class";
+
+        // WHEN we validate it
+        let reason = invalid_reason(group_id);
+
+        // THEN the keyword should be reported
+        assert_eq!("segment 'class' is a reserved Java keyword", reason);
+    }
+
+    #[test]
+    fn validate_group_id_should_reject_leading_digit() {
+        // GIVEN a group id with a segment starting with a digit
+        let group_id = "com.1org";
+
+        // WHEN we validate it
+        let reason = invalid_reason(group_id);
+
+        // THEN the segment should be reported
+        assert_eq!("segment '1org' starts with a digit", reason);
+    }
+
+    #[test]
+    fn validate_group_id_should_reject_invalid_characters_and_case() {
+        // GIVEN group ids with uppercase letters, dashes and empty segments
+        // WHEN we validate them
+        // THEN the first issue should be reported
+        assert_eq!(
+            "segment 'Com' is not lowercase",
+            invalid_reason("Com.my_org")
+        );
+        assert_eq!(
+            "segment 'My-Org' contains '-'",
+            invalid_reason("com.My-Org")
+        );
+        assert_eq!("it contains an empty segment", invalid_reason("com..test"));
+        assert_eq!("it is empty", invalid_reason(" "));
+    }
+
+    #[test]
+    fn validate_group_id_should_suggest_a_correction() {
+        // GIVEN an invalid group id
+        let group_id = "Com.My-Org";
+
+        // WHEN we validate it
+        let result = validate_group_id(group_id);
+
+        // THEN the error should suggest a valid group id
+        assert!(matches!(
+            result,
+            Err(GeneratorError::InvalidGroupId { suggestion, .. }) if suggestion == "com.my_org"
+        ));
+    }
+
+    #[test]
+    fn suggest_group_id_should_fix_every_rule() {
+        // GIVEN invalid group ids
+        // WHEN we ask for suggestions
+        // THEN the suggestions should be valid package names
+        assert_eq!("com.class_", suggest_group_id("com.class"));
+        assert_eq!("com._1org", suggest_group_id("com.1org"));
+        assert_eq!("com.test", suggest_group_id(".com..test."));
+        assert_eq!("com.example", suggest_group_id("--"));
+        assert!(validate_group_id(&suggest_group_id("Org.Some Team.Plugin!")).is_ok());
+    }
+
+    #[test]
+    fn validate_java_identifier_should_reject_invalid_class_names() {
+        // GIVEN invalid class names
+        // WHEN we validate them
+        // THEN they should be rejected with a suggestion
+        assert!(validate_java_identifier("main_class", "MyPlugin").is_ok());
+        assert!(matches!(
+            validate_java_identifier("main_class", "my-plugin"),
+            Err(GeneratorError::InvalidParameter { suggestion, .. }) if suggestion == "MyPlugin"
+        ));
+        assert!(validate_java_identifier("main_class", "2Fast").is_err());
+        assert!(validate_java_identifier("main_class", "enum").is_err());
+    }
+
+    #[test]
+    fn validate_artifact_id_and_plugin_name_should_reject_spaces() {
+        // GIVEN names containing spaces
+        // WHEN we validate them
+        // THEN they should be rejected with a suggestion
+        assert!(validate_artifact_id("my-plugin").is_ok());
+        assert!(matches!(
+            validate_artifact_id("My Plugin"),
+            Err(GeneratorError::InvalidParameter { suggestion, .. }) if suggestion == "my-plugin"
+        ));
+        assert!(validate_plugin_name("My_Plugin.v2").is_ok());
+        assert!(matches!(
+            validate_plugin_name("My Plugin"),
+            Err(GeneratorError::InvalidParameter { suggestion, .. }) if suggestion == "MyPlugin"
+        ));
+    }
+
+    #[test]
+    fn validate_mod_id_should_follow_mod_loader_rules() {
+        // GIVEN mod ids
+        // WHEN we validate them
+        // THEN only lowercase ids starting with a letter should be accepted
+        assert!(validate_mod_id("my_mod2").is_ok());
+        assert!(validate_mod_id("a").is_err());
+        assert!(matches!(
+            validate_mod_id("MyMod"),
+            Err(GeneratorError::InvalidParameter { suggestion, .. }) if suggestion == "my_mod"
+        ));
+        assert!(matches!(
+            validate_mod_id("2mod"),
+            Err(GeneratorError::InvalidParameter { suggestion, .. }) if suggestion == "mod_2mod"
+        ));
+    }
+
+    #[test]
+    fn validate_project_metadata_should_reject_malformed_values() {
+        // GIVEN project metadata values
+        // WHEN we validate them
+        // THEN only well formed ones should be accepted
+        assert!(validate_project_version("1.0.0-SNAPSHOT").is_ok());
+        assert!(validate_project_version("1.0 beta").is_err());
+        assert!(validate_website("https://example.com").is_ok());
+        assert!(matches!(
+            validate_website("example.com"),
+            Err(GeneratorError::InvalidParameter { suggestion, .. }) if suggestion == "https://example.com"
+        ));
+        assert!(validate_license("Apache-2.0").is_ok());
+        assert!(validate_license("GPL-3.0-or-later").is_ok());
+        assert!(validate_license("MIT License").is_err());
+    }
+}
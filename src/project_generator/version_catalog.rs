@@ -0,0 +1,120 @@
+use crate::errors::GeneratorError;
+use crate::project_generator::validation::invalid_parameter;
+
+/// What a Minecraft release requires from the projects built against it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MinecraftRelease {
+    /// The first version sharing these requirements.
+    pub version: &'static str,
+    pub java_version: u32,
+    /// The data pack format, data packs appearing in 1.13.
+    pub pack_format: Option<u32>,
+}
+
+impl MinecraftRelease {
+    const fn new(version: &'static str, java_version: u32, pack_format: Option<u32>) -> Self {
+        Self {
+            version,
+            java_version,
+            pack_format,
+        }
+    }
+}
+
+/// Sorted from the oldest release, a version using the requirements of the latest release not newer than it.
+pub const RELEASES: &[MinecraftRelease] = &[
+    MinecraftRelease::new("1.8", 8, None),
+    MinecraftRelease::new("1.13", 8, Some(4)),
+    MinecraftRelease::new("1.15", 8, Some(5)),
+    MinecraftRelease::new("1.16.2", 8, Some(6)),
+    MinecraftRelease::new("1.17", 16, Some(7)),
+    MinecraftRelease::new("1.18", 17, Some(8)),
+    MinecraftRelease::new("1.18.2", 17, Some(9)),
+    MinecraftRelease::new("1.19", 17, Some(10)),
+    MinecraftRelease::new("1.19.4", 17, Some(12)),
+    MinecraftRelease::new("1.20", 17, Some(15)),
+    MinecraftRelease::new("1.20.2", 17, Some(18)),
+    MinecraftRelease::new("1.20.3", 17, Some(26)),
+    MinecraftRelease::new("1.20.5", 21, Some(41)),
+    MinecraftRelease::new("1.21", 21, Some(48)),
+    MinecraftRelease::new("1.21.2", 21, Some(57)),
+    MinecraftRelease::new("1.21.4", 21, Some(61)),
+    MinecraftRelease::new("1.21.5", 21, Some(71)),
+];
+
+/// The Java version of generated projects whose Minecraft version is unknown to the catalog.
+pub const DEFAULT_JAVA_VERSION: u32 = 21;
+
+/// Finds the requirements of a `1.21.4` like version, refusing versions the catalog cannot vouch for.
+pub fn find_release(version: &str) -> Result<&'static MinecraftRelease, GeneratorError> {
+    let latest = RELEASES[RELEASES.len() - 1];
+    let invalid = |reason: &str| {
+        invalid_parameter(
+            "Minecraft version",
+            version,
+            reason,
+            latest.version.to_string(),
+        )
+    };
+    let parsed = parse_version(version).ok_or_else(|| invalid("it must look like 1.21.4"))?;
+    if Some(parsed) > parse_version(latest.version) {
+        return Err(invalid("it is newer than the version catalog"));
+    }
+    RELEASES
+        .iter()
+        .rev()
+        .find(|release| parse_version(release.version).is_some_and(|release| release <= parsed))
+        .ok_or_else(|| invalid("it is older than 1.8"))
+}
+
+/// The Java version targeted by projects built against the Minecraft version.
+pub fn java_version(version: &str) -> u32 {
+    find_release(version).map_or(DEFAULT_JAVA_VERSION, |release| release.java_version)
+}
+
+pub fn parse_version(version: &str) -> Option<(u32, u32, u32)> {
+    let mut parts = version.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    let patch = match parts.next() {
+        Some(patch) => patch.parse().ok()?,
+        None => 0,
+    };
+    match parts.next() {
+        Some(_) => None,
+        None => Some((major, minor, patch)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_release_should_use_latest_release_not_newer() {
+        // GIVEN versions between catalog entries
+        // WHEN we look for their requirements
+        let patch = find_release("1.20.6").unwrap();
+        let exact = find_release("1.21.4").unwrap();
+
+        // THEN the closest older entry should be used
+        assert_eq!(Some(41), patch.pack_format);
+        assert_eq!(21, patch.java_version);
+        assert_eq!(Some(61), exact.pack_format);
+        assert_eq!(8, java_version("1.12.2"));
+        assert_eq!(17, java_version("1.20.4"));
+    }
+
+    #[test]
+    fn find_release_should_refuse_unknown_versions() {
+        // GIVEN malformed and too new versions
+        // WHEN we look for them
+        // THEN they should be refused with the latest known release as suggestion
+        assert_eq!(
+            Some("1.21.5"),
+            find_release("1.21-R0.1").unwrap_err().suggestion()
+        );
+        assert!(find_release("1.22").is_err());
+        assert!(find_release("1.7.10").is_err());
+    }
+}
@@ -0,0 +1,335 @@
+use std::fs;
+use std::path::Path;
+
+use crate::errors::GeneratorError;
+use crate::project_generator::existing_project::{PLUGIN_YML_PATH, POM_PATH};
+use crate::project_generator::file_change::FileChange;
+use crate::project_generator::plugin_descriptor::default_api_version;
+use crate::project_generator::project_metadata::METADATA_FILE_NAME;
+use crate::project_generator::version_catalog::{find_release, MinecraftRelease};
+
+const SNAPSHOT_SUFFIX: &str = "-R0.1-SNAPSHOT";
+
+type Editor = fn(&str, &str, &MinecraftRelease) -> String;
+
+/// The files mentioning the Minecraft version and how to update each of them.
+const EDITORS: &[(&str, Editor)] = &[
+    (POM_PATH, edit_pom),
+    ("build.gradle.kts", edit_gradle_build),
+    ("build.gradle", edit_gradle_build),
+    ("gradle.properties", edit_gradle_properties),
+    (PLUGIN_YML_PATH, edit_plugin_yml),
+    ("src/main/resources/paper-plugin.yml", edit_plugin_yml),
+    ("src/main/resources/fabric.mod.json", edit_fabric_mod_json),
+    ("src/main/resources/META-INF/mods.toml", edit_mods_toml),
+    (
+        "src/main/resources/META-INF/neoforge.mods.toml",
+        edit_mods_toml,
+    ),
+    ("pack.mcmeta", edit_pack_mcmeta),
+    ("src/main/resources/pack.mcmeta", edit_pack_mcmeta),
+    (METADATA_FILE_NAME, edit_metadata),
+];
+
+/// Computes the changes moving the project to the Minecraft version, without writing them.
+pub fn set_minecraft_version(
+    root: &Path,
+    version: &str,
+) -> Result<Vec<FileChange>, GeneratorError> {
+    let release = find_release(version)?;
+    let mut found = false;
+    let mut changes = Vec::new();
+    for (path, edit) in EDITORS {
+        let Ok(before) = fs::read_to_string(root.join(path)) else {
+            continue;
+        };
+        found = true;
+        let after = edit(&before, version, release);
+        if after != before {
+            changes.push(FileChange::edited(path, before, after));
+        }
+    }
+    if !found {
+        return Err(GeneratorError::InvalidProject(
+            root.display().to_string(),
+            String::from(
+                "no pom.xml, Gradle build, plugin.yml, fabric.mod.json or mods.toml found",
+            ),
+        ));
+    }
+    Ok(changes)
+}
+
+/// The server API versions, like `1.21-R0.1-SNAPSHOT`, and the compiler Java version.
+fn edit_pom(content: &str, version: &str, release: &MinecraftRelease) -> String {
+    edit_lines(content, |line| {
+        let trimmed = line.trim_start();
+        let java_element = ["source", "target", "release"]
+            .iter()
+            .any(|name| trimmed.starts_with(&format!("<maven.compiler.{}>", name)));
+        if java_element {
+            replace_element_value(line, &release.java_version.to_string())
+        } else {
+            replace_snapshot_versions(line, version)
+        }
+    })
+}
+
+fn edit_gradle_build(content: &str, version: &str, release: &MinecraftRelease) -> String {
+    let java = release.java_version.to_string();
+    let java_constant = match release.java_version {
+        8 => String::from("1_8"),
+        java_version => java_version.to_string(),
+    };
+    edit_lines(content, |line| {
+        let line = replace_snapshot_versions(line, version);
+        let line = replace_after(&line, "JavaLanguageVersion.of(", &java, |c| {
+            c.is_ascii_digit()
+        });
+        let line = replace_after(&line, "JavaVersion.VERSION_", &java_constant, |c| {
+            c.is_ascii_digit() || c == '_'
+        });
+        let line = replace_after(&line, "release.set(", &java, |c| c.is_ascii_digit());
+        replace_after(&line, "release = ", &java, |c| c.is_ascii_digit())
+    })
+}
+
+fn edit_gradle_properties(content: &str, version: &str, _: &MinecraftRelease) -> String {
+    edit_lines(content, |line| match line.split_once('=') {
+        Some((key, _)) if key.trim() == "minecraft_version" => {
+            format!("{}={}", key, version)
+        }
+        _ => line.to_string(),
+    })
+}
+
+/// Updates the `api-version`, adding it from 1.13 and removing it before.
+fn edit_plugin_yml(content: &str, version: &str, _: &MinecraftRelease) -> String {
+    let api_version = default_api_version(version);
+    let line = api_version
+        .as_ref()
+        .map(|api_version| format!("api-version: '{}'", api_version));
+    if !content.lines().any(|l| l.starts_with("api-version:")) {
+        return match line {
+            Some(line) if content.ends_with('\n') || content.is_empty() => {
+                format!("{}{}\n", content, line)
+            }
+            Some(line) => format!("{}\n{}\n", content, line),
+            None => content.to_string(),
+        };
+    }
+    content
+        .split_inclusive('\n')
+        .filter_map(|l| match l.starts_with("api-version:") {
+            true => line
+                .as_ref()
+                .map(|line| format!("{}{}", line, line_ending(l))),
+            false => Some(l.to_string()),
+        })
+        .collect()
+}
+
+fn edit_fabric_mod_json(content: &str, version: &str, _: &MinecraftRelease) -> String {
+    edit_lines(content, |line| match line.split_once("\"minecraft\":") {
+        Some((key, value)) if value.trim_start().starts_with('"') => {
+            let comma = match value.trim_end().ends_with(',') {
+                true => ",",
+                false => "",
+            };
+            format!("{}\"minecraft\": \"~{}\"{}", key, version, comma)
+        }
+        _ => line.to_string(),
+    })
+}
+
+/// Updates the `versionRange` of the `minecraft` dependency blocks.
+fn edit_mods_toml(content: &str, version: &str, _: &MinecraftRelease) -> String {
+    let mut in_minecraft = false;
+    edit_lines(content, |line| {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            in_minecraft = false;
+        } else if trimmed.replace(' ', "") == "modId=\"minecraft\"" {
+            in_minecraft = true;
+        } else if in_minecraft && trimmed.starts_with("versionRange") {
+            let indent = &line[..line.len() - line.trim_start().len()];
+            return format!("{}versionRange=\"[{}]\"", indent, version);
+        }
+        line.to_string()
+    })
+}
+
+fn edit_pack_mcmeta(content: &str, _: &str, release: &MinecraftRelease) -> String {
+    match release.pack_format {
+        Some(pack_format) => edit_lines(content, |line| {
+            replace_after(line, "\"pack_format\": ", &pack_format.to_string(), |c| {
+                c.is_ascii_digit()
+            })
+        }),
+        None => content.to_string(),
+    }
+}
+
+/// Keeps the recorded `version` parameter in line, so upgrades render for the new version.
+fn edit_metadata(content: &str, version: &str, _: &MinecraftRelease) -> String {
+    let mut in_parameters = false;
+    edit_lines(content, |line| {
+        if line.starts_with('[') {
+            in_parameters = line.trim() == "[parameters]";
+        } else if in_parameters
+            && line.split_once('=').map(|(key, _)| key.trim()) == Some("version")
+        {
+            return format!("version = \"{}\"", version);
+        }
+        line.to_string()
+    })
+}
+
+/// Applies the edit to every line, keeping the line endings.
+fn edit_lines(content: &str, mut edit: impl FnMut(&str) -> String) -> String {
+    content
+        .split_inclusive('\n')
+        .map(|line| {
+            let ending = line_ending(line);
+            format!("{}{}", edit(&line[..line.len() - ending.len()]), ending)
+        })
+        .collect()
+}
+
+fn line_ending(line: &str) -> &str {
+    if line.ends_with("\r\n") {
+        "\r\n"
+    } else if line.ends_with('\n') {
+        "\n"
+    } else {
+        ""
+    }
+}
+
+/// `<name>old</name>` to `<name>value</name>`.
+fn replace_element_value(line: &str, value: &str) -> String {
+    match (line.find('>'), line.rfind("</")) {
+        (Some(start), Some(end)) if start < end => {
+            format!("{}{}{}", &line[..=start], value, &line[end..])
+        }
+        _ => line.to_string(),
+    }
+}
+
+/// `1.20.4-R0.1-SNAPSHOT` to `<version>-R0.1-SNAPSHOT`.
+fn replace_snapshot_versions(line: &str, version: &str) -> String {
+    let mut result = String::new();
+    let mut rest = line;
+    while let Some(index) = rest.find(SNAPSHOT_SUFFIX) {
+        let start = rest[..index]
+            .rfind(|c: char| !c.is_ascii_digit() && c != '.')
+            .map_or(0, |start| start + 1);
+        if start < index {
+            result.push_str(&rest[..start]);
+            result.push_str(version);
+        } else {
+            result.push_str(&rest[..index]);
+        }
+        result.push_str(SNAPSHOT_SUFFIX);
+        rest = &rest[index + SNAPSHOT_SUFFIX.len()..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Replaces the characters accepted by `is_value` following `marker`.
+fn replace_after(line: &str, marker: &str, value: &str, is_value: fn(char) -> bool) -> String {
+    let Some(index) = line.find(marker) else {
+        return line.to_string();
+    };
+    let start = index + marker.len();
+    let end = line[start..]
+        .find(|c: char| !is_value(c))
+        .map_or(line.len(), |end| start + end);
+    if start == end {
+        return line.to_string();
+    }
+    format!("{}{}{}", &line[..start], value, &line[end..])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project_generator::file_operations::create_file_with_parents;
+    use std::env;
+
+    #[test]
+    fn set_minecraft_version_should_update_every_version_consistently() {
+        // GIVEN a 1.20.4 project with a Maven and a Gradle build, a plugin.yml and a data pack
+        let root = env::temp_dir().join("mcdev-set-version");
+        let _ = fs::remove_dir_all(&root);
+        let files = [
+            (
+                POM_PATH,
+                "    <properties>\n        <maven.compiler.target>17</maven.compiler.target>\n        <maven.compiler.source>17</maven.compiler.source>\n        <spigot.version>1.20.4-R0.1-SNAPSHOT</spigot.version>\n    </properties>\n",
+            ),
+            (
+                "build.gradle.kts",
+                "dependencies {\n    compileOnly(\"io.papermc.paper:paper-api:1.20.4-R0.1-SNAPSHOT\")\n}\njava.toolchain.languageVersion.set(JavaLanguageVersion.of(17))\n",
+            ),
+            (
+                PLUGIN_YML_PATH,
+                "name: Homes\nmain: com.test.Homes\napi-version: '1.20'\n",
+            ),
+            ("pack.mcmeta", "{\n  \"pack\": {\n    \"pack_format\": 26\n  }\n}\n"),
+            (METADATA_FILE_NAME, "generator = \"spigot\"\n\n[parameters]\nname = \"Homes\"\nversion = \"1.20.4\"\n"),
+        ];
+        for (path, content) in files {
+            create_file_with_parents(&root.join(path).to_string_lossy(), content).unwrap();
+        }
+
+        // WHEN we move it to 1.21.4
+        let changes = set_minecraft_version(&root, "1.21.4").unwrap();
+
+        // THEN every file should target 1.21.4 and Java 21
+        let after = |path: &str| {
+            changes
+                .iter()
+                .find(|change| change.path == path)
+                .and_then(|change| change.after.clone())
+                .unwrap()
+        };
+        assert_eq!(
+            "    <properties>\n        <maven.compiler.target>21</maven.compiler.target>\n        <maven.compiler.source>21</maven.compiler.source>\n        <spigot.version>1.21.4-R0.1-SNAPSHOT</spigot.version>\n    </properties>\n",
+            after(POM_PATH)
+        );
+        assert_eq!(
+            "dependencies {\n    compileOnly(\"io.papermc.paper:paper-api:1.21.4-R0.1-SNAPSHOT\")\n}\njava.toolchain.languageVersion.set(JavaLanguageVersion.of(21))\n",
+            after("build.gradle.kts")
+        );
+        assert!(after(PLUGIN_YML_PATH).ends_with("api-version: '1.21'\n"));
+        assert!(after("pack.mcmeta").contains("\"pack_format\": 61\n"));
+        assert!(after(METADATA_FILE_NAME).ends_with("version = \"1.21.4\"\n"));
+
+        // AND the diff should only show the changed lines
+        let pom_diff = changes[0].diff();
+        assert!(pom_diff.starts_with("--- pom.xml\n@@ line 2 @@\n"));
+        assert!(
+            pom_diff.contains("-        <spigot.version>1.20.4-R0.1-SNAPSHOT</spigot.version>\n")
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn set_minecraft_version_should_update_mod_descriptors() {
+        // GIVEN a Fabric and a NeoForge mod descriptor
+        let release = find_release("1.21.4").unwrap();
+        let fabric = "{\n  \"depends\": {\n    \"fabricloader\": \">=0.16\",\n    \"minecraft\": \"~1.21\",\n    \"java\": \">=21\"\n  }\n}\n";
+        let mods_toml = "[[dependencies.homes]]\nmodId=\"neoforge\"\nversionRange=\"[21,)\"\n[[dependencies.homes]]\nmodId=\"minecraft\"\nversionRange=\"[1.21,1.21.1)\"\n";
+
+        // WHEN we update them
+        // THEN only the minecraft dependency should change
+        assert!(edit_fabric_mod_json(fabric, "1.21.4", release)
+            .contains("    \"minecraft\": \"~1.21.4\",\n    \"java\""));
+        assert_eq!(
+            "[[dependencies.homes]]\nmodId=\"neoforge\"\nversionRange=\"[21,)\"\n[[dependencies.homes]]\nmodId=\"minecraft\"\nversionRange=\"[1.21.4]\"\n",
+            edit_mods_toml(mods_toml, "1.21.4", release)
+        );
+    }
+}